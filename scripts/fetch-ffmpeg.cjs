@@ -0,0 +1,64 @@
+#!/usr/bin/env node
+// Install the ffmpeg sidecar declared in tauri.conf.json (bundle.externalBin: binaries/ffmpeg).
+// Tauri expects it at src-tauri/binaries/ffmpeg-<target-triple>[.exe].
+//
+// Source, in order: $FFMPEG_PATH, then `ffmpeg` on PATH. Use a static build when bundling
+// for release so the app doesn't depend on the build machine's shared libraries.
+// Pass --optional (postinstall) to warn instead of failing when no ffmpeg is found yet.
+
+const fs = require('fs');
+const path = require('path');
+const { execFileSync } = require('child_process');
+
+const root = path.resolve(__dirname, '..');
+const optional = process.argv.includes('--optional');
+const ext = process.platform === 'win32' ? '.exe' : '';
+
+function fail(message) {
+  if (optional) {
+    console.warn(`⚠ ${message}`);
+    console.warn('  The Tauri build needs the sidecar; run: node scripts/fetch-ffmpeg.cjs');
+    process.exit(0);
+  }
+  console.error(`✗ ${message}`);
+  process.exit(1);
+}
+
+function targetTriple() {
+  if (process.env.TAURI_ENV_TARGET_TRIPLE) return process.env.TAURI_ENV_TARGET_TRIPLE;
+  try {
+    const out = execFileSync('rustc', ['-vV'], { encoding: 'utf8' });
+    return out.match(/^host: (\S+)$/m)?.[1];
+  } catch {
+    return undefined;
+  }
+}
+
+function findFfmpeg() {
+  if (process.env.FFMPEG_PATH) return process.env.FFMPEG_PATH;
+  const lookup = process.platform === 'win32' ? 'where' : 'which';
+  try {
+    return execFileSync(lookup, ['ffmpeg'], { encoding: 'utf8' }).split(/\r?\n/)[0].trim();
+  } catch {
+    return undefined;
+  }
+}
+
+const triple = targetTriple();
+if (!triple) fail('Could not determine the Rust target triple (is rustc installed?)');
+
+const dest = path.join(root, 'src-tauri/binaries', `ffmpeg-${triple}${ext}`);
+if (fs.existsSync(dest)) {
+  console.log(`ffmpeg sidecar already present: ${path.relative(root, dest)}`);
+  process.exit(0);
+}
+
+const source = findFfmpeg();
+if (!source || !fs.existsSync(source)) {
+  fail('ffmpeg not found; set FFMPEG_PATH or put ffmpeg on PATH');
+}
+
+fs.mkdirSync(path.dirname(dest), { recursive: true });
+fs.copyFileSync(fs.realpathSync(source), dest);
+fs.chmodSync(dest, 0o755);
+console.log(`✓ ffmpeg sidecar: ${source} → ${path.relative(root, dest)}`);
@@ -0,0 +1,578 @@
+//! Backend annotation layer
+//!
+//! Rasterizes rectangles, arrows, freehand paths and text onto a copy of a capture,
+//! redacts (blurs / pixelates) sensitive regions in place, and previews tone adjustments.
+//! Images from disk can be loaded in as if captured, so the same tools apply to them.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{Rgba, RgbaImage};
+use imageproc::drawing::{
+    draw_filled_circle_mut, draw_filled_rect_mut, draw_line_segment_mut, draw_text_mut, Blend,
+};
+use imageproc::rect::Rect;
+
+use crate::state::{AppState, SharedState};
+use crate::types::{AnnotationOp, CaptureMode, ImageSource, ImageTransform, RedactMode, Region};
+
+/// Largest side load_image_for_editing accepts (px)
+const MAX_IMPORT_DIMENSION: u32 = 32768;
+/// ...and largest total size, ~400 MB as RGBA
+const MAX_IMPORT_PIXELS: u64 = 100_000_000;
+
+/// Resolve the backend-held image an edit command targets
+pub(crate) fn source_image(s: &mut AppState, source: ImageSource) -> Option<&mut RgbaImage> {
+    match source {
+        ImageSource::LastCapture => s.last_capture.as_mut(),
+        ImageSource::ScrollStitched => s.scroll_stitched.as_mut(),
+        ImageSource::Snapshot => s.cached_snapshot.as_mut(),
+    }
+}
+
+/// Encode an image as a PNG data URL for the frontend
+pub(crate) fn encode_png_data_url(img: &RgbaImage) -> Result<String, String> {
+    use image::ImageEncoder;
+    let mut png_data = Vec::new();
+    let encoder = image::codecs::png::PngEncoder::new(&mut png_data);
+    encoder
+        .write_image(
+            img.as_raw(),
+            img.width(),
+            img.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(format!("data:image/png;base64,{}", STANDARD.encode(&png_data)))
+}
+
+/// Draw a line segment with the given stroke width (round caps)
+fn draw_thick_line(
+    canvas: &mut Blend<RgbaImage>,
+    from: (f32, f32),
+    to: (f32, f32),
+    color: Rgba<u8>,
+    width: f32,
+) {
+    if width <= 1.5 {
+        draw_line_segment_mut(canvas, from, to, color);
+        return;
+    }
+
+    // Stamp discs along the segment; cheap and gives round joins for paths
+    let radius = (width / 2.0).round() as i32;
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let steps = dx.abs().max(dy.abs()).ceil().max(1.0) as i32;
+    for i in 0..=steps {
+        let t = i as f32 / steps as f32;
+        let x = (from.0 + dx * t).round() as i32;
+        let y = (from.1 + dy * t).round() as i32;
+        draw_filled_circle_mut(canvas, (x, y), radius, color);
+    }
+}
+
+/// Draw the two barbs of an arrow head at `to`
+fn draw_arrow_head(
+    canvas: &mut Blend<RgbaImage>,
+    from: (f32, f32),
+    to: (f32, f32),
+    color: Rgba<u8>,
+    width: f32,
+) {
+    let angle = (to.1 - from.1).atan2(to.0 - from.0);
+    let len = (width * 4.0).max(12.0);
+    let spread = std::f32::consts::PI / 6.0; // 30°
+    for side in [-1.0f32, 1.0] {
+        let a = angle + std::f32::consts::PI + side * spread;
+        let tip = (to.0 + len * a.cos(), to.1 + len * a.sin());
+        draw_thick_line(canvas, to, tip, color, width);
+    }
+}
+
+/// Rasterize annotation ops onto a copy of `base`
+pub fn render_annotations(base: &RgbaImage, ops: &[AnnotationOp]) -> RgbaImage {
+    let mut canvas = Blend(base.clone());
+    let mut font = None;
+
+    for op in ops {
+        match op {
+            AnnotationOp::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+                stroke_width,
+                filled,
+            } => {
+                let color = Rgba(*color);
+                if *width < 1.0 || *height < 1.0 {
+                    continue;
+                }
+                if *filled {
+                    let rect = Rect::at(*x as i32, *y as i32).of_size(*width as u32, *height as u32);
+                    draw_filled_rect_mut(&mut canvas, rect, color);
+                } else {
+                    let (x0, y0, x1, y1) = (*x, *y, x + width, y + height);
+                    draw_thick_line(&mut canvas, (x0, y0), (x1, y0), color, *stroke_width);
+                    draw_thick_line(&mut canvas, (x1, y0), (x1, y1), color, *stroke_width);
+                    draw_thick_line(&mut canvas, (x1, y1), (x0, y1), color, *stroke_width);
+                    draw_thick_line(&mut canvas, (x0, y1), (x0, y0), color, *stroke_width);
+                }
+            }
+            AnnotationOp::Line {
+                from,
+                to,
+                color,
+                stroke_width,
+                arrow,
+            } => {
+                let color = Rgba(*color);
+                draw_thick_line(&mut canvas, *from, *to, color, *stroke_width);
+                if *arrow {
+                    draw_arrow_head(&mut canvas, *from, *to, color, *stroke_width);
+                }
+            }
+            AnnotationOp::Path {
+                points,
+                color,
+                stroke_width,
+            } => {
+                let color = Rgba(*color);
+                for pair in points.windows(2) {
+                    draw_thick_line(&mut canvas, pair[0], pair[1], color, *stroke_width);
+                }
+            }
+            AnnotationOp::Text {
+                x,
+                y,
+                text,
+                font_size,
+                color,
+            } => {
+                // Load lazily so text-free op lists never touch the filesystem
+                if font.is_none() {
+                    font = crate::share_compose::load_font();
+                }
+                match font.as_ref() {
+                    Some(f) => draw_text_mut(
+                        &mut canvas,
+                        Rgba(*color),
+                        *x as i32,
+                        *y as i32,
+                        *font_size,
+                        f,
+                        text,
+                    ),
+                    None => log::warn!("[annotate] Font not found, skipping text"),
+                }
+            }
+        }
+    }
+
+    canvas.0
+}
+
+/// Apply annotations to a copy of the selected image; the original stays untouched
+/// so the frontend can re-edit and re-apply the op list.
+#[tauri::command]
+pub fn apply_annotations(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    ops: Vec<AnnotationOp>,
+) -> Result<String, String> {
+    let base = {
+        let mut s = state.lock().unwrap();
+        source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image to annotate")?
+            .clone()
+    }; // Lock released before rasterizing
+
+    log::info!("[apply_annotations] {} ops on {}x{}", ops.len(), base.width(), base.height());
+    let annotated = render_annotations(&base, &ops);
+    encode_png_data_url(&annotated)
+}
+
+/// Blur or pixelate a sub-rectangle of `img` in place (intersected with the image bounds)
+pub fn redact(img: &mut RgbaImage, region: &Region, mode: RedactMode) -> Result<(), String> {
+    let (img_w, img_h) = img.dimensions();
+    if region.width == 0 || region.height == 0 {
+        return Err("Empty redact region".to_string());
+    }
+    // i64 so a negative origin shrinks the rect instead of shifting it onto the image
+    let x0 = (region.x as i64).max(0);
+    let y0 = (region.y as i64).max(0);
+    let x1 = (region.x as i64 + region.width as i64).min(img_w as i64);
+    let y1 = (region.y as i64 + region.height as i64).min(img_h as i64);
+    if x1 <= x0 || y1 <= y0 {
+        return Err("Redact region outside image".to_string());
+    }
+    let (x, y, w, h) = (x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32);
+
+    let patch = image::imageops::crop_imm(img, x, y, w, h).to_image();
+    let redacted = match mode {
+        RedactMode::Blur { radius } => imageproc::filter::gaussian_blur_f32(&patch, radius.max(0.5)),
+        RedactMode::Pixelate { block_size } => pixelate(&patch, block_size.max(1)),
+    };
+    image::imageops::replace(img, &redacted, x as i64, y as i64);
+    Ok(())
+}
+
+/// Replace each block with its average color
+fn pixelate(img: &RgbaImage, block: u32) -> RgbaImage {
+    let (w, h) = img.dimensions();
+    let mut out = img.clone();
+
+    for by in (0..h).step_by(block as usize) {
+        for bx in (0..w).step_by(block as usize) {
+            let bw = block.min(w - bx);
+            let bh = block.min(h - by);
+            let mut sum = [0u64; 4];
+            for yy in by..by + bh {
+                for xx in bx..bx + bw {
+                    for (acc, v) in sum.iter_mut().zip(img.get_pixel(xx, yy).0) {
+                        *acc += v as u64;
+                    }
+                }
+            }
+            let n = (bw * bh) as u64;
+            let avg = Rgba([
+                (sum[0] / n) as u8,
+                (sum[1] / n) as u8,
+                (sum[2] / n) as u8,
+                (sum[3] / n) as u8,
+            ]);
+            for yy in by..by + bh {
+                for xx in bx..bx + bw {
+                    out.put_pixel(xx, yy, avg);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Redact a region of the selected image in place and return the result.
+/// Calls can be chained to hide several regions.
+#[tauri::command]
+pub fn redact_region(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    region: Region,
+    mode: RedactMode,
+) -> Result<String, String> {
+    log::info!(
+        "[redact_region] {:?} at ({}, {}) {}x{}",
+        mode, region.x, region.y, region.width, region.height
+    );
+
+    let mut s = state.lock().unwrap();
+    let img = source_image(&mut s, source.unwrap_or_default()).ok_or("No image to redact")?;
+    redact(img, &region, mode)?;
+    encode_png_data_url(img)
+}
+
+/// Per-channel lookup table for brightness/contrast/gamma (inputs already clamped)
+fn adjustment_lut(brightness: f32, contrast: f32, gamma: f32) -> [u8; 256] {
+    let mut lut = [0u8; 256];
+    for (i, out) in lut.iter_mut().enumerate() {
+        let v = (i as f32 / 255.0).powf(1.0 / gamma);
+        let v = (v - 0.5) * contrast + 0.5 + brightness;
+        *out = (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+    lut
+}
+
+/// Apply brightness/contrast/gamma to a copy of `img` (alpha untouched)
+pub fn adjust(img: &RgbaImage, brightness: f32, contrast: f32, gamma: f32) -> RgbaImage {
+    let lut = adjustment_lut(brightness, contrast, gamma);
+    let mut out = img.clone();
+    for px in out.pixels_mut() {
+        for c in &mut px.0[..3] {
+            *c = lut[*c as usize];
+        }
+    }
+    out
+}
+
+/// Brightness/contrast/gamma preview on a copy of the selected image; the original stays
+/// untouched so a slider can re-apply freely.
+/// Neutral values: brightness 0.0 (-1.0..1.0), contrast 1.0 (0.0..4.0), gamma 1.0 (0.1..5.0).
+#[tauri::command]
+pub fn adjust_image(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    brightness: Option<f32>,
+    contrast: Option<f32>,
+    gamma: Option<f32>,
+) -> Result<String, String> {
+    let brightness = brightness.unwrap_or(0.0).clamp(-1.0, 1.0);
+    let contrast = contrast.unwrap_or(1.0).clamp(0.0, 4.0);
+    let gamma = gamma.unwrap_or(1.0).clamp(0.1, 5.0);
+
+    let base = {
+        let mut s = state.lock().unwrap();
+        source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image to adjust")?
+            .clone()
+    }; // Lock released before processing
+
+    log::info!(
+        "[adjust_image] brightness={} contrast={} gamma={} on {}x{}",
+        brightness,
+        contrast,
+        gamma,
+        base.width(),
+        base.height()
+    );
+    encode_png_data_url(&adjust(&base, brightness, contrast, gamma))
+}
+
+/// SVG paint attributes for an RGBA color
+fn svg_paint(attr: &str, color: &[u8; 4]) -> String {
+    format!(
+        "{attr}=\"rgb({},{},{})\" {attr}-opacity=\"{:.3}\"",
+        color[0],
+        color[1],
+        color[2],
+        color[3] as f32 / 255.0
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Annotation ops as SVG elements (same geometry as render_annotations)
+fn annotations_to_svg(ops: &[AnnotationOp]) -> String {
+    let mut out = String::new();
+    for op in ops {
+        let element = match op {
+            AnnotationOp::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+                stroke_width,
+                filled,
+            } => {
+                if *width < 1.0 || *height < 1.0 {
+                    continue;
+                }
+                let paint = if *filled {
+                    svg_paint("fill", color)
+                } else {
+                    format!(
+                        "fill=\"none\" {} stroke-width=\"{}\"",
+                        svg_paint("stroke", color),
+                        stroke_width
+                    )
+                };
+                format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+                    x, y, width, height, paint
+                )
+            }
+            AnnotationOp::Line {
+                from,
+                to,
+                color,
+                stroke_width,
+                arrow,
+            } => {
+                let stroke = format!(
+                    "{} stroke-width=\"{}\" stroke-linecap=\"round\"",
+                    svg_paint("stroke", color),
+                    stroke_width
+                );
+                let mut el = format!(
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+                    from.0, from.1, to.0, to.1, stroke
+                );
+                if *arrow {
+                    // Mirrors draw_arrow_head
+                    let angle = (to.1 - from.1).atan2(to.0 - from.0);
+                    let len = (stroke_width * 4.0).max(12.0);
+                    let spread = std::f32::consts::PI / 6.0;
+                    let barb = |side: f32| {
+                        let a = angle + std::f32::consts::PI + side * spread;
+                        (to.0 + len * a.cos(), to.1 + len * a.sin())
+                    };
+                    let (l, r) = (barb(-1.0), barb(1.0));
+                    el.push_str(&format!(
+                        "<polyline points=\"{},{} {},{} {},{}\" fill=\"none\" {} \
+                         stroke-linejoin=\"round\"/>",
+                        l.0, l.1, to.0, to.1, r.0, r.1, stroke
+                    ));
+                }
+                el
+            }
+            AnnotationOp::Path {
+                points,
+                color,
+                stroke_width,
+            } => {
+                let pts: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+                format!(
+                    "<polyline points=\"{}\" fill=\"none\" {} stroke-width=\"{}\" \
+                     stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+                    pts.join(" "),
+                    svg_paint("stroke", color),
+                    stroke_width
+                )
+            }
+            AnnotationOp::Text {
+                x,
+                y,
+                text,
+                font_size,
+                color,
+            } => {
+                // draw_text_mut anchors at the top-left, so hang the text from y
+                format!(
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" dominant-baseline=\"hanging\" \
+                     font-family=\"sans-serif\" {}>{}</text>",
+                    x,
+                    y,
+                    font_size,
+                    svg_paint("fill", color),
+                    escape_xml(text)
+                )
+            }
+        };
+        out.push_str("  ");
+        out.push_str(&element);
+        out.push('\n');
+    }
+    out
+}
+
+/// Write the selected image as an SVG: the raster embedded as a base64 `<image>` with the
+/// annotation ops as editable vector shapes on top. Image pixels map 1:1 to user units.
+#[tauri::command]
+pub fn export_annotated_svg(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    ops: Vec<AnnotationOp>,
+    path: String,
+) -> Result<String, String> {
+    let base = {
+        let mut s = state.lock().unwrap();
+        source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image to export")?
+            .clone()
+    };
+
+    let (w, h) = base.dimensions();
+    let svg = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+         width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n  \
+         <image x=\"0\" y=\"0\" width=\"{w}\" height=\"{h}\" xlink:href=\"{}\"/>\n{}</svg>\n",
+        encode_png_data_url(&base)?,
+        annotations_to_svg(&ops)
+    );
+    std::fs::write(&path, svg).map_err(|e| e.to_string())?;
+    log::info!("[export_annotated_svg] {} ops on {}x{} -> {}", ops.len(), w, h, path);
+    Ok(path)
+}
+
+/// Rotate or flip a copy of `img`; 90/270 swap width and height
+pub fn transform(img: &RgbaImage, op: ImageTransform) -> RgbaImage {
+    use image::imageops;
+    match op {
+        ImageTransform::Rotate90 => imageops::rotate90(img),
+        ImageTransform::Rotate180 => imageops::rotate180(img),
+        ImageTransform::Rotate270 => imageops::rotate270(img),
+        ImageTransform::FlipHorizontal => imageops::flip_horizontal(img),
+        ImageTransform::FlipVertical => imageops::flip_vertical(img),
+    }
+}
+
+/// Rotate/flip the selected image and make the result the current capture.
+/// The frozen snapshot is never modified: transforming it writes to last_capture instead.
+#[tauri::command]
+pub fn transform_capture(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    op: ImageTransform,
+) -> Result<String, String> {
+    let source = source.unwrap_or_default();
+    let mut s = state.lock().unwrap();
+    let img = source_image(&mut s, source).ok_or("No image to transform")?;
+    let transformed = transform(img, op);
+    log::info!(
+        "[transform_capture] {:?} {}x{} -> {}x{}",
+        op,
+        img.width(),
+        img.height(),
+        transformed.width(),
+        transformed.height()
+    );
+
+    let data_url = encode_png_data_url(&transformed)?;
+    match source {
+        ImageSource::ScrollStitched => s.scroll_stitched = Some(transformed),
+        ImageSource::LastCapture | ImageSource::Snapshot => s.last_capture = Some(transformed),
+    }
+    Ok(data_url)
+}
+
+/// Load a PNG, JPEG or WebP from disk as if it had just been captured: it becomes the
+/// frozen snapshot and the last capture, with the region covering the whole image, so the
+/// annotate / redact / crop / export commands work on it. Returns a PNG data URL.
+#[tauri::command]
+pub fn load_image_for_editing(
+    state: tauri::State<SharedState>,
+    path: String,
+) -> Result<String, String> {
+    let open = || {
+        image::ImageReader::open(&path)
+            .and_then(|r| r.with_guessed_format())
+            .map_err(|e| e.to_string())
+    };
+
+    // Check format and size from the header before decoding anything
+    let reader = open()?;
+    match reader.format() {
+        Some(image::ImageFormat::Png | image::ImageFormat::Jpeg | image::ImageFormat::WebP) => {}
+        Some(format) => return Err(format!("Unsupported image format: {:?}", format)),
+        None => return Err("Not a PNG, JPEG or WebP image".to_string()),
+    }
+    let (width, height) = reader.into_dimensions().map_err(|e| e.to_string())?;
+    if width == 0 || height == 0 {
+        return Err("Image is empty".to_string());
+    }
+    if width > MAX_IMPORT_DIMENSION
+        || height > MAX_IMPORT_DIMENSION
+        || width as u64 * height as u64 > MAX_IMPORT_PIXELS
+    {
+        return Err(format!("Image is too large to edit ({}x{})", width, height));
+    }
+
+    let img = open()?.decode().map_err(|e| e.to_string())?.to_rgba8();
+    let data_url = encode_png_data_url(&img)?;
+
+    let region = Region {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    };
+    let mut s = state.lock().unwrap();
+    // Image pixels are the coordinate space: origin 0,0 at scale 1
+    s.screen_x = 0;
+    s.screen_y = 0;
+    s.screen_scale = 1.0;
+    s.regions = vec![region.clone()];
+    s.region = Some(region);
+    s.clear_display_snapshots();
+    s.cached_snapshot = Some(img.clone());
+    s.last_capture = Some(img);
+    s.last_capture_mode = Some(CaptureMode::StaticImage);
+    log::info!("[load_image_for_editing] {}x{} from {}", width, height, path);
+    Ok(data_url)
+}
@@ -2,13 +2,17 @@
 //!
 //! Provides a unified API for screen capture operations.
 
-use image::RgbaImage;
+use image::imageops::FilterType;
+use image::{Rgba, RgbaImage};
 use xcap::Monitor;
 
+use crate::types::{Region, RegionValidation, RegionVisibility};
+
 /// Display information matching the old screenshots API
 #[derive(Debug, Clone)]
 pub struct DisplayInfo {
-    pub id: u32,
+    pub id: u32,                // enumeration index, not a platform id
+    pub native_id: Option<u32>, // CGDirectDisplayID on macOS (xcap monitor id)
     pub x: i32,
     pub y: i32,
     pub width: u32,
@@ -16,6 +20,10 @@ pub struct DisplayInfo {
     pub scale_factor: f32,
 }
 
+/// Tries `Screen::all_nonempty` makes before giving up, and the pause between them
+const SCREEN_ENUM_ATTEMPTS: u32 = 5;
+const SCREEN_ENUM_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+
 /// Screen wrapper providing capture operations
 pub struct Screen {
     monitor: Monitor,
@@ -43,6 +51,7 @@ impl Screen {
                 Ok(Screen {
                     display_info: DisplayInfo {
                         id: idx as u32,
+                        native_id: monitor.id().ok(),
                         x,
                         y,
                         width,
@@ -55,6 +64,30 @@ impl Screen {
             .collect()
     }
 
+    /// `all()`, retried briefly when enumeration fails or comes back empty, which happens
+    /// for a moment while displays wake from sleep. Never returns an empty list.
+    pub fn all_nonempty() -> Result<Vec<Screen>, String> {
+        let mut last_err = None;
+        for attempt in 1..=SCREEN_ENUM_ATTEMPTS {
+            match Screen::all() {
+                Ok(screens) if !screens.is_empty() => return Ok(screens),
+                Ok(_) => last_err = None,
+                Err(e) => last_err = Some(e),
+            }
+            if attempt < SCREEN_ENUM_ATTEMPTS {
+                log::warn!(
+                    "[capture] No displays enumerated (attempt {}/{}), retrying",
+                    attempt, SCREEN_ENUM_ATTEMPTS
+                );
+                std::thread::sleep(SCREEN_ENUM_RETRY_DELAY);
+            }
+        }
+        Err(match last_err {
+            Some(e) => format!("Couldn't enumerate displays (they may be waking up): {}", e),
+            None => "No displays connected".to_string(),
+        })
+    }
+
     /// Capture entire screen
     pub fn capture(&self) -> Result<RgbaImage, String> {
         let img = self.monitor.capture_image().map_err(|e| e.to_string())?;
@@ -74,13 +107,11 @@ impl Screen {
         // xcap's capture_image returns the full monitor in physical pixels
         let full = self.monitor.capture_image().map_err(|e| e.to_string())?;
 
-        let scale = self.display_info.scale_factor;
-
-        // Convert logical pixels to physical pixels
-        let rel_x = ((x - self.display_info.x) as f32 * scale).max(0.0) as u32;
-        let rel_y = ((y - self.display_info.y) as f32 * scale).max(0.0) as u32;
-        let phys_w = (width as f32 * scale) as u32;
-        let phys_h = (height as f32 * scale) as u32;
+        let d = &self.display_info;
+        let phys = logical_to_physical(&Region { x, y, width, height }, (d.x, d.y), d.scale_factor);
+        let rel_x = phys.x.max(0) as u32;
+        let rel_y = phys.y.max(0) as u32;
+        let (phys_w, phys_h) = (phys.width, phys.height);
 
         // Clamp to valid bounds
         let max_x = full.width().saturating_sub(1);
@@ -99,6 +130,297 @@ impl Screen {
     }
 }
 
+/// Logical rect (global points, as `set_region` stores it) to physical px relative to the
+/// top-left of a display at logical `origin` with `scale`; origin (0, 0) gives global
+/// physical px (window frames). Truncates toward zero.
+pub fn logical_to_physical(r: &Region, origin: (i32, i32), scale: f32) -> Region {
+    Region {
+        x: ((r.x - origin.0) as f32 * scale) as i32,
+        y: ((r.y - origin.1) as f32 * scale) as i32,
+        width: (r.width as f32 * scale) as u32,
+        height: (r.height as f32 * scale) as u32,
+    }
+}
+
+/// Inverse of `logical_to_physical`
+pub fn physical_to_logical(r: &Region, origin: (i32, i32), scale: f32) -> Region {
+    let scale = if scale > 0.0 { scale } else { 1.0 };
+    Region {
+        x: origin.0 + (r.x as f32 / scale).round() as i32,
+        y: origin.1 + (r.y as f32 / scale).round() as i32,
+        width: (r.width as f32 / scale).round() as u32,
+        height: (r.height as f32 / scale).round() as u32,
+    }
+}
+
+/// Logical bounds of the displays currently connected, primary first
+pub fn display_regions() -> Result<Vec<Region>, String> {
+    Ok(Screen::all()?
+        .iter()
+        .map(|s| Region {
+            x: s.display_info.x,
+            y: s.display_info.y,
+            width: s.display_info.width,
+            height: s.display_info.height,
+        })
+        .collect())
+}
+
+/// `pad_region_on` against the displays currently connected
+pub fn pad_region(region: &Region, padding: i32) -> Region {
+    if padding == 0 {
+        return region.clone();
+    }
+    pad_region_on(region, padding, &display_regions().unwrap_or_default())
+}
+
+/// `region` grown by `padding` logical px on every side (negative = inset), clamped to
+/// the display in `displays` containing its center. An inset never shrinks it below 1x1.
+pub fn pad_region_on(region: &Region, padding: i32, displays: &[Region]) -> Region {
+    if padding == 0 {
+        return region.clone();
+    }
+    let cx = region.x + region.width as i32 / 2;
+    let cy = region.y + region.height as i32 / 2;
+    let bounds = displays.iter().find_map(|d| {
+        let inside =
+            cx >= d.x && cx < d.x + d.width as i32 && cy >= d.y && cy < d.y + d.height as i32;
+        inside.then(|| (d.x, d.y, d.x + d.width as i32, d.y + d.height as i32))
+    });
+
+    let px = padding.max(-((region.width as i32 - 1) / 2));
+    let py = padding.max(-((region.height as i32 - 1) / 2));
+    let (mut x0, mut y0) = (region.x - px, region.y - py);
+    let (mut x1, mut y1) = (
+        region.x + region.width as i32 + px,
+        region.y + region.height as i32 + py,
+    );
+    if let Some((bx0, by0, bx1, by1)) = bounds {
+        x0 = x0.max(bx0);
+        y0 = y0.max(by0);
+        x1 = x1.min(bx1);
+        y1 = y1.min(by1);
+    }
+    Region {
+        x: x0,
+        y: y0,
+        width: (x1 - x0).max(1) as u32,
+        height: (y1 - y0).max(1) as u32,
+    }
+}
+
+/// Whether `parts` (each inside `region`) together cover all of it. Splits `region` into
+/// the grid formed by the parts' edges and checks every cell, so overlapping (mirrored)
+/// displays are fine.
+fn covers(region: &Region, parts: &[Region]) -> bool {
+    let mut xs = vec![region.x, region.x + region.width as i32];
+    let mut ys = vec![region.y, region.y + region.height as i32];
+    for p in parts {
+        xs.extend([p.x, p.x + p.width as i32]);
+        ys.extend([p.y, p.y + p.height as i32]);
+    }
+    for edges in [&mut xs, &mut ys] {
+        edges.sort_unstable();
+        edges.dedup();
+    }
+    xs.windows(2).all(|xw| {
+        ys.windows(2).all(|yw| {
+            parts.iter().any(|p| {
+                p.x <= xw[0]
+                    && xw[1] <= p.x + p.width as i32
+                    && p.y <= yw[0]
+                    && yw[1] <= p.y + p.height as i32
+            })
+        })
+    })
+}
+
+/// Check `region` against `displays` (logical bounds, primary first). A region inside the
+/// union of the displays is visible, even when it spans several. A clipped one is suggested
+/// as its overlap with the display it covers most; an off-screen one keeps its size (shrunk
+/// to fit) and is moved onto the primary display.
+pub fn validate_region_on(region: &Region, displays: &[Region]) -> RegionValidation {
+    let overlap = |d: &Region| {
+        let x0 = region.x.max(d.x);
+        let y0 = region.y.max(d.y);
+        let x1 = (region.x + region.width as i32).min(d.x + d.width as i32);
+        let y1 = (region.y + region.height as i32).min(d.y + d.height as i32);
+        (x1 > x0 && y1 > y0).then(|| Region {
+            x: x0,
+            y: y0,
+            width: (x1 - x0) as u32,
+            height: (y1 - y0) as u32,
+        })
+    };
+
+    let overlaps: Vec<Region> = displays.iter().filter_map(overlap).collect();
+    let best = overlaps.iter().max_by_key(|r| r.width as u64 * r.height as u64);
+    match best {
+        Some(_) if covers(region, &overlaps) => RegionValidation {
+            visibility: RegionVisibility::Visible,
+            suggested: None,
+        },
+        Some(r) => RegionValidation {
+            visibility: RegionVisibility::Clipped,
+            suggested: Some(r.clone()),
+        },
+        None => RegionValidation {
+            visibility: RegionVisibility::Offscreen,
+            suggested: displays.first().map(|d| {
+                let width = region.width.min(d.width);
+                let height = region.height.min(d.height);
+                Region {
+                    x: region.x.clamp(d.x, d.x + (d.width - width) as i32),
+                    y: region.y.clamp(d.y, d.y + (d.height - height) as i32),
+                    width,
+                    height,
+                }
+            }),
+        },
+    }
+}
+
+/// `validate_region_on` against the displays currently connected
+pub fn validate_region(region: &Region) -> Result<RegionValidation, String> {
+    Ok(validate_region_on(region, &display_regions()?))
+}
+
+/// Part of `display` outside the menu bar and Dock (macOS); elsewhere the whole display
+pub fn content_area(display: Region) -> Region {
+    #[cfg(target_os = "macos")]
+    if let Some(visible) = crate::window_detect::visible_frame(&display) {
+        return visible;
+    }
+    display
+}
+
+/// Primary display bounds (logical px), for captures requested without a region.
+/// Honors capture_content_area_only like the display presets do.
+pub fn primary_screen_region() -> Result<Region, String> {
+    let screens = Screen::all()?;
+    let info = &screens.first().ok_or("No screens found")?.display_info;
+    let display = Region {
+        x: info.x,
+        y: info.y,
+        width: info.width,
+        height: info.height,
+    };
+    if crate::config::load_config().capture_content_area_only {
+        return Ok(content_area(display));
+    }
+    Ok(display)
+}
+
+/// Capture a logical-px rectangle that may span several displays.
+/// A region inside one display goes straight to `capture_area`; otherwise each overlapped
+/// display contributes its intersection, scaled to the highest scale factor involved, and
+/// gaps no display covers (non-adjacent monitors) are filled with `background`.
+pub fn capture_region(
+    screens: &[Screen],
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    background: Rgba<u8>,
+) -> Result<RgbaImage, String> {
+    let (x1, y1) = (x + width as i32, y + height as i32);
+    let parts: Vec<(&Screen, i32, i32, u32, u32)> = screens
+        .iter()
+        .filter_map(|screen| {
+            let d = &screen.display_info;
+            let ix0 = x.max(d.x);
+            let iy0 = y.max(d.y);
+            let ix1 = x1.min(d.x + d.width as i32);
+            let iy1 = y1.min(d.y + d.height as i32);
+            (ix1 > ix0 && iy1 > iy0).then(|| {
+                (screen, ix0, iy0, (ix1 - ix0) as u32, (iy1 - iy0) as u32)
+            })
+        })
+        .collect();
+
+    match parts.as_slice() {
+        [] => return Err("Region is outside every display".to_string()),
+        [(screen, _, _, w, h)] if *w == width && *h == height => {
+            return screen.capture_area(x, y, width, height);
+        }
+        _ => {}
+    }
+
+    let out_scale = parts
+        .iter()
+        .map(|(screen, ..)| screen.display_info.scale_factor)
+        .fold(1.0f32, f32::max);
+    let mut canvas = RgbaImage::from_pixel(
+        (width as f32 * out_scale).round() as u32,
+        (height as f32 * out_scale).round() as u32,
+        background,
+    );
+    log::info!(
+        "[capture] region spans {} displays, compositing at {}x",
+        parts.len(),
+        out_scale
+    );
+
+    for (screen, ix, iy, iw, ih) in parts {
+        let part = screen.capture_area(ix, iy, iw, ih)?;
+        let target_w = (iw as f32 * out_scale).round() as u32;
+        let target_h = (ih as f32 * out_scale).round() as u32;
+        // Lower-DPI displays are upscaled to match the sharpest one
+        let part = if part.dimensions() != (target_w, target_h) {
+            image::imageops::resize(&part, target_w, target_h, FilterType::Triangle)
+        } else {
+            part
+        };
+        let dx = ((ix - x) as f32 * out_scale).round() as i64;
+        let dy = ((iy - y) as f32 * out_scale).round() as i64;
+        image::imageops::replace(&mut canvas, &part, dx, dy);
+    }
+    Ok(canvas)
+}
+
+/// Capture every display whole and lay them out as on the virtual desktop: each at its
+/// logical origin (displays left of or above the primary have negative ones), scaled to
+/// the highest scale factor, with areas no display covers filled with `background`.
+pub fn capture_desktop(screens: &[Screen], background: Rgba<u8>) -> Result<RgbaImage, String> {
+    let infos: Vec<&DisplayInfo> = screens.iter().map(|s| &s.display_info).collect();
+    let min_x = infos.iter().map(|d| d.x).min().ok_or("No displays connected")?;
+    let min_y = infos.iter().map(|d| d.y).min().unwrap_or(0);
+    let max_x = infos.iter().map(|d| d.x + d.width as i32).max().unwrap_or(0);
+    let max_y = infos.iter().map(|d| d.y + d.height as i32).max().unwrap_or(0);
+    let out_scale = infos.iter().map(|d| d.scale_factor).fold(1.0f32, f32::max);
+
+    let mut canvas = RgbaImage::from_pixel(
+        ((max_x - min_x) as f32 * out_scale).round() as u32,
+        ((max_y - min_y) as f32 * out_scale).round() as u32,
+        background,
+    );
+    log::info!(
+        "[capture] desktop of {} displays from ({}, {}), {}x{} @ {}x",
+        screens.len(),
+        min_x,
+        min_y,
+        max_x - min_x,
+        max_y - min_y,
+        out_scale
+    );
+
+    for screen in screens {
+        let d = &screen.display_info;
+        let shot = screen.capture()?;
+        let target_w = (d.width as f32 * out_scale).round() as u32;
+        let target_h = (d.height as f32 * out_scale).round() as u32;
+        let shot = if shot.dimensions() != (target_w, target_h) {
+            image::imageops::resize(&shot, target_w, target_h, FilterType::Triangle)
+        } else {
+            shot
+        };
+        let dx = ((d.x - min_x) as f32 * out_scale).round() as i64;
+        let dy = ((d.y - min_y) as f32 * out_scale).round() as i64;
+        image::imageops::replace(&mut canvas, &shot, dx, dy);
+    }
+    Ok(canvas)
+}
+
 /// Get monitor position (platform-specific)
 #[cfg(target_os = "macos")]
 fn get_monitor_position(monitor: &Monitor, _idx: usize) -> (i32, i32) {
@@ -116,7 +438,12 @@ fn get_monitor_position(monitor: &Monitor, _idx: usize) -> (i32, i32) {
     (0, 0)
 }
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(target_os = "linux")]
+fn get_monitor_position(monitor: &Monitor, _idx: usize) -> (i32, i32) {
+    crate::linux_capture::monitor_position(monitor)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 fn get_monitor_position(_monitor: &Monitor, _idx: usize) -> (i32, i32) {
     (0, 0)
 }
@@ -139,7 +466,60 @@ fn get_scale_factor(_monitor: &Monitor, logical_width: u32) -> f32 {
     2.0 // Default Retina scale
 }
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(target_os = "linux")]
+fn get_scale_factor(monitor: &Monitor, _logical_width: u32) -> f32 {
+    crate::linux_capture::monitor_scale_factor(monitor)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 fn get_scale_factor(_monitor: &Monitor, _logical_width: u32) -> f32 {
     1.0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Region {
+        Region { x, y, width, height }
+    }
+
+    #[test]
+    fn test_validate_region_on() {
+        let displays = [rect(0, 0, 1000, 800), rect(1000, 0, 800, 600)];
+
+        let inside = validate_region_on(&rect(100, 100, 200, 200), &displays);
+        assert_eq!(inside.visibility, RegionVisibility::Visible);
+
+        // Spans both displays without leaving them
+        let spanning = validate_region_on(&rect(900, 100, 200, 200), &displays);
+        assert_eq!(spanning.visibility, RegionVisibility::Visible);
+        assert!(spanning.suggested.is_none());
+
+        // Crosses into the gap below the shorter second display
+        let clipped = validate_region_on(&rect(900, 500, 200, 200), &displays);
+        assert_eq!(clipped.visibility, RegionVisibility::Clipped);
+        let s = clipped.suggested.unwrap();
+        assert_eq!((s.x, s.y, s.width, s.height), (900, 500, 100, 200));
+
+        let off = validate_region_on(&rect(5000, 5000, 300, 300), &displays);
+        assert_eq!(off.visibility, RegionVisibility::Offscreen);
+        let s = off.suggested.unwrap();
+        assert_eq!((s.x, s.y, s.width, s.height), (700, 500, 300, 300));
+    }
+
+    #[test]
+    fn test_pad_region_on() {
+        let displays = [rect(0, 0, 1000, 800), rect(1000, 0, 800, 600)];
+        let r = |r: Region| (r.x, r.y, r.width, r.height);
+
+        assert_eq!(r(pad_region_on(&rect(100, 100, 200, 100), 10, &displays)), (90, 90, 220, 120));
+        // Clamped to the display holding the center, not the union
+        assert_eq!(r(pad_region_on(&rect(940, 5, 100, 50), 20, &displays)), (920, 0, 80, 75));
+        assert_eq!(r(pad_region_on(&rect(1010, 5, 100, 50), 20, &displays)), (1000, 0, 130, 75));
+        // An inset never collapses the region
+        assert_eq!(r(pad_region_on(&rect(100, 100, 10, 10), -50, &displays)), (104, 104, 2, 2));
+        // No display under the center: padded but unclamped
+        assert_eq!(r(pad_region_on(&rect(-500, -500, 10, 10), 5, &displays)), (-505, -505, 20, 20));
+    }
+}
@@ -0,0 +1,111 @@
+//! Shutter sound and screen flash after a successful still capture
+//!
+//! Both are opt-in (`capture_sound_enabled`, `capture_flash_enabled`) and fired from the
+//! capture command itself so the frontend doesn't have to coordinate timing.
+//! macOS only for now; elsewhere this is a no-op.
+
+use tauri::AppHandle;
+
+use crate::types::Region;
+
+/// Play the configured feedback for a capture of `region` (logical px)
+pub fn play(app: &AppHandle, region: &Region) {
+    let cfg = crate::config::load_config();
+    if cfg.capture_sound_enabled {
+        platform::play_shutter_sound(app);
+    }
+    if cfg.capture_flash_enabled {
+        platform::flash_region(app, region);
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::thread;
+    use std::time::Duration;
+
+    use cocoa::base::{id, nil, BOOL, NO, YES};
+    use cocoa::foundation::{NSPoint, NSRect, NSSize, NSString};
+    use objc::{class, msg_send, sel, sel_impl};
+    use tauri::AppHandle;
+
+    use crate::types::Region;
+
+    /// The sound the system screenshot tool plays
+    const SHUTTER_SOUND: &str = "/System/Library/Components/CoreAudio.component/Contents/\
+                                 SharedSupport/SystemSounds/system/Screen Capture.aif";
+    const FLASH_MS: u64 = 150;
+
+    /// Runs on the main thread, whose run loop drains the autorelease pool
+    pub fn play_shutter_sound(app: &AppHandle) {
+        let _ = app.run_on_main_thread(|| unsafe {
+            let path = NSString::alloc(nil).init_str(SHUTTER_SOUND);
+            let sound: id = msg_send![class!(NSSound), alloc];
+            let sound: id = msg_send![sound, initWithContentsOfFile: path byReference: YES];
+            let sound: id = if sound.is_null() {
+                // Older systems: fall back to a stock alert sound
+                msg_send![class!(NSSound), soundNamed: NSString::alloc(nil).init_str("Tink")]
+            } else {
+                msg_send![sound, autorelease]
+            };
+            if !sound.is_null() {
+                let _: BOOL = msg_send![sound, play];
+            }
+        });
+    }
+
+    /// Briefly cover the captured region with a white borderless window
+    pub fn flash_region(app: &AppHandle, region: &Region) {
+        let region = region.clone();
+        let app_for_close = app.clone();
+        let _ = app.run_on_main_thread(move || unsafe {
+            // Cocoa's origin is the bottom-left of the primary screen
+            let screens: id = msg_send![class!(NSScreen), screens];
+            let primary: id = msg_send![screens, objectAtIndex: 0usize];
+            let primary_frame: NSRect = msg_send![primary, frame];
+            let frame = NSRect::new(
+                NSPoint::new(
+                    region.x as f64,
+                    primary_frame.size.height - (region.y as f64 + region.height as f64),
+                ),
+                NSSize::new(region.width as f64, region.height as f64),
+            );
+
+            let window: id = msg_send![class!(NSWindow), alloc];
+            let window: id = msg_send![window,
+                initWithContentRect: frame
+                styleMask: 0u64 // borderless
+                backing: 2u64 // NSBackingStoreBuffered
+                defer: NO];
+            let white: id = msg_send![class!(NSColor), whiteColor];
+            let _: () = msg_send![window, setBackgroundColor: white];
+            let _: () = msg_send![window, setAlphaValue: 0.8f64];
+            let _: () = msg_send![window, setOpaque: NO];
+            let _: () = msg_send![window, setIgnoresMouseEvents: YES];
+            let _: () = msg_send![window, setReleasedWhenClosed: NO];
+            let _: () = msg_send![window, setLevel: 1000i64]; // above the selector
+            let _: () = msg_send![window, orderFrontRegardless];
+
+            let window_ptr = window as usize;
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(FLASH_MS));
+                let _ = app_for_close.run_on_main_thread(move || unsafe {
+                    let window = window_ptr as id;
+                    let _: () = msg_send![window, orderOut: nil];
+                    let _: () = msg_send![window, release];
+                });
+            });
+        });
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use tauri::AppHandle;
+
+    use crate::types::Region;
+
+    pub fn play_shutter_sound(_app: &AppHandle) {}
+
+    pub fn flash_region(_app: &AppHandle, _region: &Region) {}
+}
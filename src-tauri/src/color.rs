@@ -0,0 +1,159 @@
+//! Output color management for saved screenshots
+//!
+//! Captured pixels are in the source display's color space. On a wide-gamut (Display P3)
+//! panel they look oversaturated once a viewer assumes sRGB, so `ColorProfile` can tag
+//! the PNG as sRGB and optionally convert P3 pixels to sRGB first. Only macOS reports a
+//! display gamut; elsewhere displays are treated as sRGB and only the tag applies.
+
+use std::sync::OnceLock;
+
+use image::RgbaImage;
+
+use crate::capture::Screen;
+use crate::config::ColorProfile;
+use crate::types::Region;
+
+/// Gamut of the display a capture came from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SourceGamut {
+    Srgb,
+    DisplayP3,
+}
+
+/// Linear Display P3 -> linear sRGB (both D65)
+const P3_TO_SRGB: [[f32; 3]; 3] = [
+    [1.224_940_2, -0.224_940_4, 0.0],
+    [-0.042_056_9, 1.042_057_1, 0.0],
+    [-0.019_637_6, -0.078_636_1, 1.098_273_5],
+];
+
+/// Steps of the linear -> encoded lookup table
+const ENCODE_STEPS: usize = 4096;
+
+fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.040_45 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Both P3 and sRGB use the sRGB transfer curve, so one pair of tables covers both ends
+fn tables() -> &'static ([f32; 256], Vec<u8>) {
+    static TABLES: OnceLock<([f32; 256], Vec<u8>)> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut decode = [0.0f32; 256];
+        for (i, d) in decode.iter_mut().enumerate() {
+            *d = srgb_to_linear(i as f32 / 255.0);
+        }
+        let encode = (0..=ENCODE_STEPS)
+            .map(|i| (linear_to_srgb(i as f32 / ENCODE_STEPS as f32) * 255.0).round() as u8)
+            .collect();
+        (decode, encode)
+    })
+}
+
+/// Convert Display P3 pixels to sRGB in place; out-of-gamut colors are clipped
+pub fn convert_p3_to_srgb(img: &mut RgbaImage) {
+    let (decode, encode) = tables();
+    for px in img.pixels_mut() {
+        let lin = [decode[px[0] as usize], decode[px[1] as usize], decode[px[2] as usize]];
+        for (c, row) in P3_TO_SRGB.iter().enumerate() {
+            let v = row[0] * lin[0] + row[1] * lin[1] + row[2] * lin[2];
+            px[c] = encode[(v.clamp(0.0, 1.0) * ENCODE_STEPS as f32).round() as usize];
+        }
+    }
+}
+
+/// Gamut of the display containing the center of `region` (logical px)
+pub fn source_gamut(region: &Region) -> SourceGamut {
+    let cx = region.x + region.width as i32 / 2;
+    let cy = region.y + region.height as i32 / 2;
+    let screens = Screen::all().unwrap_or_default();
+    let display = screens.iter().find(|s| {
+        let d = &s.display_info;
+        cx >= d.x && cx < d.x + d.width as i32 && cy >= d.y && cy < d.y + d.height as i32
+    });
+    // display_info.id is only an index; CoreGraphics needs the real display id
+    match display.or(screens.first()).and_then(|s| s.display_info.native_id) {
+        Some(native_id) => platform::display_gamut(native_id),
+        None => SourceGamut::Srgb,
+    }
+}
+
+/// Apply `profile` to a capture of `region` before it is written out
+pub fn prepare_for_export(img: &mut RgbaImage, region: &Region, profile: ColorProfile) {
+    if profile != ColorProfile::ConvertToSrgb {
+        return;
+    }
+    if source_gamut(region) == SourceGamut::DisplayP3 {
+        log::info!("[color] Converting Display P3 capture to sRGB");
+        convert_p3_to_srgb(img);
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::c_void;
+
+    use super::SourceGamut;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGDisplayCopyColorSpace(display: u32) -> *const c_void;
+        fn CGColorSpaceIsWideGamutRGB(space: *const c_void) -> bool;
+        fn CGColorSpaceRelease(space: *const c_void);
+    }
+
+    /// Wide-gamut panels on Macs are Display P3
+    pub fn display_gamut(display_id: u32) -> SourceGamut {
+        unsafe {
+            let space = CGDisplayCopyColorSpace(display_id);
+            if space.is_null() {
+                return SourceGamut::Srgb;
+            }
+            let wide = CGColorSpaceIsWideGamutRGB(space);
+            CGColorSpaceRelease(space);
+            if wide {
+                SourceGamut::DisplayP3
+            } else {
+                SourceGamut::Srgb
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use super::SourceGamut;
+
+    pub fn display_gamut(_display_id: u32) -> SourceGamut {
+        SourceGamut::Srgb
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_p3_to_srgb_keeps_neutrals_and_clips_red() {
+        let mut img = RgbaImage::from_pixel(3, 1, Rgba([255, 255, 255, 255]));
+        img.put_pixel(1, 0, Rgba([128, 128, 128, 200]));
+        img.put_pixel(2, 0, Rgba([255, 0, 0, 255]));
+        convert_p3_to_srgb(&mut img);
+        assert_eq!(img.get_pixel(0, 0), &Rgba([255, 255, 255, 255]));
+        assert_eq!(img.get_pixel(1, 0), &Rgba([128, 128, 128, 200]));
+        // P3 red is outside sRGB: red saturates, green/blue clip to zero
+        assert_eq!(img.get_pixel(2, 0), &Rgba([255, 0, 0, 255]));
+    }
+}
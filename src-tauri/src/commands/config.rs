@@ -2,10 +2,14 @@ use tauri::AppHandle;
 use tauri_plugin_autostart::ManagerExt;
 use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
-use crate::config::{self, AppConfig, ShortcutConfig, WatermarkPosition};
+use crate::config::{
+    self, AppConfig, CaptureFormat, ColorProfile, DefaultFormats, LogLevel, RecordingFrameFormat,
+    RecordingLimitPolicy, ShortcutConfig, WatermarkPosition,
+};
 use crate::shortcuts::register_shortcuts_from_config;
 use crate::state::SharedState;
 use crate::tray::update_tray_menu;
+use crate::types::{CaptureMode, ExportWatermark};
 
 #[tauri::command]
 pub fn get_shortcuts_config() -> AppConfig {
@@ -40,6 +44,15 @@ pub fn add_shortcut(
     Ok(new_config)
 }
 
+/// Enable/disable an action's shortcuts (keys are kept) and re-register
+#[tauri::command]
+pub fn toggle_shortcut(app: AppHandle, action: String, enabled: bool) -> Result<AppConfig, String> {
+    let new_config = config::set_shortcut_enabled(&action, enabled)?;
+    register_shortcuts_from_config(&app)?;
+    log::info!("[shortcuts] {} {}", action, if enabled { "enabled" } else { "disabled" });
+    Ok(new_config)
+}
+
 /// Remove a shortcut from an action by index
 #[tauri::command]
 pub fn remove_shortcut(
@@ -91,6 +104,24 @@ pub fn set_screenshot_preview_enabled(enabled: bool) -> Result<AppConfig, String
     Ok(cfg)
 }
 
+/// Whether the editor/dock icon come back once a GIF/Video recording ends
+#[tauri::command]
+pub fn set_restore_main_after_capture(enabled: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.restore_main_after_capture = enabled;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Whether GIF/Video capture hides the dashboard and switches to Accessory (no Dock icon)
+#[tauri::command]
+pub fn set_accessory_during_capture(enabled: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.accessory_during_capture = enabled;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
 #[tauri::command]
 pub fn pause_shortcuts(app: AppHandle, state: tauri::State<SharedState>) -> Result<(), String> {
     {
@@ -101,7 +132,7 @@ pub fn pause_shortcuts(app: AppHandle, state: tauri::State<SharedState>) -> Resu
     app.global_shortcut()
         .unregister_all()
         .map_err(|e| e.to_string())?;
-    println!("[shortcuts] Paused all shortcuts for editing");
+    log::info!("[shortcuts] Paused all shortcuts for editing");
     Ok(())
 }
 
@@ -114,12 +145,12 @@ pub fn resume_shortcuts(app: AppHandle, state: tauri::State<SharedState>) -> Res
     };
 
     if paused_for_tray_menu {
-        println!("[shortcuts] Resume requested but tray menu is open; deferring");
+        log::info!("[shortcuts] Resume requested but tray menu is open; deferring");
         return Ok(());
     }
 
     register_shortcuts_from_config(&app)?;
-    println!("[shortcuts] Resumed shortcuts");
+    log::info!("[shortcuts] Resumed shortcuts");
     Ok(())
 }
 
@@ -209,3 +240,275 @@ pub fn set_show_caption_editor(enabled: bool) -> Result<AppConfig, String> {
     config::save_config(&cfg)?;
     Ok(cfg)
 }
+
+/// Set the recording buffer caps (0 = unlimited) and what happens when one is hit
+#[tauri::command]
+pub fn set_recording_limits(
+    max_memory_mb: u32,
+    max_frames: u32,
+    policy: String,
+) -> Result<AppConfig, String> {
+    let policy = match policy.as_str() {
+        "stop" => RecordingLimitPolicy::Stop,
+        "drop_fps" => RecordingLimitPolicy::DropFps,
+        _ => return Err("Invalid policy. Must be 'stop' or 'drop_fps'".to_string()),
+    };
+    let mut cfg = config::load_config();
+    cfg.recording_max_memory_mb = max_memory_mb;
+    cfg.recording_max_frames = max_frames;
+    cfg.recording_limit_policy = policy;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Buffer recorded frames as RGB (25% less memory) or RGBA; applies to the next recording
+#[tauri::command]
+pub fn set_recording_frame_format(format: RecordingFrameFormat) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.recording_frame_format = format;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set where quick_save writes and how it names files
+#[tauri::command]
+pub fn set_quick_save_options(
+    output_dir: Option<String>,
+    filename_template: String,
+) -> Result<AppConfig, String> {
+    if filename_template.trim().is_empty() {
+        return Err("Filename template cannot be empty".to_string());
+    }
+    let mut cfg = config::load_config();
+    cfg.output_dir = output_dir.filter(|d| !d.trim().is_empty());
+    cfg.filename_template = filename_template;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set where upload_capture sends images
+#[tauri::command]
+pub fn set_upload_settings(
+    endpoint: Option<String>,
+    token: Option<String>,
+    method: Option<String>,
+) -> Result<AppConfig, String> {
+    let method = method.unwrap_or_else(|| "post".to_string()).to_lowercase();
+    if !matches!(method.as_str(), "post" | "put") {
+        return Err(format!("Unsupported upload method: {}", method));
+    }
+    let mut cfg = config::load_config();
+    cfg.upload_endpoint = endpoint.filter(|e| !e.trim().is_empty());
+    cfg.upload_token = token.filter(|t| !t.trim().is_empty());
+    cfg.upload_method = method;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set OCR recognition languages (empty = automatic detection)
+#[tauri::command]
+pub fn set_ocr_languages(languages: Vec<String>) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.ocr_languages = languages
+        .into_iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set the smallest selection set_region accepts (logical px, at least 1)
+#[tauri::command]
+pub fn set_min_region_size(width: u32, height: u32) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.min_region_width = width.max(1);
+    cfg.min_region_height = height.max(1);
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set how many regions per display the region history keeps (at least 1)
+#[tauri::command]
+pub fn set_region_history_size(size: usize) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.region_history_size = size.max(1);
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Enable/disable the local HTTP capture API (127.0.0.1 only) and restart it on `port`
+#[tauri::command]
+pub fn set_local_api(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.local_api_enabled = enabled;
+    if let Some(port) = port.filter(|&p| p >= 1024) {
+        cfg.local_api_port = port;
+    }
+    crate::local_api::ensure_token(&mut cfg)?;
+    config::save_config(&cfg)?;
+    crate::local_api::sync_with_config(&app)?;
+    Ok(cfg)
+}
+
+/// Toggle the shutter sound / screen flash played after a still capture
+#[tauri::command]
+pub fn set_capture_feedback(sound: bool, flash: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.capture_sound_enabled = sound;
+    cfg.capture_flash_enabled = flash;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+#[tauri::command]
+pub fn get_default_formats() -> DefaultFormats {
+    config::load_config().default_formats
+}
+
+/// Set the format `mode` saves in; rejects formats the mode can't produce
+#[tauri::command]
+pub fn set_default_format(mode: CaptureMode, format: CaptureFormat) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.default_formats.set(mode, format)?;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set how saved screenshots handle the source display's color space
+#[tauri::command]
+pub fn set_color_profile(profile: ColorProfile) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.color_profile = profile;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set the size above which scroll exports warn or split (px, at least 1000)
+#[tauri::command]
+pub fn set_max_export_size(width: u32, height: u32) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.max_export_width = width.max(1000);
+    cfg.max_export_height = height.max(1000);
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Cap how many displays capture_screen_now freezes at once (0 = primary only)
+#[tauri::command]
+pub fn set_snapshot_cache_max_displays(count: u32) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.snapshot_cache_max_displays = count;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Request screen recording permission inline (and retry the capture) when it is missing
+#[tauri::command]
+pub fn set_auto_request_permission(enabled: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.auto_request_permission = enabled;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Leave lovshot's own windows (dashboard, pins, previews) out of static captures
+#[tauri::command]
+pub fn set_exclude_own_windows(enabled: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.exclude_own_windows = enabled;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Retry policy for the native screen grab (transient failures, e.g. right after wake)
+#[tauri::command]
+pub fn set_capture_retry(
+    attempts: u32,
+    backoff_ms: u64,
+    timeout_ms: u64,
+) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.capture_retry_attempts = attempts.clamp(1, 10);
+    cfg.capture_retry_backoff_ms = backoff_ms.min(2000);
+    cfg.capture_retry_timeout_ms = timeout_ms.min(10_000);
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Set selector overlay appearance (dim opacity 0-1, border / guideline CSS colors)
+#[tauri::command]
+pub fn set_selector_appearance(
+    dim_opacity: f32,
+    border_color: String,
+    guide_color: String,
+) -> Result<AppConfig, String> {
+    if !(0.0..=1.0).contains(&dim_opacity) {
+        return Err("Dim opacity must be between 0 and 1".to_string());
+    }
+    if border_color.trim().is_empty() || guide_color.trim().is_empty() {
+        return Err("Colors cannot be empty".to_string());
+    }
+    let mut cfg = config::load_config();
+    cfg.selector_dim_opacity = dim_opacity;
+    cfg.selector_border_color = border_color;
+    cfg.selector_guide_color = guide_color;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Have display presets (e.g. full screen) cover only the area outside the menu bar and Dock
+#[tauri::command]
+pub fn set_capture_content_area_only(enabled: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.capture_content_area_only = enabled;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Toggle: pressing the GIF/video shortcut again (or any shortcut) stops a recording.
+/// Off: only the stop_recording shortcuts stop it; other shortcuts are ignored meanwhile.
+#[tauri::command]
+pub fn set_recording_shortcut_toggle(enabled: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.recording_shortcut_toggle = enabled;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Logo or text stamped on saved screenshots, scroll captures and animations
+/// (None removes it). Exports can still pass their own.
+#[tauri::command]
+pub fn set_export_watermark(watermark: Option<ExportWatermark>) -> Result<AppConfig, String> {
+    if let Some(wm) = &watermark {
+        crate::commands::WatermarkStamp::new(wm)?; // reject a missing logo or font up front
+    }
+    let mut cfg = config::load_config();
+    cfg.export_watermark = watermark;
+    config::save_config(&cfg)?;
+    Ok(cfg)
+}
+
+/// Minimum log severity; applies immediately
+#[tauri::command]
+pub fn set_log_level(level: LogLevel) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.log_level = level;
+    config::save_config(&cfg)?;
+    crate::logging::set_level(level);
+    Ok(cfg)
+}
+
+/// Mirror the log to lovshot.log in the config dir (see get_log_path)
+#[tauri::command]
+pub fn set_log_to_file(enabled: bool) -> Result<AppConfig, String> {
+    let mut cfg = config::load_config();
+    cfg.log_to_file = enabled;
+    config::save_config(&cfg)?;
+    crate::logging::set_file_logging(enabled)?;
+    Ok(cfg)
+}
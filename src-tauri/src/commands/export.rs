@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::thread;
 
 use ab_glyph::{FontRef, PxScale};
-use crate::capture::Screen;
+use crate::capture::{logical_to_physical, Screen};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use gif::{Encoder, Frame, Repeat};
 use image::{Rgba, RgbaImage};
@@ -12,9 +12,13 @@ use imageproc::drawing::draw_text_mut;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
-use crate::config::WatermarkPosition;
+use crate::config::{CaptureFormat, WatermarkPosition};
 use crate::state::SharedState;
-use crate::types::{ExportConfig, ExportProgress, GifLoopMode, SaveResult, SizeEstimate};
+use crate::types::{
+    CaptureMode, ExportConfig, ExportFormat, ExportProgress, ExportWatermark, FrameSequenceExport,
+    GifLoopMode, ImageSource, OverlayAnchor, PngCompression, PngFilter, PngMetadata, SaveResult,
+    ScreenshotOptions, SizeEstimate, WatermarkContent,
+};
 
 // ============ Screenshot Watermark ============
 
@@ -61,14 +65,14 @@ fn add_screenshot_watermark(img: &mut RgbaImage, number: u64) {
 
     // Skip if position is None or Brand (Brand is handled in share_compose)
     if matches!(position, WatermarkPosition::None | WatermarkPosition::Brand) {
-        println!("[watermark] Position {:?}, skipping corner watermark", position);
+        log::warn!("[watermark] Position {:?}, skipping corner watermark", position);
         return;
     }
 
     let font = match load_watermark_font() {
         Some(f) => f,
         None => {
-            println!("[watermark] Font not found, skipping");
+            log::warn!("[watermark] Font not found, skipping");
             return;
         }
     };
@@ -102,7 +106,88 @@ fn add_screenshot_watermark(img: &mut RgbaImage, number: u64) {
     let color = Rgba([40, 40, 40, 180]);
     draw_text_mut(img, color, x, y, scale, &font, &text);
 
-    println!("[watermark] Added #{} at ({}, {}) position {:?}", number, x, y, position);
+    log::info!("[watermark] Added #{} at ({}, {}) position {:?}", number, x, y, position);
+}
+
+/// An export watermark rendered once, then stamped onto any number of images
+pub struct WatermarkStamp {
+    layer: RgbaImage, // opacity already folded into alpha
+    anchor: OverlayAnchor,
+    margin: u32,
+    all_frames: bool,
+}
+
+impl WatermarkStamp {
+    pub fn new(watermark: &ExportWatermark) -> Result<Self, String> {
+        let mut layer = match &watermark.content {
+            WatermarkContent::Image { path } => image::open(path)
+                .map_err(|e| format!("Failed to load watermark image: {}", e))?
+                .to_rgba8(),
+            WatermarkContent::Text { text, font_size, color } => {
+                let font = load_watermark_font().ok_or("No font available for the watermark")?;
+                let scale = PxScale::from(font_size.clamp(6.0, 400.0));
+                let (w, h) = imageproc::drawing::text_size(scale, &font, text);
+                if w == 0 || h == 0 {
+                    return Err("Watermark text is empty".to_string());
+                }
+                // Transparent in the text color, so antialiased edges don't darken
+                let [r, g, b, _] = *color;
+                let mut layer = RgbaImage::from_pixel(w, h, Rgba([r, g, b, 0]));
+                draw_text_mut(&mut layer, Rgba(*color), 0, 0, scale, &font, text);
+                layer
+            }
+        };
+        let opacity = watermark.opacity.clamp(0.0, 1.0);
+        for p in layer.pixels_mut() {
+            p[3] = (p[3] as f32 * opacity).round() as u8;
+        }
+        Ok(Self {
+            layer,
+            anchor: watermark.anchor,
+            margin: watermark.margin,
+            all_frames: watermark.all_frames,
+        })
+    }
+
+    /// The configured watermark, or the export_watermark setting when there is none
+    pub fn for_export(watermark: Option<&ExportWatermark>) -> Result<Option<Self>, String> {
+        match watermark {
+            Some(wm) => Self::new(wm).map(Some),
+            None => crate::config::load_config()
+                .export_watermark
+                .as_ref()
+                .map(Self::new)
+                .transpose(),
+        }
+    }
+
+    /// Composite onto `img` at the anchor; a stamp larger than the image is shrunk to fit
+    pub fn apply(&self, img: &mut RgbaImage) {
+        let (img_w, img_h) = img.dimensions();
+        let avail_w = img_w.saturating_sub(2 * self.margin);
+        let avail_h = img_h.saturating_sub(2 * self.margin);
+        if avail_w == 0 || avail_h == 0 {
+            return;
+        }
+        let (lw, lh) = self.layer.dimensions();
+        let fit = (avail_w as f32 / lw as f32).min(avail_h as f32 / lh as f32);
+        let shrunk;
+        let layer = if fit < 1.0 {
+            let w = ((lw as f32 * fit) as u32).max(1);
+            let h = ((lh as f32 * fit) as u32).max(1);
+            let filter = image::imageops::FilterType::Triangle;
+            shrunk = image::imageops::resize(&self.layer, w, h, filter);
+            &shrunk
+        } else {
+            &self.layer
+        };
+
+        let (lw, lh) = layer.dimensions();
+        let (col, row) = self.anchor.grid();
+        let x = self.margin + (avail_w - lw) * col / 2;
+        let y = self.margin + (avail_h - lh) * row / 2;
+        image::imageops::overlay(img, layer, x as i64, y as i64);
+    }
 }
 
 #[tauri::command]
@@ -145,10 +230,16 @@ pub fn estimate_export_size(
         final_frame_count
     };
 
-    // Adjust bytes_per_pixel based on quality (1-100)
-    // Low quality (1) -> ~0.05, High quality (100) -> ~0.4 (8x difference)
-    let quality_factor = config.quality.clamp(1, 100) as f64 / 100.0;
-    let bytes_per_pixel = 0.05 + quality_factor * 0.35;
+    let bytes_per_pixel = match config.format {
+        // Adjust bytes_per_pixel based on quality (1-100)
+        // Low quality (1) -> ~0.05, High quality (100) -> ~0.4 (8x difference)
+        ExportFormat::Gif => {
+            let quality_factor = config.quality.clamp(1, 100) as f64 / 100.0;
+            0.05 + quality_factor * 0.35
+        }
+        // Lossless: quality doesn't apply, screen content deflates to roughly 1 B/px
+        ExportFormat::Apng => 1.0,
+    };
     let estimated_bytes =
         (total_frames as f64 * output_width as f64 * output_height as f64 * bytes_per_pixel) as u64;
     let formatted = format_bytes(estimated_bytes);
@@ -190,7 +281,7 @@ pub fn get_frame_thumbnail(
         return Err("Frame index out of bounds".to_string());
     }
 
-    let frame = &s.frames[frame_index];
+    let frame = s.frames[frame_index].to_rgba();
     let (orig_w, orig_h) = frame.dimensions();
 
     let scale = max_height as f32 / orig_h as f32;
@@ -198,7 +289,7 @@ pub fn get_frame_thumbnail(
     let thumb_h = max_height;
 
     let thumbnail = image::imageops::resize(
-        frame,
+        &*frame,
         thumb_w,
         thumb_h,
         image::imageops::FilterType::Triangle,
@@ -249,14 +340,14 @@ pub fn get_filmstrip(
             0
         };
 
-        let frame = &s.frames[frame_idx];
+        let frame = s.frames[frame_idx].to_rgba();
         let (orig_w, orig_h) = frame.dimensions();
 
         let scale = thumb_height as f32 / orig_h as f32;
         let thumb_w = (orig_w as f32 * scale) as u32;
 
         let thumbnail = image::imageops::resize(
-            frame,
+            &*frame,
             thumb_w,
             thumb_height,
             image::imageops::FilterType::Nearest,
@@ -276,25 +367,100 @@ pub fn get_filmstrip(
     Ok(thumbnails)
 }
 
+// ============ PNG Output ============
+
+/// Luma copy of `img` (alpha kept) for the export-time grayscale filter
+pub(crate) fn to_grayscale(img: &RgbaImage) -> RgbaImage {
+    image::DynamicImage::ImageRgba8(img.clone()).grayscale().to_rgba8()
+}
+
+/// Write `img` as PNG with only the requested ancillary chunks.
+/// `None` emits IHDR/IDAT/IEND only; `Timestamp` adds capture time and app name.
+/// `Fast` compression trades larger files for quicker saves on slow machines.
+pub(crate) fn write_png(
+    img: &RgbaImage,
+    path: &std::path::Path,
+    metadata: PngMetadata,
+    compression: PngCompression,
+    filter: PngFilter,
+    srgb: bool,
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), img.width(), img.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(match compression {
+        PngCompression::Fast => png::Compression::Fast,
+        PngCompression::Best => png::Compression::Best,
+    });
+    let fixed = match filter {
+        PngFilter::Adaptive => None,
+        PngFilter::None => Some(png::FilterType::NoFilter),
+        PngFilter::Sub => Some(png::FilterType::Sub),
+        PngFilter::Up => Some(png::FilterType::Up),
+        PngFilter::Avg => Some(png::FilterType::Avg),
+        PngFilter::Paeth => Some(png::FilterType::Paeth),
+    };
+    match fixed {
+        Some(f) => {
+            encoder.set_filter(f);
+            encoder.set_adaptive_filter(png::AdaptiveFilterType::NonAdaptive);
+        }
+        None => encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive),
+    }
+    if srgb {
+        // sRGB chunk plus the matching gAMA/cHRM for decoders that ignore sRGB
+        encoder.set_source_srgb(png::SrgbRenderingIntent::Perceptual);
+    }
+
+    if metadata == PngMetadata::Timestamp {
+        // PNG spec recommends RFC 1123 for "Creation Time"
+        let created = chrono::Local::now().to_rfc2822();
+        encoder
+            .add_text_chunk("Creation Time".to_string(), created)
+            .map_err(|e| e.to_string())?;
+        encoder
+            .add_text_chunk("Software".to_string(), "Lovshot".to_string())
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(img.as_raw()).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())
+}
+
+/// Write `img` as JPEG (alpha dropped); used when a mode's default format is JPEG
+fn write_jpeg(img: &RgbaImage, path: &std::path::Path) -> Result<(), String> {
+    let rgb = image::DynamicImage::ImageRgba8(img.clone()).to_rgb8();
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(BufWriter::new(file), 90);
+    encoder
+        .encode(rgb.as_raw(), rgb.width(), rgb.height(), image::ExtendedColorType::Rgb8)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub fn save_screenshot(
     app: AppHandle,
     state: tauri::State<SharedState>,
-    scale: Option<f32>,
-    use_cached: Option<bool>,
-    caption_mode: Option<bool>,
+    options: Option<ScreenshotOptions>,
 ) -> Result<String, String> {
-    println!("[DEBUG][save_screenshot] ====== 被调用 ======");
+    log::debug!("[save_screenshot] ====== 被调用 ======");
+    let options = options.unwrap_or_default();
+    let caption_mode = options.caption_mode;
     let s = state.lock().unwrap();
-    let region = s.region.clone().ok_or("No region selected")?;
-    let output_scale = scale.unwrap_or(1.0).clamp(0.1, 1.0);
+    let region = match options.region.clone() {
+        Some(region) => region,
+        None => s.effective_region().ok_or("No region selected")?,
+    };
+    let output_scale = options.scale.unwrap_or(1.0).clamp(0.1, 1.0);
     let cached_snapshot = s.cached_snapshot.clone();
     let screen_scale = s.screen_scale;
     let screen_x = s.screen_x;
     let screen_y = s.screen_y;
-    let is_static_mode = use_cached.unwrap_or(false) && cached_snapshot.is_some();
-    println!(
-        "[DEBUG][save_screenshot] region: x={}, y={}, w={}, h={}, scale={}, static={}, caption_mode={:?}",
+    let is_static_mode = options.use_cached && cached_snapshot.is_some();
+    log::debug!(
+        "[save_screenshot] region: x={}, y={}, w={}, h={}, scale={}, static={}, caption_mode={}",
         region.x, region.y, region.width, region.height, output_scale, is_static_mode, caption_mode
     );
     drop(s);
@@ -303,54 +469,42 @@ pub fn save_screenshot(
     // Dynamic mode: capture live screen
     let captured_rgba = if is_static_mode {
         if let Some(ref snapshot) = cached_snapshot {
-            println!("[DEBUG][save_screenshot] 静态模式，从缓存截图裁剪");
-            // Convert logical pixels to physical pixels
-            let rel_x = ((region.x - screen_x) as f32 * screen_scale).max(0.0) as u32;
-            let rel_y = ((region.y - screen_y) as f32 * screen_scale).max(0.0) as u32;
-            let phys_w = (region.width as f32 * screen_scale) as u32;
-            let phys_h = (region.height as f32 * screen_scale) as u32;
-
-            // Clamp to valid bounds
-            let max_x = snapshot.width().saturating_sub(1);
-            let max_y = snapshot.height().saturating_sub(1);
-            let crop_x = rel_x.min(max_x);
-            let crop_y = rel_y.min(max_y);
-            let crop_w = phys_w.min(snapshot.width().saturating_sub(crop_x));
-            let crop_h = phys_h.min(snapshot.height().saturating_sub(crop_y));
-
-            if crop_w == 0 || crop_h == 0 {
-                return Err("Invalid capture area".to_string());
-            }
-
-            image::imageops::crop_imm(snapshot, crop_x, crop_y, crop_w, crop_h).to_image()
+            log::debug!("[save_screenshot] 静态模式，从缓存截图裁剪");
+            crop_snapshot_region(snapshot, &region, screen_x, screen_y, screen_scale)?
         } else {
             return Err("No cached snapshot for static mode".to_string());
         }
     } else {
-        println!("[DEBUG][save_screenshot] 动态模式，实时截取屏幕");
+        log::debug!("[save_screenshot] 动态模式，实时截取屏幕");
         let screens = Screen::all().map_err(|e| {
-            println!("[DEBUG][save_screenshot] Screen::all 错误: {}", e);
+            log::warn!("[save_screenshot] Screen::all 错误: {}", e);
             e.to_string()
         })?;
         if screens.is_empty() {
-            println!("[DEBUG][save_screenshot] 没有找到屏幕");
+            log::debug!("[save_screenshot] 没有找到屏幕");
             return Err("No screens found".to_string());
         }
-        println!("[DEBUG][save_screenshot] 找到 {} 个屏幕", screens.len());
+        log::debug!("[save_screenshot] 找到 {} 个屏幕", screens.len());
 
-        let screen = &screens[0];
-        println!(
-            "[DEBUG][save_screenshot] 调用 capture_area: x={}, y={}, w={}, h={}",
+        log::debug!(
+            "[save_screenshot] 调用 capture_region: x={}, y={}, w={}, h={}",
             region.x, region.y, region.width, region.height
         );
-        let captured = screen
-            .capture_area(region.x, region.y, region.width, region.height)
-            .map_err(|e| {
-                println!("[DEBUG][save_screenshot] capture_area 错误: {}", e);
-                e.to_string()
-            })?;
-        println!(
-            "[DEBUG][save_screenshot] capture_area 成功, 图像尺寸: {}x{}",
+        // Regions straddling monitors are composited from every overlapped display
+        let captured = crate::capture::capture_region(
+            &screens,
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            Rgba([0, 0, 0, 255]),
+        )
+        .map_err(|e| {
+            log::warn!("[save_screenshot] capture_region 错误: {}", e);
+            e
+        })?;
+        log::debug!(
+            "[save_screenshot] capture_region 成功, 图像尺寸: {}x{}",
             captured.width(),
             captured.height()
         );
@@ -362,7 +516,7 @@ pub fn save_screenshot(
     let mut img = if (output_scale - 1.0).abs() > 0.01 {
         let new_w = (captured_rgba.width() as f32 * output_scale) as u32;
         let new_h = (captured_rgba.height() as f32 * output_scale) as u32;
-        println!("[DEBUG][save_screenshot] 缩放到: {}x{}", new_w, new_h);
+        log::debug!("[save_screenshot] 缩放到: {}x{}", new_w, new_h);
         image::imageops::resize(
             &captured_rgba,
             new_w,
@@ -377,48 +531,80 @@ pub fn save_screenshot(
     let screenshot_number = crate::config::count_screenshots();
     add_screenshot_watermark(&mut img, screenshot_number);
 
+    {
+        let mut s = state.lock().unwrap();
+        s.last_capture = Some(img.clone());
+        s.last_capture_mode = Some(if is_static_mode {
+            CaptureMode::StaticImage
+        } else {
+            CaptureMode::Image
+        });
+    }
+
+    // Post-processing for the outputs only; last_capture keeps the original colors
+    let mut img = if options.grayscale { to_grayscale(&img) } else { img };
+    let color_profile = crate::config::load_config().color_profile;
+    crate::color::prepare_for_export(&mut img, &region, color_profile);
+    // A broken watermark setting shouldn't cost the capture itself
+    match WatermarkStamp::for_export(None) {
+        Ok(Some(stamp)) => stamp.apply(&mut img),
+        Ok(None) => {}
+        Err(e) => log::warn!("[save_screenshot] Skipping watermark: {}", e),
+    }
+
     let tauri_image =
         tauri::image::Image::new_owned(img.as_raw().to_vec(), img.width(), img.height());
     app.clipboard().write_image(&tauri_image).map_err(|e| {
-        println!("[DEBUG][save_screenshot] 复制到剪切板错误: {}", e);
+        log::warn!("[save_screenshot] 复制到剪切板错误: {}", e);
         e.to_string()
     })?;
-    println!("[DEBUG][save_screenshot] 已复制到剪切板");
+    log::debug!("[save_screenshot] 已复制到剪切板");
 
     let output_dir = dirs::picture_dir()
         .or_else(|| dirs::home_dir())
         .unwrap_or_else(|| PathBuf::from("."))
         .join("lovshot");
-    println!("[DEBUG][save_screenshot] 输出目录: {:?}", output_dir);
+    log::debug!("[save_screenshot] 输出目录: {:?}", output_dir);
 
     std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
 
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
     let filename = output_dir.join(format!("screenshot_{}.png", timestamp));
-    println!("[DEBUG][save_screenshot] 保存文件: {:?}", filename);
-
-    img.save(&filename).map_err(|e| {
-        println!("[DEBUG][save_screenshot] 保存文件错误: {}", e);
-        e.to_string()
+    log::debug!("[save_screenshot] 保存文件: {:?}", filename);
+
+    write_png(
+        &img,
+        &filename,
+        options.png_metadata,
+        options.png_compression,
+        options.png_filter,
+        color_profile.tags_srgb(),
+    )
+    .map_err(|e| {
+        log::warn!("[save_screenshot] 保存文件错误: {}", e);
+        e
     })?;
-    println!("[DEBUG][save_screenshot] 文件保存成功");
+    log::debug!("[save_screenshot] 文件保存成功");
 
     let path_str = filename.to_string_lossy().to_string();
     let _ = app.emit("screenshot-saved", &path_str);
+    crate::capture_feedback::play(&app, &region);
 
     // Show preview window: caption mode takes priority, then normal preview
-    let is_caption_mode = caption_mode.unwrap_or(false);
-    if is_caption_mode {
-        println!("[save_screenshot] Opening caption preview window");
+    if caption_mode {
+        log::info!("[save_screenshot] Opening caption preview window");
         if let Err(e) = crate::windows::open_caption_window(&app, &path_str, None) {
-            println!("[save_screenshot] Failed to open caption window: {}", e);
+            log::warn!("[save_screenshot] Failed to open caption window: {}", e);
         }
     } else {
         let cfg = crate::config::load_config();
-        println!("[save_screenshot] screenshot_preview_enabled: {}", cfg.screenshot_preview_enabled);
+        log::info!(
+            "[save_screenshot] screenshot_preview_enabled: {}",
+            cfg.screenshot_preview_enabled
+        );
         if cfg.screenshot_preview_enabled {
             if let Err(e) = crate::windows::open_preview_window(&app, &path_str) {
-                println!("[save_screenshot] Failed to open preview: {}", e);
+                log::warn!("[save_screenshot] Failed to open preview: {}", e);
             }
         }
     }
@@ -432,9 +618,9 @@ pub fn export_gif(
     state: tauri::State<SharedState>,
     config: ExportConfig,
 ) -> Result<(), String> {
-    println!("[DEBUG][export_gif] ====== 被调用 ======");
-    println!(
-        "[DEBUG][export_gif] config: start={}, end={}, scale={}, fps={}, loop={}",
+    log::debug!("[export_gif] ====== 被调用 ======");
+    log::debug!(
+        "[export_gif] config: start={}, end={}, scale={}, fps={}, loop={}",
         config.start_frame,
         config.end_frame,
         config.output_scale,
@@ -445,13 +631,15 @@ pub fn export_gif(
     let mut s = state.lock().unwrap();
 
     if s.frames.is_empty() {
-        println!("[DEBUG][export_gif] 错误: 没有帧可保存");
+        log::warn!("[export_gif] 错误: 没有帧可保存");
         let _ = app.emit(
             "export-complete",
             SaveResult {
                 success: false,
                 path: None,
                 error: Some("No frames to export".to_string()),
+                frame_count: None,
+                note: None,
             },
         );
         return Ok(());
@@ -459,8 +647,8 @@ pub fn export_gif(
 
     let total_frames = s.frames.len();
     let recording_fps = s.recording_fps;
-    println!(
-        "[DEBUG][export_gif] 原始帧数: {}, 录制帧率: {}",
+    log::debug!(
+        "[export_gif] 原始帧数: {}, 录制帧率: {}",
         total_frames, recording_fps
     );
 
@@ -479,13 +667,18 @@ pub fn export_gif(
                     success: false,
                     path: None,
                     error: Some("Invalid frame range".to_string()),
+                    frame_count: None,
+                    note: None,
                 },
             );
             return;
         }
-        let trimmed_frames: Vec<_> = all_frames[start..end].to_vec();
+        let trimmed_frames: Vec<RgbaImage> = all_frames[start..end]
+            .iter()
+            .map(|f| f.to_rgba().into_owned())
+            .collect();
         let trimmed_count = trimmed_frames.len();
-        println!("[DEBUG][export_gif] 裁剪后帧数: {}", trimmed_count);
+        log::debug!("[export_gif] 裁剪后帧数: {}", trimmed_count);
 
         // Calculate target frame count based on output duration and fps
         // output_duration = original_duration / speed
@@ -496,7 +689,9 @@ pub fn export_gif(
         let target_frame_count = (output_duration * config.target_fps as f32).round() as usize;
         let target_frame_count = target_frame_count.max(1);
 
-        // Sample frames uniformly
+        // Sample frames uniformly (dropped frames are spread evenly across the clip)
+        // When the target fps is higher than the capture fps we keep every frame and
+        // stretch the per-frame delay instead of duplicating frames.
         let sampled_frames: Vec<_> = if target_frame_count >= trimmed_count {
             trimmed_frames
         } else {
@@ -509,11 +704,18 @@ pub fn export_gif(
                 })
                 .collect()
         };
-        println!(
-            "[DEBUG][export_gif] 采样后: target={}, 实际={}, speed={}",
+        // Frames per second actually played back after sampling
+        let effective_fps = if output_duration > 0.0 {
+            (sampled_frames.len() as f32 / output_duration).min(config.target_fps.max(1) as f32)
+        } else {
+            config.target_fps as f32
+        };
+        log::debug!(
+            "[export_gif] 采样后: target={}, 实际={}, speed={}, fps={:.1}",
             target_frame_count,
             sampled_frames.len(),
-            speed
+            speed,
+            effective_fps
         );
 
         if sampled_frames.is_empty() {
@@ -523,6 +725,8 @@ pub fn export_gif(
                     success: false,
                     path: None,
                     error: Some("No frames after sampling".to_string()),
+                    frame_count: None,
+                    note: None,
                 },
             );
             return;
@@ -530,7 +734,7 @@ pub fn export_gif(
 
         let output_scale = config.output_scale.clamp(0.1, 1.0);
         let scaled_frames: Vec<RgbaImage> = if (output_scale - 1.0).abs() > 0.01 {
-            println!("[DEBUG][export_gif] 缩放帧: scale={}", output_scale);
+            log::debug!("[export_gif] 缩放帧: scale={}", output_scale);
             sampled_frames
                 .into_iter()
                 .map(|f| {
@@ -543,13 +747,40 @@ pub fn export_gif(
             sampled_frames
         };
 
+        let mut scaled_frames = scaled_frames;
+        match WatermarkStamp::for_export(config.watermark.as_ref()) {
+            Ok(Some(stamp)) => {
+                let stamped = if stamp.all_frames { scaled_frames.len() } else { 1 };
+                scaled_frames.iter_mut().take(stamped).for_each(|f| stamp.apply(f));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                emit_export_result(&app, Err(e), 0, config.format);
+                return;
+            }
+        }
+
+        // Static stretches: keep one frame and extend its delay instead of repeating it
+        let (scaled_frames, spans) = match config.dedup_threshold {
+            Some(threshold) if threshold > 0.0 => {
+                let before = scaled_frames.len();
+                let (frames, spans) = dedup_frames(scaled_frames, threshold);
+                log::debug!("[export_gif] 去重: {} -> {} 帧", before, frames.len());
+                (frames, spans)
+            }
+            _ => {
+                let spans = vec![1; scaled_frames.len()];
+                (scaled_frames, spans)
+            }
+        };
+
         let gif_loop_mode = match config.loop_mode.as_str() {
             "once" => GifLoopMode::Once,
             "pingpong" => GifLoopMode::PingPong,
             _ => GifLoopMode::Infinite,
         };
 
-        let final_frames: Vec<RgbaImage> = match gif_loop_mode {
+        let (final_frames, final_spans): (Vec<RgbaImage>, Vec<u32>) = match gif_loop_mode {
             GifLoopMode::PingPong if scaled_frames.len() > 2 => {
                 let mut result = scaled_frames.clone();
                 let reversed: Vec<_> = scaled_frames[1..scaled_frames.len() - 1]
@@ -558,14 +789,16 @@ pub fn export_gif(
                     .cloned()
                     .collect();
                 result.extend(reversed);
-                println!(
-                    "[DEBUG][export_gif] PingPong 模式: {} -> {} 帧",
+                let mut result_spans = spans.clone();
+                result_spans.extend(spans[1..spans.len() - 1].iter().rev());
+                log::debug!(
+                    "[export_gif] PingPong 模式: {} -> {} 帧",
                     scaled_frames.len(),
                     result.len()
                 );
-                result
+                (result, result_spans)
             }
-            _ => scaled_frames,
+            _ => (scaled_frames, spans),
         };
 
         let output_dir = dirs::picture_dir()
@@ -580,6 +813,8 @@ pub fn export_gif(
                     success: false,
                     path: None,
                     error: Some(e.to_string()),
+                    frame_count: None,
+                    note: None,
                 },
             );
             return;
@@ -590,37 +825,58 @@ pub fn export_gif(
             PathBuf::from(custom_path)
         } else {
             let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-            output_dir.join(format!("recording_{}.gif", timestamp))
+            let ext = match config.format {
+                ExportFormat::Gif => "gif",
+                ExportFormat::Apng => "png",
+            };
+            output_dir.join(format!("recording_{}.{}", timestamp, ext))
         };
-        println!("[DEBUG][export_gif] 保存路径: {:?}", filename);
+        log::debug!("[export_gif] 保存路径: {:?}", filename);
 
         let (width, height) = final_frames[0].dimensions();
         let frame_count = final_frames.len();
-        println!(
-            "[DEBUG][export_gif] 开始编码: {}x{}, {} 帧",
+        log::debug!(
+            "[export_gif] 开始编码: {}x{}, {} 帧",
             width, height, frame_count
         );
 
+        if config.format == ExportFormat::Apng {
+            // Explicit loop_count (total plays) wins over loop_mode
+            let plays = match (config.loop_count, &gif_loop_mode) {
+                (Some(n), _) => n as u32,
+                (None, GifLoopMode::Once) => 1,
+                (None, _) => 0,
+            };
+            let frame_ms = if effective_fps > 0.0 { 1000.0 / effective_fps } else { 100.0 };
+            let result = write_apng(&app, &filename, final_frames, &final_spans, frame_ms, plays)
+                .map(|_| filename.to_string_lossy().to_string());
+            emit_export_result(&app, result, frame_count, config.format);
+            return;
+        }
+
         let result = (|| -> Result<String, String> {
             let mut file = File::create(&filename).map_err(|e| e.to_string())?;
             let mut encoder = Encoder::new(&mut file, width as u16, height as u16, &[])
                 .map_err(|e| e.to_string())?;
 
-            let repeat = match gif_loop_mode {
-                GifLoopMode::Once => Repeat::Finite(0),
-                _ => Repeat::Infinite,
+            // Explicit loop_count wins over loop_mode; GIF counts repeats after the first play
+            let repeat = match (config.loop_count, &gif_loop_mode) {
+                (Some(0), _) => Repeat::Infinite,
+                (Some(n), _) => Repeat::Finite(n - 1),
+                (None, GifLoopMode::Once) => Repeat::Finite(0),
+                (None, _) => Repeat::Infinite,
             };
             encoder.set_repeat(repeat).map_err(|e| e.to_string())?;
 
             // GIF delay is in 1/100 seconds: delay = 100 / fps
             // (speed already affects frame count, so delay is just based on fps)
-            let delay = if config.target_fps > 0 {
-                (100.0 / config.target_fps as f32).max(1.0) as u16
+            let delay = if effective_fps > 0.0 {
+                (100.0 / effective_fps).round().max(1.0) as u16
             } else {
                 10
             };
 
-            for (i, rgba_img) in final_frames.into_iter().enumerate() {
+            for (i, (rgba_img, span)) in final_frames.into_iter().zip(final_spans).enumerate() {
                 let mut pixels: Vec<u8> = Vec::with_capacity((width * height * 4) as usize);
                 for pixel in rgba_img.pixels() {
                     pixels.push(pixel[0]);
@@ -637,7 +893,10 @@ pub fn export_gif(
                     &mut pixels,
                     gif_speed as i32,
                 );
-                frame.delay = delay;
+                if config.dither {
+                    dither_frame(&mut frame, &pixels);
+                }
+                frame.delay = (delay as u32 * span).min(u16::MAX as u32) as u16;
                 encoder.write_frame(&frame).map_err(|e| e.to_string())?;
 
                 let _ = app.emit(
@@ -650,40 +909,259 @@ pub fn export_gif(
                 );
 
                 if i == 0 || (i + 1) % 10 == 0 || i + 1 == frame_count {
-                    println!("[DEBUG][export_gif] 编码帧 {}/{}", i + 1, frame_count);
+                    log::debug!("[export_gif] 编码帧 {}/{}", i + 1, frame_count);
                 }
             }
 
             Ok(filename.to_string_lossy().to_string())
         })();
 
-        match result {
-            Ok(path) => {
-                println!("[DEBUG][export_gif] ====== 完成 ====== 路径: {}", path);
-                let _ = app.emit(
-                    "export-complete",
-                    SaveResult {
-                        success: true,
-                        path: Some(path),
-                        error: None,
-                    },
-                );
+        emit_export_result(&app, result, frame_count, config.format);
+    });
+
+    Ok(())
+}
+
+fn format_note(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Gif => "GIF: 256-color palette per frame; smallest, gradients may band",
+        ExportFormat::Apng => "APNG: lossless true color + alpha; typically several times larger",
+    }
+}
+
+fn emit_export_result(
+    app: &AppHandle,
+    result: Result<String, String>,
+    frame_count: usize,
+    format: ExportFormat,
+) {
+    match result {
+        Ok(path) => {
+            log::debug!("[export_gif] ====== 完成 ====== 路径: {}", path);
+            let _ = app.emit(
+                "export-complete",
+                SaveResult {
+                    success: true,
+                    path: Some(path),
+                    error: None,
+                    frame_count: Some(frame_count),
+                    note: Some(format_note(format).to_string()),
+                },
+            );
+        }
+        Err(e) => {
+            log::warn!("[export_gif] ====== 错误 ====== {}", e);
+            let _ = app.emit(
+                "export-complete",
+                SaveResult {
+                    success: false,
+                    path: None,
+                    error: Some(e),
+                    frame_count: None,
+                    note: None,
+                },
+            );
+        }
+    }
+}
+
+/// Encode full RGBA frames as an animated PNG. Each frame lasts `frame_ms` times its
+/// span (from dedup); `plays` = 0 loops forever.
+fn write_apng(
+    app: &AppHandle,
+    path: &std::path::Path,
+    frames: Vec<RgbaImage>,
+    spans: &[u32],
+    frame_ms: f32,
+    plays: u32,
+) -> Result<(), String> {
+    let (width, height) = frames[0].dimensions();
+    let total = frames.len();
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(png::Compression::Best);
+    encoder.set_animated(total as u32, plays).map_err(|e| e.to_string())?;
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+
+    for (i, (img, span)) in frames.into_iter().zip(spans).enumerate() {
+        // fcTL delay is a fraction of seconds; use milliseconds
+        let delay_ms = (frame_ms * *span as f32).round().clamp(1.0, u16::MAX as f32) as u16;
+        writer.set_frame_delay(delay_ms, 1000).map_err(|e| e.to_string())?;
+        writer.write_image_data(img.as_raw()).map_err(|e| e.to_string())?;
+
+        let _ = app.emit(
+            "export-progress",
+            ExportProgress {
+                current: i + 1,
+                total,
+                stage: "encoding".to_string(),
+            },
+        );
+    }
+    writer.finish().map_err(|e| e.to_string())
+}
+
+/// Write the recorded frames to `directory` as frame_0001.png, frame_0002.png, ...
+/// `every_nth` keeps every Nth frame (default 1 = all). Files are numbered consecutively
+/// regardless of the stride; progress is reported via `export-progress`.
+#[tauri::command]
+pub async fn export_frame_sequence(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    directory: String,
+    every_nth: Option<usize>,
+) -> Result<FrameSequenceExport, String> {
+    let step = every_nth.unwrap_or(1).max(1);
+    let frames: Vec<RgbaImage> = {
+        let s = state.lock().unwrap();
+        if s.frames.is_empty() {
+            return Err("No frames to export".to_string());
+        }
+        s.frames.iter().step_by(step).map(|f| f.to_rgba().into_owned()).collect()
+    };
+    let dir = PathBuf::from(&directory);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    log::info!("[export_frame_sequence] {} frames (every {}) -> {:?}", frames.len(), step, dir);
+
+    tokio::task::spawn_blocking(move || {
+        let total = frames.len();
+        // Zero-pad to at least 4 digits so files sort correctly
+        let digits = total.to_string().len().max(4);
+        for (i, frame) in frames.iter().enumerate() {
+            let path = dir.join(format!("frame_{:0width$}.png", i + 1, width = digits));
+            // Fast compression: hundreds of frames at Best takes too long
+            write_png(
+                frame,
+                &path,
+                PngMetadata::None,
+                PngCompression::Fast,
+                PngFilter::Adaptive,
+                false,
+            )?;
+            let _ = app.emit(
+                "export-progress",
+                ExportProgress {
+                    current: i + 1,
+                    total,
+                    stage: "frames".to_string(),
+                },
+            );
+        }
+        Ok(FrameSequenceExport {
+            count: total,
+            directory: dir.to_string_lossy().to_string(),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Collapse runs of near-identical consecutive frames (average RGB diff below `threshold`,
+/// same measure the scroll listener uses). Returns the kept frames and how many source
+/// frames each one stands for, so the caller can extend its delay.
+fn dedup_frames(frames: Vec<RgbaImage>, threshold: f32) -> (Vec<RgbaImage>, Vec<u32>) {
+    // Denser sampling than scroll matching: small UI changes must survive
+    let tolerance = crate::types::ScrollChangeTolerance {
+        threshold,
+        sample_rows: (1..20).map(|i| i as f32 / 20.0).collect(),
+        sample_step: 2,
+        ..Default::default()
+    };
+    let mut kept: Vec<RgbaImage> = Vec::with_capacity(frames.len());
+    let mut spans: Vec<u32> = Vec::with_capacity(frames.len());
+    for frame in frames {
+        // Compare with the run's kept frame so slow drift still starts a new frame
+        match (kept.last(), spans.last_mut()) {
+            (Some(last), Some(span))
+                if super::frames_nearly_identical(last, &frame, &tolerance) =>
+            {
+                *span += 1;
             }
-            Err(e) => {
-                println!("[DEBUG][export_gif] ====== 错误 ====== {}", e);
-                let _ = app.emit(
-                    "export-complete",
-                    SaveResult {
-                        success: false,
-                        path: None,
-                        error: Some(e),
-                    },
-                );
+            _ => {
+                kept.push(frame);
+                spans.push(1);
             }
         }
-    });
+    }
+    (kept, spans)
+}
 
-    Ok(())
+/// Re-map a quantized GIF frame onto its own palette with Floyd-Steinberg error diffusion.
+/// `rgba` is the source pixel data the frame was quantized from.
+fn dither_frame(frame: &mut Frame, rgba: &[u8]) {
+    let Some(palette) = frame.palette.as_ref() else {
+        return;
+    };
+    let colors: Vec<[i32; 3]> = palette
+        .chunks_exact(3)
+        .map(|c| [c[0] as i32, c[1] as i32, c[2] as i32])
+        .collect();
+    if colors.is_empty() {
+        return;
+    }
+
+    let width = frame.width as usize;
+    let height = frame.height as usize;
+    let transparent = frame.transparent;
+
+    let mut work: Vec<[i32; 3]> = rgba
+        .chunks_exact(4)
+        .map(|p| [p[0] as i32, p[1] as i32, p[2] as i32])
+        .collect();
+    let mut indices = vec![0u8; width * height];
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            if let Some(t) = transparent {
+                if rgba[i * 4 + 3] == 0 {
+                    indices[i] = t;
+                    continue;
+                }
+            }
+
+            let px = work[i];
+            let (best, _) = colors
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| Some(*idx as u8) != transparent)
+                .map(|(idx, c)| {
+                    let d = (0..3).map(|k| (px[k] - c[k]).pow(2)).sum::<i32>();
+                    (idx, d)
+                })
+                .min_by_key(|&(_, d)| d)
+                .unwrap_or((0, 0));
+            indices[i] = best as u8;
+
+            let err = [
+                px[0] - colors[best][0],
+                px[1] - colors[best][1],
+                px[2] - colors[best][2],
+            ];
+            let mut spread = |nx: usize, ny: usize, weight: i32| {
+                let j = ny * width + nx;
+                for k in 0..3 {
+                    work[j][k] = (work[j][k] + err[k] * weight / 16).clamp(0, 255);
+                }
+            };
+            if x + 1 < width {
+                spread(x + 1, y, 7);
+            }
+            if y + 1 < height {
+                if x > 0 {
+                    spread(x - 1, y + 1, 3);
+                }
+                spread(x, y + 1, 5);
+                if x + 1 < width {
+                    spread(x + 1, y + 1, 1);
+                }
+            }
+        }
+    }
+
+    frame.buffer = std::borrow::Cow::Owned(indices);
 }
 
 #[tauri::command]
@@ -981,7 +1459,7 @@ pub async fn get_stats() -> Result<StatsResponse, String> {
 
 #[tauri::command]
 pub fn save_caption(app: AppHandle, path: String, caption: String, close_window: Option<bool>) -> Result<(), String> {
-    println!("[save_caption] path: {}, caption: {}, close: {:?}", path, caption, close_window);
+    log::info!("[save_caption] path: {}, caption: {}, close: {:?}", path, caption, close_window);
 
     let input_path = PathBuf::from(&path);
     if !input_path.exists() {
@@ -994,25 +1472,25 @@ pub fn save_caption(app: AppHandle, path: String, caption: String, close_window:
     let app_clone = app.clone();
     std::thread::spawn(move || {
         if let Err(e) = write_png_comment(&path_clone, &caption_clone) {
-            println!("[save_caption] PNG comment error: {}", e);
+            log::warn!("[save_caption] PNG comment error: {}", e);
         } else {
             // Emit event to notify frontend
             let _ = app_clone.emit("caption-saved", serde_json::json!({
                 "path": path_clone,
                 "caption": caption_clone
             }));
-            println!("[save_caption] Emitted caption-saved event");
+            log::info!("[save_caption] Emitted caption-saved event");
         }
     });
 
-    println!("[save_caption] Caption save initiated");
+    log::info!("[save_caption] Caption save initiated");
 
     // Close all caption windows from backend (unless explicitly disabled)
     if close_window.unwrap_or(true) {
         use tauri::Manager;
         for (label, window) in app.webview_windows() {
             if label.starts_with("caption-") {
-                println!("[save_caption] Closing window: {}", label);
+                log::info!("[save_caption] Closing window: {}", label);
                 let _ = window.destroy();
             }
         }
@@ -1057,7 +1535,7 @@ tell application "Finder" to set comment of theFile to "{}""#,
         }
     }
 
-    println!("[save_caption] Finder comment set successfully");
+    log::info!("[save_caption] Finder comment set successfully");
     Ok(())
 }
 
@@ -1097,7 +1575,7 @@ pub fn get_image_description(path: String) -> Option<String> {
 pub fn delete_file(path: String) -> Result<(), String> {
     // Move to trash instead of permanent delete
     trash::delete(&path).map_err(|e| format!("Failed to delete file: {}", e))?;
-    println!("[delete_file] Moved to trash: {}", path);
+    log::info!("[delete_file] Moved to trash: {}", path);
     Ok(())
 }
 
@@ -1185,7 +1663,7 @@ pub fn create_folder(name: String) -> Result<FolderInfo, String> {
     }
 
     std::fs::create_dir(&folder_path).map_err(|e| e.to_string())?;
-    println!("[create_folder] Created: {:?}", folder_path);
+    log::info!("[create_folder] Created: {:?}", folder_path);
 
     Ok(FolderInfo {
         name: name.to_string(),
@@ -1219,7 +1697,7 @@ pub fn delete_folder(path: String) -> Result<(), String> {
     }
 
     std::fs::remove_dir_all(&folder_path).map_err(|e| e.to_string())?;
-    println!("[delete_folder] Deleted: {:?}", folder_path);
+    log::info!("[delete_folder] Deleted: {:?}", folder_path);
     Ok(())
 }
 
@@ -1246,7 +1724,7 @@ pub fn rename_folder(path: String, new_name: String) -> Result<FolderInfo, Strin
     }
 
     std::fs::rename(&old_path, &new_path).map_err(|e| e.to_string())?;
-    println!("[rename_folder] Renamed {:?} -> {:?}", old_path, new_path);
+    log::info!("[rename_folder] Renamed {:?} -> {:?}", old_path, new_path);
 
     let file_count = std::fs::read_dir(&new_path)
         .map(|entries| {
@@ -1316,7 +1794,7 @@ pub fn move_to_folder(file_paths: Vec<String>, folder_path: Option<String>) -> R
         };
 
         std::fs::rename(&src, &final_dest).map_err(|e| e.to_string())?;
-        println!("[move_to_folder] Moved {:?} -> {:?}", src, final_dest);
+        log::info!("[move_to_folder] Moved {:?} -> {:?}", src, final_dest);
         new_paths.push(final_dest.to_string_lossy().to_string());
     }
 
@@ -1409,7 +1887,7 @@ pub fn export_folder_to_md(folder_path: Option<String>, format: String) -> Resul
     let md_filename = scan_dir.join(format!("{}_export_{}.md", folder_name, timestamp));
     std::fs::write(&md_filename, &md_content).map_err(|e| e.to_string())?;
 
-    println!("[export_folder_to_md] Exported to {:?}", md_filename);
+    log::info!("[export_folder_to_md] Exported to {:?}", md_filename);
     Ok(md_filename.to_string_lossy().to_string())
 }
 
@@ -1423,6 +1901,22 @@ pub fn open_zoom_viewer(app: AppHandle, path: String) -> Result<(), String> {
     crate::windows::open_zoom_window(&app, &path)
 }
 
+/// Show a capture in the borderless review window (save / copy / annotate)
+#[tauri::command]
+pub fn open_review_window(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+) -> Result<(), String> {
+    let source = source.unwrap_or_default();
+    let (w, h) = {
+        let mut s = state.lock().unwrap();
+        let img = crate::annotate::source_image(&mut s, source).ok_or("No image available")?;
+        img.dimensions()
+    };
+    crate::windows::open_review_window(&app, source, w, h)
+}
+
 #[tauri::command]
 pub fn copy_image_to_clipboard(app: AppHandle, path: String) -> Result<(), String> {
     let img = image::open(&path).map_err(|e| format!("Failed to open image: {}", e))?;
@@ -1434,30 +1928,57 @@ pub fn copy_image_to_clipboard(app: AppHandle, path: String) -> Result<(), Strin
 
 #[tauri::command]
 pub fn copy_rgba_to_clipboard(app: AppHandle, data: Vec<u8>, width: u32, height: u32) -> Result<(), String> {
-    println!("[copy_rgba_to_clipboard] {}x{}, data len: {}", width, height, data.len());
+    log::info!("[copy_rgba_to_clipboard] {}x{}, data len: {}", width, height, data.len());
     let tauri_image = tauri::image::Image::new_owned(data, width, height);
     app.clipboard().write_image(&tauri_image).map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
-    println!("[copy_rgba_to_clipboard] Success");
+    log::info!("[copy_rgba_to_clipboard] Success");
     Ok(())
 }
 
 #[tauri::command]
 pub fn save_rgba_to_file(data: Vec<u8>, width: u32, height: u32, path: String) -> Result<(), String> {
-    println!("[save_rgba_to_file] {}x{} -> {}", width, height, path);
+    log::info!("[save_rgba_to_file] {}x{} -> {}", width, height, path);
     let img = image::RgbaImage::from_raw(width, height, data)
         .ok_or("Failed to create image from RGBA data")?;
     img.save(&path).map_err(|e| format!("Failed to save image: {}", e))?;
-    println!("[save_rgba_to_file] Success");
+    log::info!("[save_rgba_to_file] Success");
     Ok(())
 }
 
+/// Crop a logical-pixel region out of the (physical-pixel) cached snapshot
+pub(crate) fn crop_snapshot_region(
+    snapshot: &RgbaImage,
+    region: &crate::types::Region,
+    screen_x: i32,
+    screen_y: i32,
+    screen_scale: f32,
+) -> Result<RgbaImage, String> {
+    let phys = logical_to_physical(region, (screen_x, screen_y), screen_scale);
+    let rel_x = phys.x.max(0) as u32;
+    let rel_y = phys.y.max(0) as u32;
+    let (phys_w, phys_h) = (phys.width, phys.height);
+
+    let max_x = snapshot.width().saturating_sub(1);
+    let max_y = snapshot.height().saturating_sub(1);
+    let crop_x = rel_x.min(max_x);
+    let crop_y = rel_y.min(max_y);
+    let crop_w = phys_w.min(snapshot.width().saturating_sub(crop_x));
+    let crop_h = phys_h.min(snapshot.height().saturating_sub(crop_y));
+
+    if crop_w == 0 || crop_h == 0 {
+        return Err("Invalid capture area".to_string());
+    }
+
+    Ok(image::imageops::crop_imm(snapshot, crop_x, crop_y, crop_w, crop_h).to_image())
+}
+
 /// Capture a region and return base64 PNG for annotation editing
 #[tauri::command(rename_all = "camelCase")]
 pub fn capture_region_preview(
     state: tauri::State<SharedState>,
     region: crate::types::Region,
 ) -> Result<String, String> {
-    println!("[capture_region_preview] region: x={}, y={}, w={}, h={}",
+    log::info!("[capture_region_preview] region: x={}, y={}, w={}, h={}",
         region.x, region.y, region.width, region.height);
 
     let s = state.lock().unwrap();
@@ -1469,40 +1990,24 @@ pub fn capture_region_preview(
 
     // Try to use cached snapshot first (for static mode)
     let captured_rgba = if let Some(ref snapshot) = cached_snapshot {
-        println!("[capture_region_preview] Using cached snapshot");
-        // Convert logical pixels to physical pixels
-        let rel_x = ((region.x - screen_x) as f32 * screen_scale).max(0.0) as u32;
-        let rel_y = ((region.y - screen_y) as f32 * screen_scale).max(0.0) as u32;
-        let phys_w = (region.width as f32 * screen_scale) as u32;
-        let phys_h = (region.height as f32 * screen_scale) as u32;
-
-        let max_x = snapshot.width().saturating_sub(1);
-        let max_y = snapshot.height().saturating_sub(1);
-        let crop_x = rel_x.min(max_x);
-        let crop_y = rel_y.min(max_y);
-        let crop_w = phys_w.min(snapshot.width().saturating_sub(crop_x));
-        let crop_h = phys_h.min(snapshot.height().saturating_sub(crop_y));
-
-        if crop_w == 0 || crop_h == 0 {
-            return Err("Invalid capture area".to_string());
-        }
-
-        image::imageops::crop_imm(snapshot, crop_x, crop_y, crop_w, crop_h).to_image()
+        log::info!("[capture_region_preview] Using cached snapshot");
+        crop_snapshot_region(snapshot, &region, screen_x, screen_y, screen_scale)?
     } else {
         // Capture live screen
-        println!("[capture_region_preview] Capturing live screen");
+        log::info!("[capture_region_preview] Capturing live screen");
         let screens = Screen::all().map_err(|e| e.to_string())?;
         if screens.is_empty() {
             return Err("No screens found".to_string());
         }
 
-        let screen = &screens[0];
-        let captured = screen
-            .capture_area(region.x, region.y, region.width, region.height)
-            .map_err(|e| e.to_string())?;
-
-        RgbaImage::from_raw(captured.width(), captured.height(), captured.into_raw())
-            .ok_or("Failed to convert image")?
+        crate::capture::capture_region(
+            &screens,
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            Rgba([0, 0, 0, 255]),
+        )?
     };
 
     // Encode to PNG base64
@@ -1520,14 +2025,56 @@ pub fn capture_region_preview(
     Ok(format!("data:image/png;base64,{}", base64_str))
 }
 
+/// Capture only the given window (ignores windows overlapping it) as base64 PNG.
+/// Falls back to a plain region capture when the window-id capture fails.
+#[tauri::command(rename_all = "camelCase")]
+pub fn capture_window_isolated(
+    state: tauri::State<SharedState>,
+    window_id: u32,
+    region: crate::types::Region,
+) -> Result<String, String> {
+    #[cfg(target_os = "macos")]
+    {
+        use crate::native_screenshot;
+
+        if let Some(rgba) = native_screenshot::capture_window_cgimage(window_id)
+            .and_then(|img| native_screenshot::cgimage_to_rgba(&img))
+        {
+            log::info!(
+                "[capture_window_isolated] window {} -> {}x{}",
+                window_id,
+                rgba.width(),
+                rgba.height()
+            );
+            return crate::annotate::encode_png_data_url(&rgba);
+        }
+        log::warn!(
+            "[capture_window_isolated] window {} capture failed, falling back to region",
+            window_id
+        );
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = window_id;
+
+    capture_region_preview(state, region)
+}
+
 /// Save an annotated screenshot from base64 PNG data
 #[tauri::command(rename_all = "camelCase")]
 pub fn save_annotated_screenshot(
     app: AppHandle,
+    state: tauri::State<SharedState>,
     image_data: String,
     caption_mode: Option<bool>,
+    png_metadata: Option<PngMetadata>,
+    png_compression: Option<PngCompression>,
+    png_filter: Option<PngFilter>,
+    grayscale: Option<bool>,
 ) -> Result<String, String> {
-    println!("[save_annotated_screenshot] Saving annotated screenshot, caption_mode={:?}", caption_mode);
+    log::info!(
+        "[save_annotated_screenshot] Saving annotated screenshot, caption_mode={:?}",
+        caption_mode
+    );
 
     // Decode base64
     let decoded = STANDARD.decode(&image_data)
@@ -1538,12 +2085,25 @@ pub fn save_annotated_screenshot(
         .map_err(|e| format!("Image load error: {}", e))?
         .to_rgba8();
 
-    println!("[save_annotated_screenshot] Image size: {}x{}", img.width(), img.height());
+    log::info!("[save_annotated_screenshot] Image size: {}x{}", img.width(), img.height());
 
     // Add watermark with screenshot number
     let screenshot_number = crate::config::count_screenshots();
     add_screenshot_watermark(&mut img, screenshot_number);
 
+    let region = {
+        let mut s = state.lock().unwrap();
+        s.last_capture = Some(img.clone());
+        s.last_capture_mode = Some(CaptureMode::Image);
+        s.region.clone()
+    };
+
+    let mut img = if grayscale.unwrap_or(false) { to_grayscale(&img) } else { img };
+    let color_profile = crate::config::load_config().color_profile;
+    if let Some(region) = &region {
+        crate::color::prepare_for_export(&mut img, region, color_profile);
+    }
+
     // Copy to clipboard
     let tauri_image = tauri::image::Image::new_owned(
         img.as_raw().to_vec(),
@@ -1552,7 +2112,7 @@ pub fn save_annotated_screenshot(
     );
     app.clipboard().write_image(&tauri_image)
         .map_err(|e| format!("Clipboard error: {}", e))?;
-    println!("[save_annotated_screenshot] Copied to clipboard");
+    log::info!("[save_annotated_screenshot] Copied to clipboard");
 
     // Save to file
     let output_dir = dirs::picture_dir()
@@ -1565,8 +2125,16 @@ pub fn save_annotated_screenshot(
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
     let filename = output_dir.join(format!("screenshot_{}.png", timestamp));
 
-    img.save(&filename).map_err(|e| format!("Save error: {}", e))?;
-    println!("[save_annotated_screenshot] Saved to {:?}", filename);
+    write_png(
+        &img,
+        &filename,
+        png_metadata.unwrap_or_default(),
+        png_compression.unwrap_or_default(),
+        png_filter.unwrap_or_default(),
+        color_profile.tags_srgb(),
+    )
+    .map_err(|e| format!("Save error: {}", e))?;
+    log::info!("[save_annotated_screenshot] Saved to {:?}", filename);
 
     let path_str = filename.to_string_lossy().to_string();
     let _ = app.emit("screenshot-saved", &path_str);
@@ -1575,16 +2143,390 @@ pub fn save_annotated_screenshot(
     let is_caption_mode = caption_mode.unwrap_or(false);
     if is_caption_mode {
         if let Err(e) = crate::windows::open_caption_window(&app, &path_str, None) {
-            println!("[save_annotated_screenshot] Failed to open caption window: {}", e);
+            log::warn!("[save_annotated_screenshot] Failed to open caption window: {}", e);
         }
     } else {
         let cfg = crate::config::load_config();
         if cfg.screenshot_preview_enabled {
             if let Err(e) = crate::windows::open_preview_window(&app, &path_str) {
-                println!("[save_annotated_screenshot] Failed to open preview: {}", e);
+                log::warn!("[save_annotated_screenshot] Failed to open preview: {}", e);
             }
         }
     }
 
     Ok(path_str)
 }
+
+/// Save the most recent capture to the configured output directory without a dialog
+/// Filename comes from `filename_template`, format from `default_formats`; returns the path
+#[tauri::command]
+pub fn quick_save(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    png_metadata: Option<PngMetadata>,
+    png_compression: Option<PngCompression>,
+    png_filter: Option<PngFilter>,
+    grayscale: Option<bool>,
+) -> Result<String, String> {
+    let (img, mode, region) = {
+        let s = state.lock().unwrap();
+        let img = s.last_capture.clone().ok_or("No capture to save")?;
+        (img, s.last_capture_mode.unwrap_or_default(), s.region.clone())
+    };
+    let mut img = if grayscale.unwrap_or(false) { to_grayscale(&img) } else { img };
+
+    let cfg = crate::config::load_config();
+    if let Some(region) = &region {
+        crate::color::prepare_for_export(&mut img, region, cfg.color_profile);
+    }
+    match WatermarkStamp::for_export(None) {
+        Ok(Some(stamp)) => stamp.apply(&mut img),
+        Ok(None) => {}
+        Err(e) => log::warn!("[quick_save] Skipping watermark: {}", e),
+    }
+    let output_dir = crate::config::get_output_dir(&cfg);
+    std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
+
+    let now = chrono::Local::now();
+    let counter = crate::config::next_filename_counter(&cfg.filename_template);
+    let stem = crate::config::render_filename_template(&cfg.filename_template, mode, counter, &now);
+    let format = cfg.default_formats.get(mode);
+    let ext = if format == CaptureFormat::Jpeg { "jpg" } else { "png" };
+
+    // Never overwrite an existing capture
+    let mut filename = output_dir.join(format!("{}.{}", stem, ext));
+    let mut suffix = 1;
+    while filename.exists() {
+        filename = output_dir.join(format!("{}_{}.{}", stem, suffix, ext));
+        suffix += 1;
+    }
+
+    if format == CaptureFormat::Jpeg {
+        write_jpeg(&img, &filename)
+    } else {
+        write_png(
+            &img,
+            &filename,
+            png_metadata.unwrap_or_default(),
+            png_compression.unwrap_or_default(),
+            png_filter.unwrap_or_default(),
+            cfg.color_profile.tags_srgb(),
+        )
+    }
+    .map_err(|e| format!("Save error: {}", e))?;
+    log::info!("[quick_save] Saved to {:?}", filename);
+
+    let path_str = filename.to_string_lossy().to_string();
+    let _ = app.emit("screenshot-saved", &path_str);
+    Ok(path_str)
+}
+
+/// Crop every accumulated region, with `capture_padding` applied, from the cached snapshot.
+/// Returns PNG data URLs, or with `save` the paths of numbered files in the output dir,
+/// written with the color profile and watermark settings like a regular screenshot.
+#[tauri::command]
+pub fn capture_all_regions(
+    state: tauri::State<SharedState>,
+    save: Option<bool>,
+) -> Result<Vec<String>, String> {
+    let displays = crate::capture::display_regions()?;
+    let (snapshot, regions, screen_x, screen_y, screen_scale) = {
+        let s = state.lock().unwrap();
+        if s.regions.is_empty() {
+            return Err("No regions selected".to_string());
+        }
+        (
+            s.cached_snapshot.clone().ok_or("No cached snapshot")?,
+            s.regions
+                .iter()
+                .map(|r| crate::capture::pad_region_on(r, s.capture_padding, &displays))
+                .collect::<Vec<_>>(),
+            s.screen_x,
+            s.screen_y,
+            s.screen_scale,
+        )
+    };
+
+    let crops = regions
+        .iter()
+        .map(|r| crop_snapshot_region(&snapshot, r, screen_x, screen_y, screen_scale))
+        .collect::<Result<Vec<_>, _>>()?;
+    log::info!("[capture_all_regions] {} regions", crops.len());
+
+    if !save.unwrap_or(false) {
+        return crops.iter().map(crate::annotate::encode_png_data_url).collect();
+    }
+
+    let cfg = crate::config::load_config();
+    let output_dir = crate::config::get_output_dir(&cfg);
+    std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
+    let now = chrono::Local::now();
+    let counter = crate::config::next_filename_counter(&cfg.filename_template);
+    let stem = crate::config::render_filename_template(
+        &cfg.filename_template,
+        CaptureMode::StaticImage,
+        counter,
+        &now,
+    );
+
+    let stamp = WatermarkStamp::for_export(None).unwrap_or_else(|e| {
+        log::warn!("[capture_all_regions] Skipping watermark: {}", e);
+        None
+    });
+
+    let mut paths = Vec::with_capacity(crops.len());
+    for (i, (mut img, region)) in crops.into_iter().zip(&regions).enumerate() {
+        crate::color::prepare_for_export(&mut img, region, cfg.color_profile);
+        if let Some(stamp) = &stamp {
+            stamp.apply(&mut img);
+        }
+        let filename = output_dir.join(format!("{}_{}.png", stem, i + 1));
+        write_png(
+            &img,
+            &filename,
+            PngMetadata::default(),
+            PngCompression::default(),
+            PngFilter::default(),
+            cfg.color_profile.tags_srgb(),
+        )
+        .map_err(|e| format!("Save error: {}", e))?;
+        paths.push(filename.to_string_lossy().to_string());
+    }
+    Ok(paths)
+}
+
+/// Crop a sub-region out of the cached full-resolution snapshot without recapturing.
+/// The crop becomes the current capture (quick_save, annotations); returns a PNG data URL.
+#[tauri::command]
+pub fn recrop_capture(
+    state: tauri::State<SharedState>,
+    region: crate::types::Region,
+) -> Result<String, String> {
+    let mut s = state.lock().unwrap();
+    let snapshot = s.cached_snapshot.as_ref().ok_or("No cached snapshot")?;
+
+    // Reject instead of clamping: a partially outside crop is almost always a stale region
+    let scale = s.screen_scale;
+    let left = (region.x - s.screen_x) as f32 * scale;
+    let top = (region.y - s.screen_y) as f32 * scale;
+    let right = left + region.width as f32 * scale;
+    let bottom = top + region.height as f32 * scale;
+    if region.width == 0
+        || region.height == 0
+        || left < 0.0
+        || top < 0.0
+        || right > snapshot.width() as f32 + 0.5
+        || bottom > snapshot.height() as f32 + 0.5
+    {
+        return Err("Region lies outside the captured snapshot".to_string());
+    }
+
+    let cropped = crop_snapshot_region(snapshot, &region, s.screen_x, s.screen_y, scale)?;
+    log::info!(
+        "[recrop_capture] ({}, {}) {}x{} -> {}x{}",
+        region.x,
+        region.y,
+        region.width,
+        region.height,
+        cropped.width(),
+        cropped.height()
+    );
+
+    let data_url = crate::annotate::encode_png_data_url(&cropped)?;
+    s.region = Some(region);
+    s.last_capture = Some(cropped);
+    s.last_capture_mode = Some(CaptureMode::StaticImage);
+    Ok(data_url)
+}
+
+/// Export a backend-held image as HEIC (macOS only, quality 0.0-1.0, default 0.9).
+/// Native capture paths that already hold a CGImageRef can call
+/// `native_screenshot::write_heic` directly and skip the RGBA round-trip.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn export_heic(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    path: String,
+    quality: Option<f32>,
+) -> Result<String, String> {
+    let img = {
+        let mut s = state.lock().unwrap();
+        crate::annotate::source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image to export")?
+            .clone()
+    };
+
+    let cg_image =
+        crate::native_screenshot::rgba_to_cgimage(&img).ok_or("Failed to create CGImage")?;
+    let quality = quality.unwrap_or(0.9);
+    crate::native_screenshot::write_heic(&cg_image, std::path::Path::new(&path), quality)?;
+    log::info!("[export_heic] Saved {}x{} to {}", img.width(), img.height(), path);
+    Ok(path)
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+pub fn export_heic(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    path: String,
+    quality: Option<f32>,
+) -> Result<String, String> {
+    let _ = (state, source, path, quality);
+    Err("HEIC export is only available on macOS".to_string())
+}
+
+/// Whether export_heic works on this platform (frontend hides the format otherwise)
+#[tauri::command]
+pub fn supports_heic_export() -> bool {
+    cfg!(target_os = "macos")
+}
+
+// ============ Temp Files (drag-out) ============
+
+/// Temp captures older than this are pruned on the next capture_to_temp_file
+const TEMP_CAPTURE_TTL_SECS: u64 = 60 * 60;
+
+static TEMP_CAPTURE_SEQ: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn temp_capture_dir() -> PathBuf {
+    std::env::temp_dir().join("lovshot-drag")
+}
+
+/// Delete temp captures older than `max_age_secs`; returns how many were removed
+fn prune_temp_captures(max_age_secs: u64) -> usize {
+    let Ok(entries) = std::fs::read_dir(temp_capture_dir()) else {
+        return 0;
+    };
+    let max_age = std::time::Duration::from_secs(max_age_secs);
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age >= max_age);
+        if expired && std::fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Write a backend-held image as PNG into the OS temp dir and return its path,
+/// so the frontend can start a native file drag into apps that only accept files.
+#[tauri::command]
+pub fn capture_to_temp_file(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+) -> Result<String, String> {
+    let img = {
+        let mut s = state.lock().unwrap();
+        crate::annotate::source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image to export")?
+            .clone()
+    };
+
+    prune_temp_captures(TEMP_CAPTURE_TTL_SECS);
+    let path = write_temp_png(&img, &temp_capture_dir())?;
+    log::info!("[capture_to_temp_file] {}x{} -> {:?}", img.width(), img.height(), path);
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Write `img` as a uniquely named PNG in `dir`
+fn write_temp_png(img: &RgbaImage, dir: &std::path::Path) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let seq = TEMP_CAPTURE_SEQ.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let name = format!(
+        "lovshot_{}_{}_{}.png",
+        chrono::Local::now().format("%Y%m%d_%H%M%S"),
+        std::process::id(),
+        seq
+    );
+    let path = dir.join(name);
+    // Temp files are read right away: favor speed over size
+    write_png(img, &path, PngMetadata::None, PngCompression::Fast, PngFilter::Adaptive, false)?;
+    Ok(path)
+}
+
+/// Save a backend-held image to a temp PNG and open it in `app_path` (an app name or
+/// path, e.g. "Preview" or "/Applications/Adobe Photoshop 2025.app"), or in the OS
+/// default viewer when None. The file lives outside the auto-pruned drag-out dir because
+/// the editor may keep it open; delete the returned path once done with it.
+#[tauri::command]
+pub fn capture_and_open(
+    state: tauri::State<SharedState>,
+    app_path: Option<String>,
+    source: Option<ImageSource>,
+) -> Result<String, String> {
+    let img = {
+        let mut s = state.lock().unwrap();
+        crate::annotate::source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image to open")?
+            .clone()
+    };
+    let path = write_temp_png(&img, &std::env::temp_dir().join("lovshot-edit"))?;
+    let path_str = path.to_string_lossy().to_string();
+    log::info!("[capture_and_open] {:?} in {:?}", path, app_path);
+
+    match app_path.filter(|a| !a.trim().is_empty()) {
+        None => open_file(path_str.clone())?,
+        Some(app) => open_with(&app, &path_str)?,
+    }
+    Ok(path_str)
+}
+
+/// Launch `app` (name or path) on `path`
+fn open_with(app: &str, path: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut cmd = {
+        let mut cmd = std::process::Command::new("open");
+        cmd.args(["-a", app, path]);
+        cmd
+    };
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = std::process::Command::new("cmd");
+        cmd.args(["/c", "start", "", app, path]);
+        cmd
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut cmd = {
+        let mut cmd = std::process::Command::new(app);
+        cmd.arg(path);
+        cmd
+    };
+    cmd.spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open with {}: {}", app, e))
+}
+
+/// Remove temp drag-out files (all of them, or only those older than `max_age_secs`)
+#[tauri::command]
+pub fn cleanup_temp_captures(max_age_secs: Option<u64>) -> usize {
+    let removed = prune_temp_captures(max_age_secs.unwrap_or(0));
+    log::info!("[cleanup_temp_captures] Removed {} file(s)", removed);
+    removed
+}
+
+/// Raw RGBA pixels of a backend-held image as a binary IPC response, so large (scroll)
+/// captures can be blitted into a canvas `ImageData` without a PNG/base64 round-trip.
+/// Layout: width (u32 LE), height (u32 LE), then `width * height * 4` RGBA bytes.
+#[tauri::command]
+pub fn get_capture_rgba(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+) -> Result<tauri::ipc::Response, String> {
+    let mut s = state.lock().unwrap();
+    let img = crate::annotate::source_image(&mut s, source.unwrap_or_default())
+        .ok_or("No image available")?;
+
+    let raw = img.as_raw();
+    let mut bytes = Vec::with_capacity(8 + raw.len());
+    bytes.extend_from_slice(&img.width().to_le_bytes());
+    bytes.extend_from_slice(&img.height().to_le_bytes());
+    bytes.extend_from_slice(raw);
+    Ok(tauri::ipc::Response::new(bytes))
+}
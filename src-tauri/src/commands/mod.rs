@@ -6,6 +6,7 @@ mod recording;
 mod screen;
 mod scroll;
 mod selector;
+mod video;
 
 pub use config::*;
 pub use export::*;
@@ -15,3 +16,4 @@ pub use recording::*;
 pub use screen::*;
 pub use scroll::*;
 pub use selector::*;
+pub use video::*;
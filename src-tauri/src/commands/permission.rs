@@ -1,4 +1,8 @@
+use tauri::{AppHandle, Emitter, Manager};
+
 use crate::permission;
+use crate::state::SharedState;
+use crate::types::CaptureMode;
 
 #[derive(serde::Serialize)]
 pub struct PermissionStatus {
@@ -26,3 +30,140 @@ pub fn request_screen_permission() -> PermissionStatus {
 pub fn open_permission_settings() -> Result<(), String> {
     permission::open_screen_recording_settings()
 }
+
+/// Start a `mode` capture, requesting screen recording permission first if needed.
+/// Returns true if the selector opened. When the request isn't granted (macOS only applies
+/// a new grant after a relaunch), the permission window opens right away instead.
+#[tauri::command]
+pub fn ensure_permission_then(app: AppHandle, mode: CaptureMode) -> Result<bool, String> {
+    if permission::has_screen_recording_permission() {
+        start_capture(&app, mode)?;
+        return Ok(true);
+    }
+    request_then_capture(&app, mode)
+}
+
+/// Called by the selector when permission is missing: follow `auto_request_permission`
+pub fn handle_missing_permission(app: &AppHandle) {
+    if !crate::config::load_config().auto_request_permission {
+        let _ = crate::windows::open_permission_window(app);
+        return;
+    }
+    let mode = app.state::<SharedState>().lock().unwrap().pending_mode.unwrap_or_default();
+    if let Err(e) = request_then_capture(app, mode) {
+        log::warn!("[permission] Failed to open selector: {}", e);
+    }
+}
+
+fn start_capture(app: &AppHandle, mode: CaptureMode) -> Result<(), String> {
+    app.state::<SharedState>().lock().unwrap().pending_mode = Some(mode);
+    crate::commands::open_selector_internal(app.clone())
+}
+
+/// Trigger the system prompt and act on its answer: capture when granted, otherwise open
+/// the permission window (settings link and relaunch) without waiting. Returns whether the
+/// selector opened.
+fn request_then_capture(app: &AppHandle, mode: CaptureMode) -> Result<bool, String> {
+    if permission::request_screen_recording_permission() {
+        log::info!("[permission] Screen recording granted, starting {:?} capture", mode);
+        let _ = app.emit("permission-granted", mode);
+        start_capture(app, mode)?;
+        return Ok(true);
+    }
+    log::info!("[permission] Screen recording not granted, opening permission window");
+    let _ = app.emit("permission-denied", mode);
+    crate::windows::open_permission_window(app)?;
+    Ok(false)
+}
+
+/// Check if Accessibility permission is granted (call before starting scroll capture)
+#[tauri::command]
+pub fn check_accessibility_permission() -> PermissionStatus {
+    PermissionStatus {
+        granted: permission::has_accessibility_permission(),
+    }
+}
+
+/// Open System Preferences to Accessibility settings
+#[tauri::command]
+pub fn open_accessibility_settings() -> Result<(), String> {
+    permission::open_accessibility_settings()
+}
+
+/// Tri-state result for a single diagnostics check
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticStatus {
+    Ok,
+    Failed,
+    NotApplicable,
+}
+
+impl From<bool> for DiagnosticStatus {
+    fn from(ok: bool) -> Self {
+        if ok {
+            DiagnosticStatus::Ok
+        } else {
+            DiagnosticStatus::Failed
+        }
+    }
+}
+
+/// Copy-pasteable capability / permission report for support
+#[derive(serde::Serialize)]
+pub struct DiagnosticsReport {
+    pub app_version: String,
+    pub os: String,
+    pub os_version: String,
+    pub screen_recording_permission: DiagnosticStatus,
+    pub accessibility_permission: DiagnosticStatus, // required by the scroll CGEventTap
+    pub display_count: usize,
+    pub native_capture: DiagnosticStatus, // capture_cgimage returns an image
+}
+
+/// Best-effort OS version string
+fn os_version() -> String {
+    #[cfg(target_os = "macos")]
+    let output = std::process::Command::new("sw_vers").arg("-productVersion").output();
+    #[cfg(not(target_os = "macos"))]
+    let output = std::process::Command::new("uname").arg("-r").output();
+
+    output
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Run capability / permission self-test
+#[tauri::command]
+pub fn run_diagnostics(app: tauri::AppHandle) -> DiagnosticsReport {
+    #[cfg(target_os = "macos")]
+    let (screen_recording_permission, accessibility_permission, native_capture) = (
+        permission::has_screen_recording_permission().into(),
+        permission::has_accessibility_permission().into(),
+        crate::native_screenshot::capture_cgimage().is_some().into(),
+    );
+    #[cfg(not(target_os = "macos"))]
+    let (screen_recording_permission, accessibility_permission, native_capture) = (
+        DiagnosticStatus::NotApplicable,
+        DiagnosticStatus::NotApplicable,
+        DiagnosticStatus::NotApplicable,
+    );
+
+    let report = DiagnosticsReport {
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_version: os_version(),
+        screen_recording_permission,
+        accessibility_permission,
+        display_count: crate::capture::Screen::all().map(|s| s.len()).unwrap_or(0),
+        native_capture,
+    };
+
+    log::info!(
+        "[run_diagnostics] {} {} displays={}",
+        report.os, report.os_version, report.display_count
+    );
+    report
+}
@@ -5,59 +5,110 @@ use crate::capture::Screen;
 use image::RgbaImage;
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::config::{self, RecordingLimitPolicy};
 use crate::shortcuts::{register_stop_shortcuts, unregister_stop_shortcuts};
-use crate::state::SharedState;
-use crate::tray::{create_recording_overlay, update_tray_icon};
-use crate::types::{RecordingInfo, RecordingState};
-use crate::windows::{open_editor_window, set_activation_policy};
+use crate::state::{RecordedFrame, SharedState, DEFAULT_RECORDING_FPS};
+use crate::tray::{create_recording_overlay, show_recording_ready, update_tray_icon};
+use crate::types::{
+    ExportConfig, ExportFormat, RecordingBufferInfo, RecordingInfo, RecordingState,
+    RecordingStats, Region,
+};
+use crate::windows::{hide_main_window, open_editor_window, set_activation_policy};
 
 #[tauri::command]
 pub fn start_recording(app: AppHandle, state: tauri::State<SharedState>) -> Result<(), String> {
-    println!("[DEBUG][start_recording] ====== 被调用 ======");
+    log::debug!("[start_recording] ====== 被调用 ======");
+    begin_recording(&app, state.inner(), DEFAULT_RECORDING_FPS, RecordingFinish::Present)
+}
+
+/// What the recording thread does with the frames once recording stops
+#[derive(Clone, Copy)]
+enum RecordingFinish {
+    /// Open the editor, or stay in the background (present_recording)
+    Present,
+    /// Export straight to a GIF without the editor (capture_timed_gif)
+    ExportGif,
+}
+
+/// Record the effective region at `fps` until stop_recording or a limit, then `finish`
+fn begin_recording(
+    app: &AppHandle,
+    state: &SharedState,
+    fps: u32,
+    finish: RecordingFinish,
+) -> Result<(), String> {
     let mut s = state.lock().unwrap();
     if s.recording {
-        println!("[DEBUG][start_recording] 已经在录制中，跳过");
+        log::debug!("[start_recording] 已经在录制中，跳过");
         return Err("Already recording".to_string());
     }
+    if s.interval_capturing {
+        return Err("Interval capture in progress".to_string());
+    }
 
-    let region = s.region.clone().ok_or("No region selected")?;
-    println!(
-        "[DEBUG][start_recording] region: x={}, y={}, w={}, h={}",
+    let mut region = s.effective_region().ok_or("No region selected")?;
+    log::debug!(
+        "[start_recording] region: x={}, y={}, w={}, h={}",
         region.x, region.y, region.width, region.height
     );
+    // Follow a window: keep the region's offset from the window's top-left
+    let anchor: Option<WindowAnchor> = match (s.recording_track_window, s.recording_window_id) {
+        (true, Some(window_id)) => {
+            let win = tracked_window_bounds(window_id).ok_or("Tracked window not found")?;
+            Some((window_id, (region.x - win.x, region.y - win.y)))
+        }
+        _ => None,
+    };
     s.recording = true;
     s.frames.clear();
+    s.frames_bytes = 0;
+    s.frames_started_at = Some(Instant::now());
+    s.recording_fps = fps;
 
     let recording_fps = s.recording_fps;
+    let max_duration = s
+        .recording_max_duration_secs
+        .filter(|&secs| secs > 0)
+        .map(|secs| Duration::from_secs(secs as u64));
     drop(s);
 
+    let cfg = config::load_config();
+    let max_bytes = cfg.recording_max_memory_mb as u64 * 1024 * 1024;
+    let max_frames = cfg.recording_max_frames as usize;
+    let frame_format = cfg.recording_frame_format;
+    let limit_policy = cfg.recording_limit_policy;
+
     // Register stop shortcuts (ESC, etc.) now that we're recording
-    register_stop_shortcuts(&app);
+    register_stop_shortcuts(app);
 
-    update_tray_icon(&app, true);
-    create_recording_overlay(&app, &region, false);
+    update_tray_icon(app, true);
+    create_recording_overlay(app, &region, false);
 
-    let state_clone = state.inner().clone();
+    let state_clone = state.clone();
     let app_clone = app.clone();
 
     thread::spawn(move || {
-        println!("[DEBUG][recording_thread] 录制线程启动");
+        log::debug!("[recording_thread] 录制线程启动");
         let screens = Screen::all().unwrap_or_default();
         if screens.is_empty() {
-            println!("[DEBUG][recording_thread] 错误: 没有找到屏幕");
+            log::warn!("[recording_thread] 错误: 没有找到屏幕");
             return;
         }
         let screen = &screens[0];
-        println!(
-            "[DEBUG][recording_thread] 屏幕: {}x{}, scale={}, fps={}",
+        log::debug!(
+            "[recording_thread] 屏幕: {}x{}, scale={}, fps={}",
             screen.display_info.width,
             screen.display_info.height,
             screen.display_info.scale_factor,
             recording_fps
         );
-        let frame_duration = Duration::from_millis(1000 / recording_fps as u64);
+        let mut frame_duration = Duration::from_millis(1000 / recording_fps as u64);
 
         let mut frame_idx = 0u32;
+        let mut first_frame_at: Option<Instant> = None;
+        // Achieved-fps window for recording-stats
+        let mut stats_window_start = Instant::now();
+        let mut stats_window_frames = 0u32;
         loop {
             let start = Instant::now();
 
@@ -65,8 +116,8 @@ pub fn start_recording(app: AppHandle, state: tauri::State<SharedState>) -> Resu
                 let s = state_clone.lock().unwrap();
                 if !s.recording {
                     let frame_count = s.frames.len();
-                    println!(
-                        "[DEBUG][recording_thread] 录制停止，共捕获 {} 帧",
+                    log::debug!(
+                        "[recording_thread] 录制停止，共捕获 {} 帧",
                         frame_count
                     );
                     drop(s);
@@ -77,12 +128,13 @@ pub fn start_recording(app: AppHandle, state: tauri::State<SharedState>) -> Resu
                         let _ = overlay.close();
                     }
 
-                    // Switch to Regular activation policy so window stays visible after cmd+tab
-                    set_activation_policy(0);
-
-                    // Open a new editor window
-                    if let Err(e) = open_editor_window(&app_clone) {
-                        println!("[DEBUG][recording_thread] 打开编辑器窗口失败: {}", e);
+                    match finish {
+                        // Nothing to edit after reset_state emptied the buffer
+                        RecordingFinish::Present if frame_count > 0 => {
+                            present_recording(&app_clone, frame_count);
+                        }
+                        RecordingFinish::Present => {}
+                        RecordingFinish::ExportGif => export_timed_gif(&app_clone),
                     }
 
                     let _ = app_clone.emit(
@@ -95,17 +147,101 @@ pub fn start_recording(app: AppHandle, state: tauri::State<SharedState>) -> Resu
                 }
             }
 
+            if let Some((window_id, (dx, dy))) = anchor {
+                match tracked_window_bounds(window_id) {
+                    Some(win) if (win.x + dx, win.y + dy) != (region.x, region.y) => {
+                        region.x = win.x + dx;
+                        region.y = win.y + dy;
+                        let _ = app_clone.emit("recording-region-moved", &region);
+                    }
+                    Some(_) => {}
+                    None => {
+                        log::debug!("[recording_thread] tracked window closed, stopping");
+                        let mut s = state_clone.lock().unwrap();
+                        s.recording = false;
+                        let _ = app_clone.emit(
+                            "tracked-window-closed",
+                            serde_json::json!({
+                                "window_id": window_id,
+                                "frame_count": s.frames.len(),
+                            }),
+                        );
+                        drop(s);
+                        unregister_stop_shortcuts(&app_clone);
+                        continue; // the stop check at the top wraps up
+                    }
+                }
+            }
+
             match screen.capture_area(region.x, region.y, region.width, region.height) {
                 Ok(img) => {
                     let rgba =
                         RgbaImage::from_raw(img.width(), img.height(), img.into_raw()).unwrap();
 
+                    let frame = RecordedFrame::new(rgba, frame_format);
                     let mut s = state_clone.lock().unwrap();
-                    s.frames.push(rgba);
+                    s.frames_bytes += frame.byte_len();
+                    s.frames.push(frame);
                     frame_idx += 1;
+                    let recording_start = *first_frame_at.get_or_insert_with(Instant::now);
 
                     if frame_idx <= 3 || frame_idx % 10 == 0 {
-                        println!("[DEBUG][recording_thread] 捕获帧 #{}", frame_idx);
+                        log::debug!("[recording_thread] 捕获帧 #{}", frame_idx);
+                    }
+
+                    let over_memory = max_bytes > 0 && s.frames_bytes >= max_bytes;
+                    let over_frames = max_frames > 0 && s.frames.len() >= max_frames;
+                    if over_memory || over_frames {
+                        let can_drop = limit_policy == RecordingLimitPolicy::DropFps
+                            && s.recording_fps > 1;
+                        if can_drop {
+                            // Keep every other frame so the buffer stays under the cap,
+                            // then continue at half the rate to match the thinned timeline
+                            decimate_frames(&mut s.frames);
+                            s.frames_bytes = s.frames.iter().map(RecordedFrame::byte_len).sum();
+                            s.recording_fps = (s.recording_fps / 2).max(1);
+                            frame_duration = Duration::from_millis(1000 / s.recording_fps as u64);
+                            log::debug!(
+                                "[recording_thread] 达到上限，降帧至 {} fps ({} 帧)",
+                                s.recording_fps,
+                                s.frames.len()
+                            );
+                        } else {
+                            log::debug!(
+                                "[recording_thread] 达到上限，自动停止 ({} 帧, {} bytes)",
+                                s.frames.len(),
+                                s.frames_bytes
+                            );
+                            s.recording = false;
+                            let _ = app_clone.emit(
+                                "recording-limit-reached",
+                                serde_json::json!({
+                                    "frame_count": s.frames.len(),
+                                    "bytes": s.frames_bytes,
+                                }),
+                            );
+                            unregister_stop_shortcuts(&app_clone);
+                        }
+                    }
+
+                    // Duration cap (whichever limit hits first stops the recording)
+                    if let Some(max) = max_duration {
+                        let elapsed = recording_start.elapsed();
+                        if s.recording && elapsed >= max {
+                            log::debug!(
+                                "[recording_thread] 达到时长上限 {}s，自动停止",
+                                max.as_secs()
+                            );
+                            s.recording = false;
+                            let _ = app_clone.emit(
+                                "recording-auto-stopped",
+                                serde_json::json!({
+                                    "frame_count": s.frames.len(),
+                                    "elapsed_ms": elapsed.as_millis() as u64,
+                                }),
+                            );
+                            unregister_stop_shortcuts(&app_clone);
+                        }
                     }
 
                     let _ = app_clone.emit(
@@ -115,12 +251,28 @@ pub fn start_recording(app: AppHandle, state: tauri::State<SharedState>) -> Resu
                             frame_count: s.frames.len() as u32,
                         },
                     );
+
+                    stats_window_frames += 1;
+                    let window = stats_window_start.elapsed();
+                    if window >= Duration::from_secs(1) {
+                        let _ = app_clone.emit(
+                            "recording-stats",
+                            RecordingStats {
+                                fps: stats_window_frames as f32 / window.as_secs_f32(),
+                                target_fps: s.recording_fps,
+                                frame_count: s.frames.len(),
+                                elapsed_ms: recording_start.elapsed().as_millis() as u64,
+                            },
+                        );
+                        stats_window_start = Instant::now();
+                        stats_window_frames = 0;
+                    }
                 }
                 Err(e) => {
                     if frame_idx == 0 {
-                        println!("[DEBUG][recording_thread] capture_area 失败: {:?}", e);
-                        println!(
-                            "[DEBUG][recording_thread] 参数: x={}, y={}, w={}, h={}",
+                        log::warn!("[recording_thread] capture_area 失败: {:?}", e);
+                        log::debug!(
+                            "[recording_thread] 参数: x={}, y={}, w={}, h={}",
                             region.x, region.y, region.width, region.height
                         );
                     }
@@ -132,18 +284,79 @@ pub fn start_recording(app: AppHandle, state: tauri::State<SharedState>) -> Resu
                 thread::sleep(frame_duration - elapsed);
             }
         }
-        println!("[DEBUG][recording_thread] 线程退出");
+        log::debug!("[recording_thread] 线程退出");
     });
 
     Ok(())
 }
 
+/// Window a recording follows, and the region's offset from its top-left
+type WindowAnchor = (u32, (i32, i32));
+
+#[cfg(target_os = "macos")]
+fn tracked_window_bounds(window_id: u32) -> Option<Region> {
+    crate::window_detect::window_bounds_by_id(window_id)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn tracked_window_bounds(_window_id: u32) -> Option<Region> {
+    None
+}
+
+/// Make the next recording follow `window_id` (from get_window_info_at_cursor) as it
+/// moves; the region keeps its offset from the window. Stops with `tracked-window-closed`
+/// if the window goes away. macOS only.
+#[tauri::command]
+pub fn set_recording_window_tracking(
+    state: tauri::State<SharedState>,
+    track: bool,
+    window_id: Option<u32>,
+) -> Result<(), String> {
+    if track {
+        let window_id = window_id.ok_or("window_id is required to track a window")?;
+        if tracked_window_bounds(window_id).is_none() {
+            return Err(format!("Window {} not found", window_id));
+        }
+    }
+    log::debug!("[set_recording_window_tracking] track={}, id={:?}", track, window_id);
+    let mut s = state.lock().unwrap();
+    s.recording_track_window = track;
+    s.recording_window_id = window_id;
+    Ok(())
+}
+
+/// Open the editor for a finished recording, or stay in the background
+/// (tray tooltip only) when `restore_main_after_capture` is off
+fn present_recording(app: &AppHandle, frame_count: usize) {
+    if !config::load_config().restore_main_after_capture {
+        log::debug!("[recording_thread] 保持后台 ({} 帧)", frame_count);
+        set_activation_policy(1);
+        show_recording_ready(app, frame_count);
+        return;
+    }
+
+    // Switch to Regular activation policy so window stays visible after cmd+tab
+    set_activation_policy(0);
+
+    // Open a new editor window
+    if let Err(e) = open_editor_window(app) {
+        log::warn!("[recording_thread] 打开编辑器窗口失败: {}", e);
+    }
+}
+
+/// Set an optional recording duration limit (seconds); applies to the next start_recording
+#[tauri::command]
+pub fn set_recording_max_duration(state: tauri::State<SharedState>, secs: Option<u32>) {
+    log::debug!("[set_recording_max_duration] {:?}", secs);
+    state.lock().unwrap().recording_max_duration_secs = secs;
+}
+
 #[tauri::command]
 pub fn stop_recording(app: AppHandle, state: tauri::State<SharedState>) {
-    println!("[DEBUG][stop_recording] ====== 被调用 ======");
+    log::debug!("[stop_recording] ====== 被调用 ======");
     let mut s = state.lock().unwrap();
     s.recording = false;
-    println!("[DEBUG][stop_recording] 录制标志已设置为 false");
+    log::debug!("[stop_recording] 录制标志已设置为 false");
     drop(s);
 
     // Unregister stop shortcuts since recording ended
@@ -174,16 +387,248 @@ pub fn get_recording_info(state: tauri::State<SharedState>) -> RecordingInfo {
     }
 }
 
+/// Live snapshot of the frame buffer, for a memory gauge while recording
+#[tauri::command]
+pub fn get_recording_buffer_info(state: tauri::State<SharedState>) -> RecordingBufferInfo {
+    let (frame_count, dimensions, total_bytes, started_at, recording, fps) = {
+        let s = state.lock().unwrap();
+        (
+            s.frames.len(),
+            s.frames.first().map(RecordedFrame::dimensions),
+            s.frames_bytes,
+            s.frames_started_at,
+            s.recording || s.interval_capturing,
+            s.recording_fps.max(1),
+        )
+    };
+    let (width, height) = dimensions.unwrap_or((0, 0));
+    // Wall time while capturing; once stopped, the clip length the buffer plays back at
+    let elapsed_ms = match started_at {
+        Some(t) if recording => t.elapsed().as_millis() as u64,
+        _ => frame_count as u64 * 1000 / fps as u64,
+    };
+    RecordingBufferInfo {
+        frame_count,
+        width,
+        height,
+        total_bytes,
+        max_bytes: config::load_config().recording_max_memory_mb as u64 * 1024 * 1024,
+        elapsed_ms,
+        recording,
+    }
+}
+
 #[tauri::command]
 pub fn discard_recording(app: AppHandle, state: tauri::State<SharedState>) {
-    println!("[DEBUG][discard_recording] 丢弃录制数据");
+    log::debug!("[discard_recording] 丢弃录制数据");
     let mut s = state.lock().unwrap();
     s.frames.clear();
+    s.frames_bytes = 0;
+    s.frames_started_at = None;
     drop(s);
 
-    // Hide main window and switch back to Accessory policy
-    if let Some(main_win) = app.get_webview_window("main") {
-        let _ = main_win.hide();
-        set_activation_policy(1);
+    hide_main_window(&app);
+}
+
+/// Timelapse: grab `region` every `interval_secs` into the recording frame buffer.
+/// Frames play back at the default fps, so GIF/video export yields a sped-up clip.
+#[tauri::command]
+pub fn start_interval_capture(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    interval_secs: f32,
+    region: Region,
+) -> Result<(), String> {
+    if interval_secs.is_nan() || interval_secs < 0.1 {
+        return Err("Interval must be at least 0.1s".to_string());
+    }
+    {
+        let mut s = state.lock().unwrap();
+        if s.recording || s.interval_capturing {
+            return Err("Already recording".to_string());
+        }
+        s.interval_capturing = true;
+        s.region = Some(region.clone());
+        s.frames.clear();
+        s.frames_bytes = 0;
+        s.frames_started_at = Some(Instant::now());
+        s.recording_fps = DEFAULT_RECORDING_FPS;
+    }
+    log::info!(
+        "[interval_capture] every {}s at ({}, {}) {}x{}",
+        interval_secs, region.x, region.y, region.width, region.height
+    );
+
+    let cfg = config::load_config();
+    let max_bytes = cfg.recording_max_memory_mb as u64 * 1024 * 1024;
+    let max_frames = cfg.recording_max_frames as usize;
+    let frame_format = cfg.recording_frame_format;
+
+    update_tray_icon(&app, true);
+    create_recording_overlay(&app, &region, true);
+
+    let interval = Duration::from_secs_f32(interval_secs);
+    let state_clone = state.inner().clone();
+
+    thread::spawn(move || {
+        let screens = Screen::all().unwrap_or_default();
+        let Some(screen) = screens.first() else {
+            log::warn!("[interval_capture] No screens found");
+            state_clone.lock().unwrap().interval_capturing = false;
+            return;
+        };
+
+        let mut next_tick = Instant::now();
+        loop {
+            if !state_clone.lock().unwrap().interval_capturing {
+                break;
+            }
+
+            if Instant::now() >= next_tick {
+                next_tick += interval;
+                match screen.capture_area(region.x, region.y, region.width, region.height) {
+                    Ok(rgba) => {
+                        let frame = RecordedFrame::new(rgba, frame_format);
+                        let mut s = state_clone.lock().unwrap();
+                        s.frames_bytes += frame.byte_len();
+                        s.frames.push(frame);
+                        let frame_count = s.frames.len();
+                        let _ = app.emit(
+                            "timelapse-frame",
+                            serde_json::json!({ "frame_count": frame_count }),
+                        );
+
+                        let over_memory = max_bytes > 0 && s.frames_bytes >= max_bytes;
+                        let over_frames = max_frames > 0 && frame_count >= max_frames;
+                        if over_memory || over_frames {
+                            log::warn!(
+                                "[interval_capture] Buffer limit reached ({} frames)",
+                                frame_count
+                            );
+                            s.interval_capturing = false;
+                            let _ = app.emit(
+                                "recording-limit-reached",
+                                serde_json::json!({
+                                    "frame_count": frame_count,
+                                    "bytes": s.frames_bytes,
+                                }),
+                            );
+                        }
+                    }
+                    Err(e) => log::warn!("[interval_capture] capture_area failed: {}", e),
+                }
+            }
+
+            // Short naps keep stop_interval_capture responsive on long intervals
+            let until_tick = next_tick.saturating_duration_since(Instant::now());
+            thread::sleep(until_tick.min(Duration::from_millis(100)));
+        }
+
+        let frame_count = state_clone.lock().unwrap().frames.len();
+        log::info!("[interval_capture] Stopped with {} frames", frame_count);
+        update_tray_icon(&app, false);
+        if let Some(overlay) = app.get_webview_window("recording-overlay") {
+            let _ = overlay.close();
+        }
+        if frame_count > 0 {
+            present_recording(&app, frame_count);
+        }
+        let _ = app.emit("recording-stopped", serde_json::json!({ "frame_count": frame_count }));
+    });
+
+    Ok(())
+}
+
+/// Stop timelapse capture; collected frames open in the editor
+#[tauri::command]
+pub fn stop_interval_capture(state: tauri::State<SharedState>) {
+    log::info!("[interval_capture] Stop requested");
+    state.lock().unwrap().interval_capturing = false;
+}
+
+/// Longest clip capture_timed_gif will record
+const MAX_TIMED_GIF_SECS: f32 = 300.0;
+
+/// Record `region` (or the current one) for `duration_secs` at `fps`, then export a GIF
+/// without opening the editor. Runs the regular recording loop (frame and memory limits,
+/// window tracking), so progress comes as `recording-state`; then the usual
+/// `export-progress`, and `export-complete` carries the output path and frame count.
+/// stop_recording ends the clip early and exports what was captured.
+#[tauri::command]
+pub fn capture_timed_gif(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    region: Option<Region>,
+    duration_secs: f32,
+    fps: Option<u32>,
+) -> Result<(), String> {
+    if !(duration_secs > 0.0 && duration_secs <= MAX_TIMED_GIF_SECS) {
+        return Err(format!("Duration must be between 0 and {}s", MAX_TIMED_GIF_SECS));
+    }
+    let fps = fps.unwrap_or(DEFAULT_RECORDING_FPS).clamp(1, 60);
+    {
+        let mut s = state.lock().unwrap();
+        if s.recording || s.interval_capturing {
+            return Err("Already recording".to_string());
+        }
+        if let Some(region) = region {
+            s.region = Some(region);
+        }
     }
+    begin_recording(&app, state.inner(), fps, RecordingFinish::ExportGif)?;
+    let started_at = state.lock().unwrap().frames_started_at;
+    log::info!("[timed_gif] {}s @ {}fps", duration_secs, fps);
+
+    // Stop after the duration unless this clip already ended (stopped early or hit a limit)
+    thread::spawn(move || {
+        thread::sleep(Duration::from_secs_f32(duration_secs));
+        let state = app.state::<SharedState>();
+        let current = {
+            let s = state.lock().unwrap();
+            s.recording && s.frames_started_at == started_at
+        };
+        if current {
+            stop_recording(app.clone(), state);
+        }
+    });
+
+    Ok(())
+}
+
+/// Export a finished capture_timed_gif clip at the rate it ended up recorded at
+fn export_timed_gif(app: &AppHandle) {
+    let (frame_count, fps) = {
+        let state = app.state::<SharedState>();
+        let s = state.lock().unwrap();
+        (s.frames.len(), s.recording_fps)
+    };
+    let export = ExportConfig {
+        start_frame: 0,
+        end_frame: frame_count,
+        output_scale: 1.0,
+        target_fps: fps,
+        loop_mode: "infinite".to_string(),
+        quality: 80,
+        speed: 1.0,
+        output_path: None,
+        loop_count: None,
+        dither: false,
+        dedup_threshold: None,
+        format: ExportFormat::Gif,
+        watermark: None,
+    };
+    // export_gif reports an empty buffer through export-complete as well
+    if let Err(e) = crate::commands::export_gif(app.clone(), app.state(), export) {
+        log::warn!("[timed_gif] Export failed: {}", e);
+    }
+}
+
+/// Drop every other frame (keeps the first), halving the buffer
+fn decimate_frames(frames: &mut Vec<RecordedFrame>) {
+    let mut idx = 0;
+    frames.retain(|_| {
+        let keep = idx % 2 == 0;
+        idx += 1;
+        keep
+    });
 }
@@ -1,4 +1,5 @@
-use crate::capture::Screen;
+use crate::capture::{logical_to_physical, physical_to_logical, Screen};
+use crate::types::{CoordConversion, Region};
 use base64::{engine::general_purpose::STANDARD, Engine};
 
 #[tauri::command]
@@ -19,6 +20,29 @@ pub fn get_screens() -> Vec<serde_json::Value> {
         .collect()
 }
 
+/// Convert a rect (or a point, as a zero-size rect) between logical global coordinates
+/// and physical pixels relative to the top-left of display `display` (index into
+/// `get_screens`, default 0), using that display's scale factor.
+#[tauri::command]
+pub fn convert_coords(
+    rect: Region,
+    direction: CoordConversion,
+    display: Option<u32>,
+) -> Result<Region, String> {
+    let screens = Screen::all()?;
+    let index = display.unwrap_or(0);
+    let info = &screens
+        .iter()
+        .find(|s| s.display_info.id == index)
+        .ok_or_else(|| format!("Display {} not found", index))?
+        .display_info;
+    let origin = (info.x, info.y);
+    Ok(match direction {
+        CoordConversion::LogicalToPhysical => logical_to_physical(&rect, origin, info.scale_factor),
+        CoordConversion::PhysicalToLogical => physical_to_logical(&rect, origin, info.scale_factor),
+    })
+}
+
 #[tauri::command]
 pub fn capture_screenshot() -> Result<String, String> {
     let screens = Screen::all().map_err(|e| e.to_string())?;
@@ -44,3 +68,13 @@ pub fn capture_screenshot() -> Result<String, String> {
     let base64_str = STANDARD.encode(&png_data);
     Ok(format!("data:image/png;base64,{}", base64_str))
 }
+
+/// Capture the whole virtual desktop, every display placed by its origin, as one PNG data
+/// URL. Gaps between displays are filled with `background` (default opaque black).
+#[tauri::command]
+pub fn capture_all_displays(background: Option<[u8; 4]>) -> Result<String, String> {
+    let screens = Screen::all_nonempty()?;
+    let background = image::Rgba(background.unwrap_or([0, 0, 0, 255]));
+    let img = crate::capture::capture_desktop(&screens, background)?;
+    crate::annotate::encode_png_data_url(&img)
+}
@@ -4,41 +4,56 @@ use crate::capture::Screen;
 use base64::{engine::general_purpose::STANDARD, Engine};
 use image::codecs::jpeg::JpegEncoder;
 use image::ExtendedColorType;
-use image::{DynamicImage, GenericImage, RgbaImage};
+use image::{DynamicImage, GenericImage, Rgba, RgbaImage};
 use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
+use crate::commands::export::{to_grayscale, write_png, WatermarkStamp};
 use crate::fft_match::detect_scroll_delta_fft;
+use crate::row_hash::detect_scroll_delta_horizontal;
 #[cfg(target_os = "macos")]
 use crate::scroll_event::{start_scroll_listener, stop_scroll_listener};
+#[cfg(all(target_os = "linux", feature = "linux-scroll"))]
+use crate::linux_capture::{start_scroll_listener, stop_scroll_listener};
 use crate::shortcuts::register_stop_scroll_shortcuts;
-use crate::state::SharedState;
-use crate::types::{CropEdges, Region, ScrollCaptureProgress};
+use crate::state::{AppState, SharedState};
+use crate::types::{
+    CaptureDiff, CaptureMode, CropEdges, ExportWatermark, FrameCompare, MergeInput, OversizePolicy,
+    PngCompression, PngFilter, PngMetadata, Region, ScrollAxis, ScrollCaptureProgress,
+    ScrollChangeTolerance, ScrollExportResult, ScrollMatchInset, ScrollProgressEstimate,
+};
+
+/// Consecutive unchanged frames after which a scroll capture is reported likely complete
+pub const SCROLL_LIKELY_COMPLETE_FRAMES: u32 = 3;
 
 /// Internal function to capture initial scroll frame
 fn capture_initial_scroll_frame(
     state: &SharedState,
     region: &Region,
 ) -> Result<(), String> {
-    println!("[DEBUG][capture_initial_scroll_frame] 开始捕获初始帧");
+    log::debug!("[capture_initial_scroll_frame] 开始捕获初始帧");
 
     let mut s = state.lock().unwrap();
 
     // Clear previous scroll capture state
     s.scroll_frames.clear();
+    s.scroll_last_frame = None;
     s.scroll_offsets.clear();
     s.scroll_stitched = None;
+    s.scroll_recent_deltas.clear();
+    s.scroll_identical_streak = 0;
+    s.scroll_region = Some(region.clone());
     s.scroll_capturing = true;
 
     drop(s);
 
     // Capture initial frame
     let screens = Screen::all().map_err(|e| {
-        println!("[DEBUG][capture_initial_scroll_frame] Screen::all 错误: {}", e);
+        log::warn!("[capture_initial_scroll_frame] Screen::all 错误: {}", e);
         e.to_string()
     })?;
     if screens.is_empty() {
-        println!("[DEBUG][capture_initial_scroll_frame] 错误: No screens found");
+        log::warn!("[capture_initial_scroll_frame] 错误: No screens found");
         return Err("No screens found".to_string());
     }
 
@@ -46,7 +61,7 @@ fn capture_initial_scroll_frame(
     let captured = screen
         .capture_area(region.x, region.y, region.width, region.height)
         .map_err(|e| {
-            println!("[DEBUG][capture_initial_scroll_frame] capture_area 错误: {}", e);
+            log::warn!("[capture_initial_scroll_frame] capture_area 错误: {}", e);
             e.to_string()
         })?;
 
@@ -55,11 +70,10 @@ fn capture_initial_scroll_frame(
 
     // Store initial frame
     let mut s = state.lock().unwrap();
-    s.scroll_frames.push(frame.clone());
-    s.scroll_offsets.push(0);
+    s.push_scroll_frame(frame.clone(), 0, 0);
     s.scroll_stitched = Some(frame);
 
-    println!("[DEBUG][capture_initial_scroll_frame] 完成");
+    log::debug!("[capture_initial_scroll_frame] 完成");
     Ok(())
 }
 
@@ -68,16 +82,16 @@ fn capture_initial_scroll_frame(
 pub fn start_scroll_capture(
     state: tauri::State<SharedState>,
 ) -> Result<ScrollCaptureProgress, String> {
-    println!("[DEBUG][start_scroll_capture] ====== 被调用 ======");
+    log::debug!("[start_scroll_capture] ====== 被调用 ======");
     let region = {
         let s = state.lock().unwrap();
-        s.region.clone().ok_or_else(|| {
-            println!("[DEBUG][start_scroll_capture] 错误: No region selected");
+        s.effective_region().ok_or_else(|| {
+            log::warn!("[start_scroll_capture] 错误: No region selected");
             "No region selected".to_string()
         })?
     };
-    println!(
-        "[DEBUG][start_scroll_capture] region: x={}, y={}, w={}, h={}",
+    log::debug!(
+        "[start_scroll_capture] region: x={}, y={}, w={}, h={}",
         region.x, region.y, region.width, region.height
     );
 
@@ -86,18 +100,20 @@ pub fn start_scroll_capture(
     // Generate preview
     let s = state.lock().unwrap();
     let frame = s.scroll_stitched.as_ref().ok_or("No frame captured")?;
-    let (_width, height) = frame.dimensions();
+    let (width, height) = frame.dimensions();
     let preview = generate_preview_base64(frame, 600)?;
 
-    println!(
-        "[DEBUG][start_scroll_capture] 完成! frame_count=1, height={}",
-        height
+    log::debug!(
+        "[start_scroll_capture] 完成! frame_count=1, {}x{}",
+        width, height
     );
 
     Ok(ScrollCaptureProgress {
         frame_count: 1,
         total_height: height,
+        total_width: width,
         preview_base64: preview,
+        estimated_complete: false,
     })
 }
 
@@ -106,17 +122,28 @@ pub fn start_scroll_capture(
 #[tauri::command]
 pub fn capture_scroll_frame_auto(
     state: tauri::State<SharedState>,
+) -> Result<Option<ScrollCaptureProgress>, String> {
+    capture_scroll_frame_internal(&state)
+}
+
+/// Polling-mode capture, used when no global scroll listener is running
+fn capture_scroll_frame_internal(
+    state: &SharedState,
 ) -> Result<Option<ScrollCaptureProgress>, String> {
     // Step 1: Get required data with minimal lock time
-    let (region, last_frame, scroll_stitched) = {
+    let (region, last_frame, scroll_stitched, axis, inset, seam_blend, tolerance) = {
         let s = state.lock().unwrap();
         if !s.scroll_capturing {
             return Err("Not in scroll capture mode".to_string());
         }
         (
-            s.region.clone().ok_or("No region selected")?,
-            s.scroll_frames.last().cloned().ok_or("No previous frame")?,
+            s.scroll_region.clone().ok_or("No region selected")?,
+            s.last_scroll_frame().cloned().ok_or("No previous frame")?,
             s.scroll_stitched.clone().ok_or("No stitched image")?,
+            s.scroll_axis,
+            s.scroll_match_inset,
+            s.scroll_seam_blend,
+            s.scroll_change_tolerance.clone(),
         )
     }; // Lock released here
 
@@ -134,16 +161,21 @@ pub fn capture_scroll_frame_auto(
     let new_frame = RgbaImage::from_raw(captured.width(), captured.height(), captured.into_raw())
         .ok_or("Failed to convert image")?;
 
-    // Detect scroll direction and amount using FFT-based matching (expensive!)
-    let scroll_delta = detect_scroll_delta_fft(&last_frame, &new_frame, 0, None);
+    // Detect scroll direction and amount (expensive!)
+    let prev_match = inset_for_matching(&last_frame, inset, region.width);
+    let curr_match = inset_for_matching(&new_frame, inset, region.width);
+    let scroll_delta = detect_scroll_delta(axis, &prev_match, &curr_match, 0, None);
 
     // If no scroll detected, don't refresh preview (keeps UI stable)
     if scroll_delta == 0 {
+        if frames_nearly_identical(&prev_match, &curr_match, &tolerance) {
+            state.lock().unwrap().scroll_identical_streak += 1;
+        }
         return Ok(None);
     }
 
     // Stitch the image (expensive!)
-    let stitched = stitch_scroll_image(&scroll_stitched, &new_frame, scroll_delta)?;
+    let stitched = stitch_scroll(axis, &scroll_stitched, &new_frame, scroll_delta, seam_blend)?;
 
     // Calculate new cumulative offset
     let last_offset = {
@@ -162,17 +194,19 @@ pub fn capture_scroll_frame_auto(
         return Err("Scroll capture was cancelled".to_string());
     }
 
-    s.scroll_frames.push(new_frame);
-    s.scroll_offsets.push(new_offset);
+    s.push_scroll_frame(new_frame, scroll_delta, new_offset);
     s.scroll_stitched = Some(stitched);
+    s.scroll_identical_streak = 0;
 
     let frame_count = s.scroll_frames.len();
-    let total_height = s.scroll_stitched.as_ref().unwrap().height();
+    let (total_width, total_height) = s.scroll_stitched.as_ref().unwrap().dimensions();
 
     Ok(Some(ScrollCaptureProgress {
         frame_count,
         total_height,
+        total_width,
         preview_base64: preview,
+        estimated_complete: false,
     }))
 }
 
@@ -182,10 +216,14 @@ pub fn get_scroll_preview(
     state: tauri::State<SharedState>,
 ) -> Result<ScrollCaptureProgress, String> {
     // Get data with minimal lock time
-    let (frame_count, total_height, stitched) = {
+    let (frame_count, stitched, estimated_complete) = {
         let s = state.lock().unwrap();
         match s.scroll_stitched.as_ref() {
-            Some(img) => (s.scroll_frames.len(), img.height(), img.clone()),
+            Some(img) => (
+                s.scroll_frames.len(),
+                img.clone(),
+                s.scroll_identical_streak >= SCROLL_LIKELY_COMPLETE_FRAMES,
+            ),
             None => return Err("No scroll capture in progress".to_string()),
         }
     }; // Lock released here
@@ -194,8 +232,31 @@ pub fn get_scroll_preview(
     let preview = generate_preview_base64(&stitched, 600)?;
     Ok(ScrollCaptureProgress {
         frame_count,
-        total_height,
+        total_height: stitched.height(),
+        total_width: stitched.width(),
         preview_base64: preview,
+        estimated_complete,
+    })
+}
+
+/// Heuristic progress of the running scroll capture. The page's scrollable length isn't
+/// available generically, so the end is inferred from repeated scrolls that moved nothing.
+#[tauri::command]
+pub fn estimate_scroll_progress(
+    state: tauri::State<SharedState>,
+) -> Result<ScrollProgressEstimate, String> {
+    scroll_progress_estimate(&state.lock().unwrap())
+}
+
+/// Estimate from the current scroll state (shared with the listeners' end-of-page event)
+pub fn scroll_progress_estimate(s: &AppState) -> Result<ScrollProgressEstimate, String> {
+    let stitched = s.scroll_stitched.as_ref().ok_or("No scroll capture in progress")?;
+    Ok(ScrollProgressEstimate {
+        frame_count: s.scroll_frames.len(),
+        total_height: stitched.height(),
+        total_width: stitched.width(),
+        identical_streak: s.scroll_identical_streak,
+        estimated_complete: s.scroll_identical_streak >= SCROLL_LIKELY_COMPLETE_FRAMES,
     })
 }
 
@@ -205,11 +266,15 @@ pub fn copy_scroll_to_clipboard(
     app: AppHandle,
     state: tauri::State<SharedState>,
     crop: Option<CropEdges>,
+    grayscale: Option<bool>,
 ) -> Result<(), String> {
     let s = state.lock().unwrap();
     let stitched = s.scroll_stitched.as_ref().ok_or("No stitched image")?;
 
-    let final_img = apply_crop(stitched, crop)?;
+    let mut final_img = apply_crop(stitched, crop)?;
+    if grayscale.unwrap_or(false) {
+        final_img = to_grayscale(&final_img);
+    }
 
     let tauri_image = tauri::image::Image::new_owned(
         final_img.as_raw().to_vec(),
@@ -223,44 +288,187 @@ pub fn copy_scroll_to_clipboard(
     Ok(())
 }
 
-/// Finish scroll capture - save the stitched image to specified path
+/// Positions along the stitch axis that cut `length` px into pieces of at most `max`,
+/// using the furthest frame seam that fits and a hard cut when none does
+fn split_points(length: u32, max: u32, seams: &[u32]) -> Vec<u32> {
+    let mut cuts = Vec::new();
+    let mut start = 0;
+    while length - start > max {
+        let limit = start + max;
+        let cut = seams
+            .iter()
+            .copied()
+            .filter(|&p| p > start && p <= limit)
+            .max()
+            .unwrap_or(limit);
+        cuts.push(cut);
+        start = cut;
+    }
+    cuts
+}
+
+/// `path` with `_<n>` appended to the file stem
+fn numbered_path(path: &str, n: usize) -> String {
+    let p = PathBuf::from(path);
+    let stem = p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let name = match p.extension() {
+        Some(ext) => format!("{}_{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}_{}", stem, n),
+    };
+    p.with_file_name(name).to_string_lossy().to_string()
+}
+
+/// Finish scroll capture - save the stitched image to specified path.
+/// Images larger than max_export_width/height follow `oversize`: by default nothing is
+/// written and the capture is kept, so the caller can retry with Split or Allow.
+/// `watermark` (default: the export_watermark setting) is stamped on every written file.
 #[tauri::command]
 pub fn finish_scroll_capture(
     app: AppHandle,
     state: tauri::State<SharedState>,
     path: String,
     crop: Option<CropEdges>,
-) -> Result<String, String> {
-    #[cfg(target_os = "macos")]
+    grayscale: Option<bool>,
+    oversize: Option<OversizePolicy>,
+    watermark: Option<ExportWatermark>,
+) -> Result<ScrollExportResult, String> {
+    let cfg = crate::config::load_config();
+    let (max_width, max_height) = (cfg.max_export_width, cfg.max_export_height);
+    let oversize = oversize.unwrap_or_default();
+    let stamp = WatermarkStamp::for_export(watermark.as_ref())?;
+
+    let (final_img, axis, seams, region) = {
+        let s = state.lock().unwrap();
+        let stitched = s.scroll_stitched.as_ref().ok_or("No stitched image")?;
+        // Frame seams (where each appended strip begins), shifted by the leading crop
+        let (frame_len, lead) = match (s.scroll_axis, s.scroll_frames.first(), &crop) {
+            (ScrollAxis::Vertical, Some(f), c) => (
+                f.height() as i32,
+                c.as_ref().map_or(0.0, |c| c.top / 100.0 * stitched.height() as f32),
+            ),
+            (ScrollAxis::Horizontal, Some(f), c) => (
+                f.width() as i32,
+                c.as_ref().map_or(0.0, |c| c.left / 100.0 * stitched.width() as f32),
+            ),
+            (_, None, _) => (0, 0.0),
+        };
+        let lead = lead.round() as i32;
+        let seams: Vec<u32> = s
+            .scroll_offsets
+            .iter()
+            .map(|&offset| offset + frame_len - lead)
+            .filter(|&p| p > 0)
+            .map(|p| p as u32)
+            .collect();
+        (apply_crop(stitched, crop)?, s.scroll_axis, seams, s.scroll_region.clone())
+    };
+
+    let (width, height) = final_img.dimensions();
+    let oversized = width > max_width || height > max_height;
+    if oversized && oversize == OversizePolicy::Warn {
+        log::info!(
+            "[finish_scroll_capture] {}x{} exceeds {}x{}",
+            width, height, max_width, max_height
+        );
+        return Ok(ScrollExportResult {
+            paths: Vec::new(),
+            width,
+            height,
+            oversized,
+            max_width,
+            max_height,
+        });
+    }
+
+    #[cfg(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll")))]
     stop_scroll_listener();
 
-    let mut s = state.lock().unwrap();
-    let stitched = s.scroll_stitched.take().ok_or("No stitched image")?;
+    {
+        let mut s = state.lock().unwrap();
+        s.scroll_stitched = None;
+        // Clear scroll state
+        s.scroll_capturing = false;
+        s.scroll_frames.clear();
+        s.scroll_last_frame = None;
+        s.scroll_offsets.clear();
+    }
 
-    // Clear scroll state
-    s.scroll_capturing = false;
-    s.scroll_frames.clear();
-    s.scroll_offsets.clear();
+    let (length, max) = match axis {
+        ScrollAxis::Vertical => (height, max_height),
+        ScrollAxis::Horizontal => (width, max_width),
+    };
+    let cuts = if oversized && oversize == OversizePolicy::Split {
+        split_points(length, max, &seams)
+    } else {
+        Vec::new()
+    };
 
-    drop(s);
+    // Only the written files are grayscale / color converted / watermarked; last_capture
+    // keeps the original
+    let gray = grayscale.unwrap_or(false);
+    let color_profile = cfg.color_profile;
+    let write = |img: RgbaImage, path: &str| {
+        let mut img = if gray { to_grayscale(&img) } else { img };
+        if let Some(region) = &region {
+            crate::color::prepare_for_export(&mut img, region, color_profile);
+        }
+        if let Some(stamp) = &stamp {
+            stamp.apply(&mut img);
+        }
+        write_png(
+            &img,
+            std::path::Path::new(path),
+            PngMetadata::default(),
+            PngCompression::default(),
+            PngFilter::default(),
+            color_profile.tags_srgb(),
+        )
+    };
+    let mut paths = Vec::new();
+    if cuts.is_empty() {
+        write(final_img.clone(), &path)?;
+        paths.push(path);
+    } else {
+        let bounds: Vec<u32> = std::iter::once(0).chain(cuts).chain([length]).collect();
+        for (i, pair) in bounds.windows(2).enumerate() {
+            let (from, to) = (pair[0], pair[1]);
+            let (x, y, w, h) = match axis {
+                ScrollAxis::Vertical => (0, from, width, to - from),
+                ScrollAxis::Horizontal => (from, 0, to - from, height),
+            };
+            let piece_path = numbered_path(&path, i + 1);
+            write(image::imageops::crop_imm(&final_img, x, y, w, h).to_image(), &piece_path)?;
+            paths.push(piece_path);
+        }
+        log::info!("[finish_scroll_capture] Split {}x{} into {} files", width, height, paths.len());
+    }
 
-    // Apply crop and save
-    let final_img = apply_crop(&stitched, crop)?;
-    final_img.save(&path).map_err(|e| e.to_string())?;
+    {
+        let mut s = state.lock().unwrap();
+        s.last_capture = Some(final_img);
+        s.last_capture_mode = Some(CaptureMode::Scroll);
+    }
 
     // Close region overlay after finishing
     if let Some(overlay) = app.get_webview_window("recording-overlay") {
         let _ = overlay.close();
     }
 
-    Ok(path)
+    Ok(ScrollExportResult {
+        paths,
+        width,
+        height,
+        oversized,
+        max_width,
+        max_height,
+    })
 }
 
 /// Stop scroll capture (keep data for preview)
 #[tauri::command]
 pub fn stop_scroll_capture(app: AppHandle, state: tauri::State<SharedState>) {
-    println!("[DEBUG][shortcut] 停止滚动截图");
-    #[cfg(target_os = "macos")]
+    log::debug!("[shortcut] 停止滚动截图");
+    #[cfg(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll")))]
     stop_scroll_listener();
     let mut s = state.lock().unwrap();
     s.scroll_capturing = false;
@@ -287,11 +495,12 @@ pub fn stop_scroll_capture(app: AppHandle, state: tauri::State<SharedState>) {
 /// Cancel scroll capture
 #[tauri::command]
 pub fn cancel_scroll_capture(app: AppHandle, state: tauri::State<SharedState>) {
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll")))]
     stop_scroll_listener();
     let mut s = state.lock().unwrap();
     s.scroll_capturing = false;
     s.scroll_frames.clear();
+    s.scroll_last_frame = None;
     s.scroll_offsets.clear();
     s.scroll_stitched = None;
 
@@ -301,6 +510,383 @@ pub fn cancel_scroll_capture(app: AppHandle, state: tauri::State<SharedState>) {
     }
 }
 
+/// Abort the scroll capture and drop every buffer so the next capture starts clean.
+/// Safe to call when no capture is running.
+#[tauri::command]
+pub fn discard_scroll_capture(app: AppHandle, state: tauri::State<SharedState>) {
+    log::info!("[discard_scroll_capture] Discarding scroll capture");
+    #[cfg(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll")))]
+    stop_scroll_listener();
+    #[cfg(target_os = "macos")]
+    crate::scroll_event::reset_no_match_count();
+
+    {
+        let mut s = state.lock().unwrap();
+        s.scroll_capturing = false;
+        s.scroll_frames.clear();
+        s.scroll_last_frame = None;
+        s.scroll_offsets.clear();
+        s.scroll_stitched = None;
+        s.scroll_recent_deltas.clear();
+    }
+
+    crate::shortcuts::unregister_stop_scroll_shortcuts(&app);
+    if let Some(overlay) = app.get_webview_window("recording-overlay") {
+        let _ = overlay.close();
+    }
+    let _ = app.emit("scroll-discarded", ());
+}
+
+/// Crop the repeated footer / blank band off the end of the stitched image (bottom for
+/// vertical captures, right edge for horizontal). Only runs of at least `min_run` px
+/// (default 40) are trimmed so intentional solid backgrounds stay intact. Frames that
+/// started inside the trimmed band are dropped along with their offsets.
+#[tauri::command]
+pub fn trim_scroll_tail(
+    state: tauri::State<SharedState>,
+    min_run: Option<u32>,
+) -> Result<ScrollCaptureProgress, String> {
+    const MAX_PERIOD: u32 = 400; // tallest footer we look for
+    let min_run = min_run.unwrap_or(40);
+
+    let mut s = state.lock().unwrap();
+    let axis = s.scroll_axis;
+    let stitched = s.scroll_stitched.as_ref().ok_or("No scroll capture")?;
+    let (w, h) = stitched.dimensions();
+
+    let trimmed = match axis {
+        ScrollAxis::Vertical => {
+            let run = crate::row_hash::trailing_repeat_rows(stitched, MAX_PERIOD, min_run);
+            image::imageops::crop_imm(stitched, 0, 0, w, h - run).to_image()
+        }
+        ScrollAxis::Horizontal => {
+            // Columns become rows: the right edge ends up at the bottom
+            let rotated = image::imageops::rotate90(stitched);
+            let run = crate::row_hash::trailing_repeat_rows(&rotated, MAX_PERIOD, min_run);
+            image::imageops::crop_imm(stitched, 0, 0, w - run, h).to_image()
+        }
+    };
+    let (total_width, total_height) = trimmed.dimensions();
+    log::info!("[trim_scroll_tail] {}x{} -> {}x{}", w, h, total_width, total_height);
+
+    // Frames that now start past the end only showed the trimmed tail. The newest full
+    // frame stays available for matching if the capture continues.
+    let len = match axis {
+        ScrollAxis::Vertical => total_height,
+        ScrollAxis::Horizontal => total_width,
+    } as i32;
+    while s.scroll_offsets.len() > 1 && s.scroll_offsets.last().is_some_and(|&o| o >= len) {
+        s.scroll_offsets.pop();
+        let frame = s.scroll_frames.pop();
+        if s.scroll_last_frame.is_none() {
+            s.scroll_last_frame = frame;
+        }
+    }
+
+    let preview_base64 = generate_preview_base64(&trimmed, 600)?;
+    s.scroll_stitched = Some(trimmed);
+    Ok(ScrollCaptureProgress {
+        frame_count: s.scroll_frames.len(),
+        total_height,
+        total_width,
+        preview_base64,
+        estimated_complete: s.scroll_identical_streak >= SCROLL_LIKELY_COMPLETE_FRAMES,
+    })
+}
+
+/// Let the app scroll the captured window itself (macOS): synthesized wheel events of
+/// `step` px (default 120) every `interval_ms` (default 250) until the content stops
+/// moving. Requires a running scroll capture; stop_scroll_capture cancels it too.
+#[tauri::command]
+pub fn start_auto_scroll_capture(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    step: Option<i32>,
+    interval_ms: Option<u64>,
+) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let region = {
+            let s = state.lock().unwrap();
+            if !s.scroll_capturing {
+                return Err("No scroll capture in progress".to_string());
+            }
+            s.scroll_region.clone().ok_or("No region selected")?
+        };
+
+        // Wheel events only reach the target once its app is active
+        let center_x = region.x as f64 + region.width as f64 / 2.0;
+        let center_y = region.y as f64 + region.height as f64 / 2.0;
+        crate::window_detect::activate_window_at_position(center_x, center_y);
+
+        let step = step.unwrap_or(120).clamp(10, 2000);
+        let interval_ms = interval_ms.unwrap_or(250).clamp(50, 5000);
+        crate::scroll_event::start_auto_scroll(app, step, interval_ms);
+        Ok(())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app, state, step, interval_ms);
+        Err("Auto-scroll capture is only available on macOS".to_string())
+    }
+}
+
+/// Find the part of the selection that actually scrolls (macOS): grabs it, scrolls the
+/// content by `step` px (default 60) and back, and diffs the two frames by row hash.
+/// Returns the suggested inner region (logical px) without static toolbars, footers or
+/// sidebars, for the user to narrow the selection to.
+#[tauri::command]
+pub fn detect_scroll_region(
+    state: tauri::State<SharedState>,
+    region: Option<Region>,
+    step: Option<i32>,
+) -> Result<Region, String> {
+    #[cfg(target_os = "macos")]
+    {
+        use crate::row_hash::detect_moving_rect;
+
+        let (region, axis) = {
+            let s = state.lock().unwrap();
+            let region = region.or_else(|| s.region.clone()).ok_or("No region selected")?;
+            (region, s.scroll_axis)
+        };
+        let center_x = region.x as f64 + region.width as f64 / 2.0;
+        let center_y = region.y as f64 + region.height as f64 / 2.0;
+        crate::window_detect::activate_window_at_position(center_x, center_y);
+
+        let step = step.unwrap_or(60).clamp(10, 500);
+        let (before, after) = crate::scroll_event::probe_scroll(&region, axis, step)
+            .ok_or("Failed to capture the selection")?;
+
+        // Horizontal content scrolls along columns: rotate so it scrolls along rows
+        let moved = match axis {
+            ScrollAxis::Vertical => detect_moving_rect(&before, &after),
+            ScrollAxis::Horizontal => {
+                let rotate = image::imageops::rotate90;
+                detect_moving_rect(&rotate(&before), &rotate(&after)).map(|(x, y, w, h)| {
+                    (y, before.height() - x - w, h, w)
+                })
+            }
+        };
+        let (x, y, w, h) = moved.ok_or("No scrolling content found in the selection")?;
+
+        let sx = region.width as f64 / before.width() as f64;
+        let sy = region.height as f64 / before.height() as f64;
+        let inner = Region {
+            x: region.x + (x as f64 * sx).round() as i32,
+            y: region.y + (y as f64 * sy).round() as i32,
+            width: ((w as f64 * sx).round() as u32).max(1),
+            height: ((h as f64 * sy).round() as u32).max(1),
+        };
+        log::info!(
+            "[detect_scroll_region] ({}, {}) {}x{} -> ({}, {}) {}x{}",
+            region.x, region.y, region.width, region.height,
+            inner.x, inner.y, inner.width, inner.height
+        );
+        Ok(inner)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (state, region, step);
+        Err("Scroll region detection is only available on macOS".to_string())
+    }
+}
+
+/// Stop the auto-scroll driver but keep capturing manual scrolls
+#[tauri::command]
+pub fn stop_auto_scroll_capture() {
+    #[cfg(target_os = "macos")]
+    crate::scroll_event::stop_auto_scroll();
+}
+
+/// Write per-frame scroll metadata (offsets, detected deltas, sizes) as JSON for
+/// debugging bad stitches. Call before finish_scroll_capture clears the frames.
+#[tauri::command]
+pub fn export_scroll_metadata(state: tauri::State<SharedState>, path: String) -> Result<String, String> {
+    let metadata = {
+        let s = state.lock().unwrap();
+        if s.scroll_frames.is_empty() {
+            return Err("No scroll frames captured".to_string());
+        }
+
+        let frames: Vec<serde_json::Value> = s
+            .scroll_frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let offset = s.scroll_offsets.get(i).copied().unwrap_or(0);
+                let delta = match i {
+                    0 => 0,
+                    _ => offset - s.scroll_offsets.get(i - 1).copied().unwrap_or(0),
+                };
+                serde_json::json!({
+                    "index": i,
+                    "scroll_offset": offset,
+                    "delta": delta,
+                    "width": frame.width(),
+                    "height": frame.height(),
+                })
+            })
+            .collect();
+
+        let (stitched_width, stitched_height) =
+            s.scroll_stitched.as_ref().map(|img| img.dimensions()).unwrap_or((0, 0));
+
+        serde_json::json!({
+            "axis": s.scroll_axis,
+            "frame_count": frames.len(),
+            "stitched_width": stitched_width,
+            "stitched_height": stitched_height,
+            "frames": frames,
+        })
+    }; // Lock released before file IO
+
+    let json = serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| e.to_string())?;
+    log::info!("[export_scroll_metadata] Saved to {}", path);
+    Ok(path)
+}
+
+/// Set the max stitched height before scroll capture auto-stops (0 = unlimited)
+#[tauri::command]
+pub fn set_scroll_max_height(state: tauri::State<SharedState>, max_height: u32) {
+    log::info!("[set_scroll_max_height] {}", max_height);
+    state.lock().unwrap().scroll_max_height = max_height;
+}
+
+/// Set the frame downscale factor used for scroll matching (0 = auto by frame width)
+#[tauri::command]
+pub fn set_scroll_match_downscale(state: tauri::State<SharedState>, factor: u32) {
+    log::info!("[set_scroll_match_downscale] {}", factor);
+    state.lock().unwrap().scroll_match_downscale = factor;
+}
+
+/// Set how many logical px on each side are ignored when matching scroll frames
+#[tauri::command]
+pub fn set_scroll_match_inset(state: tauri::State<SharedState>, inset: ScrollMatchInset) {
+    log::info!("[set_scroll_match_inset] left={}, right={}", inset.left, inset.right);
+    state.lock().unwrap().scroll_match_inset = inset;
+}
+
+#[tauri::command]
+pub fn get_scroll_match_inset(state: tauri::State<SharedState>) -> ScrollMatchInset {
+    state.lock().unwrap().scroll_match_inset
+}
+
+/// Keep only the newly revealed strip of each scroll frame instead of the whole frame,
+/// cutting per-frame memory to delta x width; takes effect from the next frame
+#[tauri::command]
+pub fn set_scroll_store_strips(state: tauri::State<SharedState>, enabled: bool) {
+    log::info!("[set_scroll_store_strips] {}", enabled);
+    state.lock().unwrap().scroll_store_strips = enabled;
+}
+
+/// Set how many rows are cross-faded at each vertical stitch seam (0 = pixel-exact)
+#[tauri::command]
+pub fn set_scroll_seam_blend(state: tauri::State<SharedState>, rows: u32) {
+    log::info!("[set_scroll_seam_blend] {}", rows);
+    state.lock().unwrap().scroll_seam_blend = rows;
+}
+
+/// Tune when the scroll listener treats consecutive frames as unchanged
+#[tauri::command]
+pub fn set_scroll_change_tolerance(
+    state: tauri::State<SharedState>,
+    tolerance: ScrollChangeTolerance,
+) {
+    log::info!(
+        "[set_scroll_change_tolerance] {:?}: threshold={}, rows={:?}, step={}, ssim>={}",
+        tolerance.compare,
+        tolerance.threshold,
+        tolerance.sample_rows,
+        tolerance.sample_step,
+        tolerance.ssim_threshold
+    );
+    state.lock().unwrap().scroll_change_tolerance = tolerance;
+}
+
+/// Current unchanged-frame tolerance
+#[tauri::command]
+pub fn get_scroll_change_tolerance(state: tauri::State<SharedState>) -> ScrollChangeTolerance {
+    state.lock().unwrap().scroll_change_tolerance.clone()
+}
+
+/// Pause the scroll listener (e.g. to reposition the page) without losing frames
+#[tauri::command]
+pub fn pause_scroll_listener(state: tauri::State<SharedState>) -> Result<(), String> {
+    if !state.lock().unwrap().scroll_capturing {
+        return Err("Not in scroll capture mode".to_string());
+    }
+    #[cfg(target_os = "macos")]
+    crate::scroll_event::pause_scroll_listener();
+    #[cfg(all(target_os = "linux", feature = "linux-scroll"))]
+    crate::linux_capture::pause_scroll_listener();
+    Ok(())
+}
+
+/// Resume the scroll listener after pause_scroll_listener
+#[tauri::command]
+pub fn resume_scroll_listener(state: tauri::State<SharedState>) -> Result<(), String> {
+    if !state.lock().unwrap().scroll_capturing {
+        return Err("Not in scroll capture mode".to_string());
+    }
+    #[cfg(target_os = "macos")]
+    crate::scroll_event::resume_scroll_listener();
+    #[cfg(all(target_os = "linux", feature = "linux-scroll"))]
+    crate::linux_capture::resume_scroll_listener();
+    Ok(())
+}
+
+/// Flip the scroll listener between paused and live; returns whether it is now live
+#[cfg(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll")))]
+fn toggle_listener_pause() -> Result<bool, String> {
+    #[cfg(target_os = "macos")]
+    use crate::scroll_event as listener;
+    #[cfg(all(target_os = "linux", feature = "linux-scroll"))]
+    use crate::linux_capture as listener;
+
+    if listener::is_scroll_listener_paused() {
+        listener::resume_scroll_listener();
+        Ok(true)
+    } else {
+        listener::pause_scroll_listener();
+        Ok(false)
+    }
+}
+
+#[cfg(not(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll"))))]
+fn toggle_listener_pause() -> Result<bool, String> {
+    Err("Scroll listener not supported on this platform".to_string())
+}
+
+/// Shortcut handler: pause/resume the listener of a running scroll capture, or start a
+/// scroll capture on the current region without going through the selector.
+/// Emits `scroll-listener-toggled` with whether the listener is now live.
+pub fn toggle_scroll_listener_internal(app: AppHandle) -> Result<bool, String> {
+    let state = app.state::<SharedState>();
+    let (capturing, recording, region) = {
+        let s = state.lock().unwrap();
+        (s.scroll_capturing, s.recording, s.effective_region())
+    };
+
+    let listening = if capturing {
+        toggle_listener_pause()?
+    } else {
+        if recording {
+            return Err("Recording in progress".to_string());
+        }
+        let region = region.ok_or("No region selected")?;
+        let progress = start_scroll_capture(app.state())?;
+        open_scroll_overlay(app.clone(), app.state(), region)?;
+        let _ = app.emit("scroll-preview-update", &progress);
+        true
+    };
+
+    log::info!("[toggle_scroll_listener] listening={}", listening);
+    let _ = app.emit("scroll-listener-toggled", listening);
+    Ok(listening)
+}
+
 /// Stitch two images based on scroll delta
 /// scroll_delta > 0: scrolled down, new content at bottom
 /// scroll_delta < 0: scrolled up, new content at top
@@ -308,6 +894,7 @@ pub fn stitch_scroll_image(
     base: &RgbaImage,
     new_frame: &RgbaImage,
     scroll_delta: i32,
+    seam_blend: u32,
 ) -> Result<RgbaImage, String> {
     let (base_w, base_h) = base.dimensions();
     let (new_w, new_h) = new_frame.dimensions();
@@ -351,6 +938,10 @@ pub fn stitch_scroll_image(
                 .copy_from(&cropped, 0, base_h)
                 .map_err(|e| e.to_string())?;
 
+            // Fade the base's last rows into the same rows of new_frame
+            let rows = seam_blend.min(crop_y).min(base_h);
+            blend_rows(&mut result, base_h - rows, new_frame, crop_y - rows, rows, true);
+
             Ok(result)
         }
     } else {
@@ -385,11 +976,286 @@ pub fn stitch_scroll_image(
                 .copy_from(base, 0, pixels_to_add)
                 .map_err(|e| e.to_string())?;
 
+            // Fade from new_frame back into the base's first rows
+            let rows = seam_blend.min(new_h - pixels_to_add).min(base_h);
+            blend_rows(&mut result, pixels_to_add, new_frame, pixels_to_add, rows, false);
+
             Ok(result)
         }
     }
 }
 
+/// Cross-fade `rows` rows of `result` from `start_y` with the same content in `src` from
+/// `src_y`, so sub-pixel scroll or compression differences don't leave a line at a seam.
+/// `toward_src`: src's weight grows row by row (seam below), otherwise it shrinks.
+fn blend_rows(
+    result: &mut RgbaImage,
+    start_y: u32,
+    src: &RgbaImage,
+    src_y: u32,
+    rows: u32,
+    toward_src: bool,
+) {
+    for k in 0..rows {
+        let step = (k + 1) as f32 / (rows + 1) as f32;
+        let t = if toward_src { step } else { 1.0 - step };
+        for x in 0..result.width().min(src.width()) {
+            let s = src.get_pixel(x, src_y + k);
+            let d = result.get_pixel_mut(x, start_y + k);
+            for c in 0..4 {
+                d[c] = (d[c] as f32 * (1.0 - t) + s[c] as f32 * t).round() as u8;
+            }
+        }
+    }
+}
+
+/// Stitch two images based on horizontal scroll delta
+/// scroll_delta > 0: scrolled right, new content at the right edge
+/// scroll_delta < 0: scrolled left, new content at the left edge
+pub fn stitch_scroll_image_horizontal(
+    base: &RgbaImage,
+    new_frame: &RgbaImage,
+    scroll_delta: i32,
+) -> Result<RgbaImage, String> {
+    let (base_w, base_h) = base.dimensions();
+    let (new_w, new_h) = new_frame.dimensions();
+
+    // Ensure same height
+    if base_h != new_h {
+        return Err("Frame height mismatch".to_string());
+    }
+
+    // Only the non-overlapping strip of new_frame is added
+    let cols_to_add = (scroll_delta.unsigned_abs()).min(new_w);
+    let mut result = RgbaImage::new(base_w + cols_to_add, base_h);
+
+    if scroll_delta > 0 {
+        // Scrolled right: append new columns after base
+        result.copy_from(base, 0, 0).map_err(|e| e.to_string())?;
+        let cropped =
+            image::imageops::crop_imm(new_frame, new_w - cols_to_add, 0, cols_to_add, new_h)
+                .to_image();
+        result
+            .copy_from(&cropped, base_w, 0)
+            .map_err(|e| e.to_string())?;
+    } else {
+        // Scrolled left: prepend new columns before base
+        let cropped = image::imageops::crop_imm(new_frame, 0, 0, cols_to_add, new_h).to_image();
+        result.copy_from(&cropped, 0, 0).map_err(|e| e.to_string())?;
+        result
+            .copy_from(base, cols_to_add, 0)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(result)
+}
+
+/// Detect scroll delta along the configured axis
+/// expected_direction / max_delta only apply to the vertical (FFT) matcher
+pub fn detect_scroll_delta(
+    axis: ScrollAxis,
+    prev: &RgbaImage,
+    curr: &RgbaImage,
+    expected_direction: i32,
+    max_delta: Option<i32>,
+) -> i32 {
+    match axis {
+        ScrollAxis::Vertical => detect_scroll_delta_fft(prev, curr, expected_direction, max_delta),
+        ScrollAxis::Horizontal => detect_scroll_delta_horizontal(prev, curr),
+    }
+}
+
+/// Stitch along the configured axis
+pub fn stitch_scroll(
+    axis: ScrollAxis,
+    base: &RgbaImage,
+    new_frame: &RgbaImage,
+    scroll_delta: i32,
+    seam_blend: u32,
+) -> Result<RgbaImage, String> {
+    match axis {
+        ScrollAxis::Vertical => stitch_scroll_image(base, new_frame, scroll_delta, seam_blend),
+        ScrollAxis::Horizontal => stitch_scroll_image_horizontal(base, new_frame, scroll_delta),
+    }
+}
+
+/// Place `second` after `first` along `axis` with an optional gap, centering the
+/// narrower one on a `background` canvas (general form of the scroll stitcher)
+pub fn concat_images(
+    first: &RgbaImage,
+    second: &RgbaImage,
+    axis: ScrollAxis,
+    gap: u32,
+    background: Rgba<u8>,
+) -> Result<RgbaImage, String> {
+    let (w1, h1) = first.dimensions();
+    let (w2, h2) = second.dimensions();
+    let (width, height, second_pos) = match axis {
+        ScrollAxis::Vertical => (w1.max(w2), h1 + gap + h2, (0, h1 + gap)),
+        ScrollAxis::Horizontal => (w1 + gap + w2, h1.max(h2), (w1 + gap, 0)),
+    };
+
+    let mut result = RgbaImage::from_pixel(width, height, background);
+    let (first_pos, second_pos) = match axis {
+        ScrollAxis::Vertical => (((width - w1) / 2, 0), ((width - w2) / 2, second_pos.1)),
+        ScrollAxis::Horizontal => ((0, (height - h1) / 2), (second_pos.0, (height - h2) / 2)),
+    };
+    result
+        .copy_from(first, first_pos.0, first_pos.1)
+        .map_err(|e| e.to_string())?;
+    result
+        .copy_from(second, second_pos.0, second_pos.1)
+        .map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+fn resolve_merge_input(state: &SharedState, input: MergeInput) -> Result<RgbaImage, String> {
+    match input {
+        MergeInput::Source { source } => {
+            let mut s = state.lock().unwrap();
+            crate::annotate::source_image(&mut s, source)
+                .cloned()
+                .ok_or_else(|| format!("No {:?} image", source))
+        }
+        MergeInput::Base64(data) => {
+            // Accept both bare base64 and data URLs
+            let encoded = data.split_once(',').map(|(_, b)| b).unwrap_or(&data);
+            let bytes = STANDARD
+                .decode(encoded)
+                .map_err(|e| format!("Base64 decode error: {}", e))?;
+            image::load_from_memory(&bytes)
+                .map(|img| img.to_rgba8())
+                .map_err(|e| format!("Image load error: {}", e))
+        }
+    }
+}
+
+/// Combine two captures side by side or stacked (before/after, split captures).
+/// The result becomes the current capture and is returned as a PNG data URL.
+#[tauri::command]
+pub fn merge_captures(
+    state: tauri::State<SharedState>,
+    first: MergeInput,
+    second: MergeInput,
+    axis: ScrollAxis,
+    gap: Option<u32>,
+    background: Option<[u8; 4]>,
+) -> Result<String, String> {
+    let first = resolve_merge_input(&state, first)?;
+    let second = resolve_merge_input(&state, second)?;
+    let background = Rgba(background.unwrap_or([255, 255, 255, 255]));
+
+    let merged = concat_images(&first, &second, axis, gap.unwrap_or(0), background)?;
+    log::info!(
+        "[merge_captures] {:?} {}x{} + {}x{} -> {}x{}",
+        axis,
+        first.width(),
+        first.height(),
+        second.width(),
+        second.height(),
+        merged.width(),
+        merged.height()
+    );
+
+    let data_url = crate::annotate::encode_png_data_url(&merged)?;
+    let mut s = state.lock().unwrap();
+    s.last_capture = Some(merged);
+    s.last_capture_mode = Some(CaptureMode::StaticImage);
+    Ok(data_url)
+}
+
+/// Highlight pixels whose average RGB difference exceeds `threshold` in magenta over a
+/// faded copy of `second`. Both images must have the same size; pixels only one image
+/// covers (after padding) count as changed. Returns the overlay and the changed count.
+fn diff_images(first: &RgbaImage, second: &RgbaImage, threshold: u8) -> (RgbaImage, u64) {
+    const HIGHLIGHT: Rgba<u8> = Rgba([255, 0, 255, 255]);
+    let (width, height) = first.dimensions();
+    let mut out = RgbaImage::new(width, height);
+    let mut changed = 0u64;
+    for (x, y, out_px) in out.enumerate_pixels_mut() {
+        let a = first.get_pixel(x, y);
+        let b = second.get_pixel(x, y);
+        let diff = (0..3).map(|c| (a[c] as i32 - b[c] as i32).unsigned_abs()).sum::<u32>() / 3;
+        // Padding is transparent; a pixel present in only one image is a change
+        if diff > threshold as u32 || (a[3] == 0) != (b[3] == 0) {
+            changed += 1;
+            *out_px = HIGHLIGHT;
+        } else {
+            // Fade towards white so the highlight stands out
+            let faded = |v: u8| ((v as u32 + 2 * 255) / 3) as u8;
+            *out_px = Rgba([faded(b[0]), faded(b[1]), faded(b[2]), 255]);
+        }
+    }
+    (out, changed)
+}
+
+/// Compare two captures for visual regression checks. Mismatched sizes are an error
+/// unless `pad` is set, in which case both are padded to the larger size.
+/// The overlay becomes the current capture.
+#[tauri::command]
+pub fn diff_captures(
+    state: tauri::State<SharedState>,
+    first: MergeInput,
+    second: MergeInput,
+    threshold: Option<u8>,
+    pad: Option<bool>,
+) -> Result<CaptureDiff, String> {
+    let mut first = resolve_merge_input(&state, first)?;
+    let mut second = resolve_merge_input(&state, second)?;
+
+    if first.dimensions() != second.dimensions() {
+        if !pad.unwrap_or(false) {
+            return Err(format!(
+                "Size mismatch: {}x{} vs {}x{}",
+                first.width(),
+                first.height(),
+                second.width(),
+                second.height()
+            ));
+        }
+        let width = first.width().max(second.width());
+        let height = first.height().max(second.height());
+        for img in [&mut first, &mut second] {
+            let mut padded = RgbaImage::new(width, height);
+            padded.copy_from(&*img, 0, 0).map_err(|e| e.to_string())?;
+            *img = padded;
+        }
+    }
+
+    let (overlay, changed_pixels) = diff_images(&first, &second, threshold.unwrap_or(16));
+    let (width, height) = overlay.dimensions();
+    let total = (width as u64 * height as u64).max(1);
+    let percent_changed = changed_pixels as f32 * 100.0 / total as f32;
+    log::info!(
+        "[diff_captures] {}x{}: {} px changed ({:.2}%)",
+        width, height, changed_pixels, percent_changed
+    );
+
+    let image_base64 = crate::annotate::encode_png_data_url(&overlay)?;
+    let mut s = state.lock().unwrap();
+    s.last_capture = Some(overlay);
+    s.last_capture_mode = Some(CaptureMode::StaticImage);
+    Ok(CaptureDiff {
+        image_base64,
+        width,
+        height,
+        changed_pixels,
+        percent_changed,
+    })
+}
+
+/// Select the scroll capture axis (call before starting capture)
+#[tauri::command]
+pub fn set_scroll_axis(state: tauri::State<SharedState>, axis: ScrollAxis) -> Result<(), String> {
+    let mut s = state.lock().unwrap();
+    if s.scroll_capturing {
+        return Err("Cannot change axis while scroll capture is running".to_string());
+    }
+    log::info!("[set_scroll_axis] {:?}", axis);
+    s.scroll_axis = axis;
+    Ok(())
+}
+
 /// Apply percentage-based edge crop to an image
 fn apply_crop(img: &RgbaImage, crop: Option<CropEdges>) -> Result<RgbaImage, String> {
     let crop = match crop {
@@ -419,6 +1285,63 @@ fn apply_crop(img: &RgbaImage, crop: Option<CropEdges>) -> Result<RgbaImage, Str
     Ok(cropped)
 }
 
+/// Drop the inset columns from a frame before matching. `logical_width` is the region
+/// width the frame was captured from, to convert the inset to frame pixels. Returns the
+/// frame untouched when the inset is zero or would leave less than half of it.
+pub fn inset_for_matching(
+    frame: &RgbaImage,
+    inset: ScrollMatchInset,
+    logical_width: u32,
+) -> std::borrow::Cow<'_, RgbaImage> {
+    use std::borrow::Cow;
+
+    let scale = frame.width() as f32 / logical_width.max(1) as f32;
+    let left = (inset.left as f32 * scale).round() as u32;
+    let right = (inset.right as f32 * scale).round() as u32;
+    if left + right == 0 || (left + right) * 2 > frame.width() {
+        return Cow::Borrowed(frame);
+    }
+    let width = frame.width() - left - right;
+    Cow::Owned(image::imageops::crop_imm(frame, left, 0, width, frame.height()).to_image())
+}
+
+/// Check if two frames are nearly identical (no visible change).
+/// Shared by the scroll listener and GIF frame deduplication.
+pub fn frames_nearly_identical(
+    a: &image::RgbaImage,
+    b: &image::RgbaImage,
+    tolerance: &ScrollChangeTolerance,
+) -> bool {
+    let (w, h) = a.dimensions();
+    if b.dimensions() != (w, h) || h == 0 {
+        return false;
+    }
+    if tolerance.compare == FrameCompare::Ssim {
+        return crate::ssim::mean_ssim(a, b) >= tolerance.ssim_threshold as f64;
+    }
+
+    // Sample a few rows (fractions of the height, middle of the image by default)
+    let step = tolerance.sample_step.max(1) as usize;
+    let mut total_diff = 0u64;
+    let mut samples = 0u64;
+
+    for &frac in &tolerance.sample_rows {
+        let y = ((frac.clamp(0.0, 1.0) * h as f32) as u32).min(h - 1);
+        for x in (0..w).step_by(step) {
+            let pa = a.get_pixel(x, y);
+            let pb = b.get_pixel(x, y);
+            let diff = (pa[0] as i32 - pb[0] as i32).unsigned_abs()
+                + (pa[1] as i32 - pb[1] as i32).unsigned_abs()
+                + (pa[2] as i32 - pb[2] as i32).unsigned_abs();
+            total_diff += diff as u64;
+            samples += 1;
+        }
+    }
+
+    let avg_diff = total_diff as f64 / samples.max(1) as f64;
+    avg_diff < tolerance.threshold as f64 // Very similar = content hasn't scrolled
+}
+
 /// Generate a preview image as base64 JPEG (fast), scaled to fit max_height
 pub fn generate_preview_base64(img: &RgbaImage, max_height: u32) -> Result<String, String> {
     let (w, h) = img.dimensions();
@@ -464,7 +1387,7 @@ pub fn open_scroll_overlay(
     state: tauri::State<SharedState>,
     region: Region,
 ) -> Result<(), String> {
-    println!("[DEBUG][open_scroll_overlay] 打开滚动截图悬浮窗");
+    log::debug!("[open_scroll_overlay] 打开滚动截图悬浮窗");
 
     // Close existing scroll-overlay if any
     if let Some(win) = app.get_webview_window("scroll-overlay") {
@@ -506,9 +1429,9 @@ pub fn open_scroll_overlay(
     }
 
     // Register stop_scroll shortcuts so user can stop with ESC (or configured key)
-    println!("[DEBUG][open_scroll_overlay] 注册 stop_scroll 快捷键");
+    log::debug!("[open_scroll_overlay] 注册 stop_scroll 快捷键");
     register_stop_scroll_shortcuts(&app);
-    println!("[DEBUG][open_scroll_overlay] 快捷键注册完成");
+    log::debug!("[open_scroll_overlay] 快捷键注册完成");
 
     // Build window WITHOUT focus - critical for scroll events to pass through
     // Create window BEFORE capturing to reduce perceived latency
@@ -541,15 +1464,15 @@ pub fn open_scroll_overlay(
         std::thread::sleep(std::time::Duration::from_millis(150));
 
         if let Err(e) = capture_initial_scroll_frame(&state_clone, &region_clone) {
-            eprintln!("[ERROR] Failed to capture initial scroll frame: {}", e);
+            log::error!("[open_scroll_overlay] Failed to capture initial scroll frame: {}", e);
             return;
         }
 
         // Get data with minimal lock time
-        let (frame_count, total_height, stitched) = {
+        let (frame_count, stitched) = {
             let s = state_clone.lock().unwrap();
             match s.scroll_stitched.as_ref() {
-                Some(img) => (s.scroll_frames.len(), img.height(), img.clone()),
+                Some(img) => (s.scroll_frames.len(), img.clone()),
                 None => return,
             }
         }; // Lock released here
@@ -560,8 +1483,10 @@ pub fn open_scroll_overlay(
                 "scroll-preview-update",
                 ScrollCaptureProgress {
                     frame_count,
-                    total_height,
+                    total_height: stitched.height(),
+                    total_width: stitched.width(),
                     preview_base64: preview,
+                    estimated_complete: false,
                 },
             );
         }
@@ -599,14 +1524,14 @@ pub fn open_scroll_overlay(
         crate::window_detect::activate_window_at_position(center_x, center_y);
     }
 
-    // Start event-driven scroll listener (macOS)
-    #[cfg(target_os = "macos")]
+    // Start event-driven scroll listener (macOS CGEventTap / Linux evdev)
+    #[cfg(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll")))]
     {
-        println!("[DEBUG][open_scroll_overlay] 启动滚动监听");
+        log::debug!("[open_scroll_overlay] 启动滚动监听");
         start_scroll_listener(app.clone());
     }
 
-    println!("[DEBUG][open_scroll_overlay] 悬浮窗创建成功 (non-activating)");
+    log::debug!("[open_scroll_overlay] 悬浮窗创建成功 (non-activating)");
     Ok(())
 }
 
@@ -618,7 +1543,7 @@ pub fn start_scroll_capture_inline(
     state: tauri::State<SharedState>,
     region: Region,
 ) -> Result<ScrollCaptureProgress, String> {
-    println!("[DEBUG][start_scroll_capture_inline] 开始内联滚动捕获");
+    log::debug!("[start_scroll_capture_inline] 开始内联滚动捕获");
 
     // Store region for capture
     {
@@ -633,11 +1558,11 @@ pub fn start_scroll_capture_inline(
     capture_initial_scroll_frame(state.inner(), &region)?;
 
     // Generate preview
-    let (frame_count, total_height, preview) = {
+    let (frame_count, (total_width, total_height), preview) = {
         let s = state.lock().unwrap();
         let frame = s.scroll_stitched.as_ref().ok_or("No frame captured")?;
         let preview = generate_preview_base64(frame, 600)?;
-        (s.scroll_frames.len(), frame.height(), preview)
+        (s.scroll_frames.len(), frame.dimensions(), preview)
     };
 
     // Activate the window under the capture region (center point)
@@ -648,21 +1573,23 @@ pub fn start_scroll_capture_inline(
         crate::window_detect::activate_window_at_position(center_x, center_y);
     }
 
-    // Start event-driven scroll listener (macOS)
-    #[cfg(target_os = "macos")]
+    // Start event-driven scroll listener (macOS CGEventTap / Linux evdev)
+    #[cfg(any(target_os = "macos", all(target_os = "linux", feature = "linux-scroll")))]
     {
-        println!("[DEBUG][start_scroll_capture_inline] 启动滚动监听");
+        log::debug!("[start_scroll_capture_inline] 启动滚动监听");
         start_scroll_listener(app.clone());
     }
 
-    println!(
-        "[DEBUG][start_scroll_capture_inline] 完成! frame_count={}, height={}",
+    log::debug!(
+        "[start_scroll_capture_inline] 完成! frame_count={}, height={}",
         frame_count, total_height
     );
 
     Ok(ScrollCaptureProgress {
         frame_count,
         total_height,
+        total_width,
         preview_base64: preview,
+        estimated_complete: false,
     })
 }
@@ -1,10 +1,20 @@
-use crate::capture::Screen;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::capture::{logical_to_physical, Screen};
+use image::RgbaImage;
 use mouse_position::mouse_position::Mouse;
-use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder};
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder};
 
+use crate::config::{self, DisplayKey, LastRegion};
 use crate::state::SharedState;
-use crate::types::{CaptureMode, Region, WindowInfo};
-use crate::windows::{open_permission_window, set_activation_policy};
+use crate::types::{
+    CaptureMode, LoupeData, Region, RegionHistoryItem, RegionPreset, RegionValidation,
+    RegionVisibility, ScreenshotOptions, WindowInfo,
+};
+use crate::windows::hide_main_for_capture;
 
 #[cfg(target_os = "macos")]
 use crate::native_screenshot;
@@ -15,20 +25,20 @@ use crate::window_detect;
 
 #[tauri::command]
 pub fn open_selector(app: AppHandle, state: tauri::State<SharedState>) -> Result<(), String> {
-    println!("[DEBUG][open_selector] 入口");
+    log::debug!("[open_selector] 入口");
 
     // Check screen recording permission first (macOS only)
     #[cfg(target_os = "macos")]
     {
         if !permission::has_screen_recording_permission() {
-            println!("[DEBUG][open_selector] 无屏幕录制权限，打开权限窗口");
-            let _ = open_permission_window(&app);
+            log::debug!("[open_selector] 无屏幕录制权限");
+            crate::commands::handle_missing_permission(&app);
             return Ok(());
         }
     }
 
     if let Some(win) = app.get_webview_window("selector") {
-        println!("[DEBUG][open_selector] selector 窗口已存在，跳过");
+        log::debug!("[open_selector] selector 窗口已存在，跳过");
         let _ = win.show();
         let _ = win.set_focus();
         return Ok(());
@@ -47,19 +57,14 @@ pub fn open_selector(app: AppHandle, state: tauri::State<SharedState>) -> Result
             Some(CaptureMode::Gif) | Some(CaptureMode::Video)
         );
     if should_hide {
-        if let Some(main_win) = app.get_webview_window("main") {
-            println!("[DEBUG][open_selector] GIF/Video 模式，隐藏主窗口");
-            let _ = main_win.hide();
-            set_activation_policy(1);
-        }
+        log::debug!("[open_selector] GIF/Video 模式，隐藏主窗口");
+        hide_main_for_capture(&app);
     } else {
-        println!("[DEBUG][open_selector] 截图/滚动模式或有编辑数据，保持主窗口");
+        log::debug!("[open_selector] 截图/滚动模式或有编辑数据，保持主窗口");
     }
 
-    let screens = Screen::all().map_err(|e| e.to_string())?;
-    if screens.is_empty() {
-        return Err("No screens found".to_string());
-    }
+    // Displays can briefly vanish while waking from sleep
+    let screens = Screen::all_nonempty()?;
 
     let screen = &screens[0];
     let screen_x = screen.display_info.x;
@@ -75,10 +80,11 @@ pub fn open_selector(app: AppHandle, state: tauri::State<SharedState>) -> Result
         s.screen_scale = scale;
     }
 
-    println!("[DEBUG][open_selector] 准备创建 selector 窗口");
+    log::debug!("[open_selector] 准备创建 selector 窗口");
 
     let win = WebviewWindowBuilder::new(&app, "selector", WebviewUrl::App("/selector.html".into()))
         .title("Select Region")
+        .initialization_script(&selector_style_script())
         .decorations(false)
         .always_on_top(true)
         .skip_taskbar(true)
@@ -89,14 +95,13 @@ pub fn open_selector(app: AppHandle, state: tauri::State<SharedState>) -> Result
         .build()
         .map_err(|e| e.to_string())?;
 
-    let physical_width = (width as f32 * scale) as u32;
-    let physical_height = (height as f32 * scale) as u32;
-    let physical_x = (screen_x as f32 * scale) as i32;
-    let physical_y = (screen_y as f32 * scale) as i32;
+    // Window frames are global physical px: the display's logical bounds scaled from (0, 0)
+    let display = Region { x: screen_x, y: screen_y, width, height };
+    let physical = logical_to_physical(&display, (0, 0), scale);
 
-    win.set_size(PhysicalSize::new(physical_width, physical_height))
+    win.set_size(PhysicalSize::new(physical.width, physical.height))
         .map_err(|e| e.to_string())?;
-    win.set_position(PhysicalPosition::new(physical_x, physical_y))
+    win.set_position(PhysicalPosition::new(physical.x, physical.y))
         .map_err(|e| e.to_string())?;
 
     #[cfg(target_os = "macos")]
@@ -108,7 +113,9 @@ pub fn open_selector(app: AppHandle, state: tauri::State<SharedState>) -> Result
                 let _: () = msg_send![ns_window, setLevel: 1000_i64];
                 // Prevent mouse events from passing through transparent areas
                 let _: () = msg_send![ns_window, setIgnoresMouseEvents: false];
-                // Set a minimal background color to capture mouse events
+                // Near-transparent backing so AppKit hit-tests the whole window. Deliberately
+                // not `selector_dim_opacity`: the dim is painted by the page (--selector-dim),
+                // and this alpha has to stay invisible whatever that setting is.
                 let ns_color_class = class!(NSColor);
                 let clear_color: *mut objc::runtime::Object =
                     msg_send![ns_color_class, colorWithWhite:0.0_f64 alpha:0.005_f64];
@@ -120,21 +127,437 @@ pub fn open_selector(app: AppHandle, state: tauri::State<SharedState>) -> Result
     Ok(())
 }
 
+/// Init script exposing the configured overlay colors as CSS variables, so the
+/// selection dim / border / guidelines are styled before the page first paints
+fn selector_style_script() -> String {
+    let cfg = crate::config::load_config();
+    let style = serde_json::json!({
+        "--selector-dim": cfg.selector_dim_opacity.clamp(0.0, 1.0).to_string(),
+        "--selector-border": cfg.selector_border_color,
+        "--selector-guide": cfg.selector_guide_color,
+    });
+    format!(
+        "(() => {{ const s = {}; const root = document.documentElement; \
+         for (const k in s) root.style.setProperty(k, s[k]); }})();",
+        style
+    )
+}
+
 #[tauri::command]
-pub fn set_region(state: tauri::State<SharedState>, region: Region) {
-    println!(
-        "[DEBUG][set_region] ====== 被调用 ====== x={}, y={}, w={}, h={}",
-        region.x, region.y, region.width, region.height
+pub fn set_region(
+    state: tauri::State<SharedState>,
+    region: Region,
+    append: Option<bool>,
+) -> Result<(), String> {
+    log::debug!(
+        "[set_region] ====== 被调用 ====== x={}, y={}, w={}, h={}, append={:?}",
+        region.x, region.y, region.width, region.height, append
     );
+    // Accidental clicks/tiny drags: reject so the selector stays open
+    let cfg = config::load_config();
+    if region.width < cfg.min_region_width || region.height < cfg.min_region_height {
+        return Err(format!(
+            "Region too small ({}x{}, minimum {}x{})",
+            region.width, region.height, cfg.min_region_width, cfg.min_region_height
+        ));
+    }
+    let append = append.unwrap_or(false);
+    let display = if append { None } else { display_key_for(&region) };
     let mut s = state.lock().unwrap();
-    println!("[DEBUG][set_region] 直接使用逻辑像素坐标（不缩放）");
+    log::debug!("[set_region] 直接使用逻辑像素坐标（不缩放）");
+    // append=true accumulates selections for capture_all_regions
+    if append {
+        s.regions.push(region.clone());
+    } else {
+        s.regions = vec![region.clone()];
+        let display_id = display.as_ref().map(|d| d.id);
+        push_region_history(&mut s.region_history, display_id, &region, cfg.region_history_size);
+        remember_last_region(&region, display);
+    }
     s.region = Some(region);
+    Ok(())
+}
+
+/// Margin captures add around the selected region on each side (logical px, < 0 = inset),
+/// e.g. so the subject doesn't touch the edge. Applies to screenshots, recordings and
+/// scroll captures; returns the resulting effective region (clamped to its display).
+#[tauri::command]
+pub fn set_capture_padding(
+    state: tauri::State<SharedState>,
+    px: i32,
+) -> Result<Option<Region>, String> {
+    let mut s = state.lock().unwrap();
+    s.capture_padding = px.clamp(-500, 500);
+    let effective = s.effective_region();
+    if let Some(r) = &effective {
+        log::info!(
+            "[set_capture_padding] {}px -> ({}, {}) {}x{}",
+            s.capture_padding, r.x, r.y, r.width, r.height
+        );
+    }
+    Ok(effective)
+}
+
+/// Current capture padding (px) and the effective region it produces, if a region is set
+#[tauri::command]
+pub fn get_capture_padding(state: tauri::State<SharedState>) -> (i32, Option<Region>) {
+    let s = state.lock().unwrap();
+    (s.capture_padding, s.effective_region())
+}
+
+/// Move `region` to the front of the history, keeping at most `per_display` per display
+fn push_region_history(
+    history: &mut Vec<(Option<u32>, Region)>,
+    display_id: Option<u32>,
+    region: &Region,
+    per_display: usize,
+) {
+    history.retain(|(id, r)| {
+        !(*id == display_id
+            && r.x == region.x
+            && r.y == region.y
+            && r.width == region.width
+            && r.height == region.height)
+    });
+    history.insert(0, (display_id, region.clone()));
+
+    let mut seen = 0;
+    history.retain(|(id, _)| {
+        if *id != display_id {
+            return true;
+        }
+        seen += 1;
+        seen <= per_display.max(1)
+    });
+}
+
+/// Display whose bounds contain the region's top-left corner (falls back to the first)
+fn display_key_for(region: &Region) -> Option<DisplayKey> {
+    let screens = Screen::all().ok()?;
+    let info = screens
+        .iter()
+        .map(|screen| &screen.display_info)
+        .find(|d| {
+            region.x >= d.x
+                && region.y >= d.y
+                && region.x < d.x + d.width as i32
+                && region.y < d.y + d.height as i32
+        })
+        .or_else(|| screens.first().map(|screen| &screen.display_info))?;
+    Some(DisplayKey {
+        id: info.id,
+        x: info.x,
+        y: info.y,
+        width: info.width,
+        height: info.height,
+        scale: info.scale_factor,
+    })
+}
+
+/// Persist a confirmed region so use_last_region can repeat it
+fn remember_last_region(region: &Region, display: Option<DisplayKey>) {
+    let Some(display) = display else {
+        return;
+    };
+    let last = LastRegion {
+        region: region.clone(),
+        display,
+    };
+    if let Err(e) = config::save_last_region(last) {
+        log::warn!("[last_region] Failed to save: {}", e);
+    }
+}
+
+/// Most recent remembered region: the current display's if any, otherwise any stored one
+fn find_last_region() -> Option<LastRegion> {
+    let mut all = config::load_last_regions();
+    let current = Screen::all()
+        .ok()
+        .and_then(|screens| screens.first().map(|screen| screen.display_info.id));
+    current
+        .and_then(|id| all.remove(&id))
+        .or_else(|| all.into_values().next())
+}
+
+/// Last confirmed region (for showing it in the UI)
+#[tauri::command]
+pub fn get_last_region() -> Option<Region> {
+    find_last_region().map(|last| last.region)
+}
+
+/// Check `region` (default: the current one) against the connected displays: fully visible,
+/// clipped or off-screen, with a clamped region to use instead
+#[tauri::command]
+pub fn validate_region(
+    state: tauri::State<SharedState>,
+    region: Option<Region>,
+) -> Result<RegionValidation, String> {
+    let region = match region {
+        Some(region) => region,
+        None => state.lock().unwrap().region.clone().ok_or("No region selected")?,
+    };
+    crate::capture::validate_region(&region)
+}
+
+/// Vet a stored region before reusing it: an off-screen region is an error, a clipped one
+/// is replaced by its on-screen part (announced via `region-clipped`)
+fn ensure_region_on_screen(app: &AppHandle, region: Region) -> Result<Region, String> {
+    let validation = crate::capture::validate_region(&region)?;
+    match (validation.visibility, &validation.suggested) {
+        (RegionVisibility::Visible, _) => Ok(region),
+        (RegionVisibility::Clipped, Some(clamped)) => {
+            log::warn!(
+                "[validate_region] ({}, {}) {}x{} clipped to ({}, {}) {}x{}",
+                region.x, region.y, region.width, region.height,
+                clamped.x, clamped.y, clamped.width, clamped.height
+            );
+            let _ = app.emit("region-clipped", &validation);
+            Ok(clamped.clone())
+        }
+        _ => Err(format!(
+            "Region ({}, {}) {}x{} is off-screen; select it again",
+            region.x, region.y, region.width, region.height
+        )),
+    }
+}
+
+/// Capture the last confirmed region again without opening the selector
+#[tauri::command]
+pub fn use_last_region(app: AppHandle, state: tauri::State<SharedState>) -> Result<String, String> {
+    let last = find_last_region().ok_or("No previous region")?;
+    let region = last.region.clone();
+
+    // Display resized, rescaled or moved since: region may now cover something else
+    let current = display_key_for(&region);
+    if current.as_ref() != Some(&last.display) {
+        log::info!(
+            "[use_last_region] Display layout changed (saved {:?}, now {:?})",
+            last.display, current
+        );
+        let _ = app.emit("last-region-display-changed", &last.display);
+    }
+
+    let region = ensure_region_on_screen(&app, region)?;
+    {
+        let mut s = state.lock().unwrap();
+        s.regions = vec![region.clone()];
+        s.region = Some(region);
+    }
+    crate::commands::save_screenshot(app, state, None)
+}
+
+/// Recently confirmed regions, newest first, with thumbnails when the cached snapshot
+/// covers them (static mode / repeat capture)
+#[tauri::command]
+pub fn get_region_history(state: tauri::State<SharedState>) -> Vec<RegionHistoryItem> {
+    const THUMB_MAX: u32 = 160;
+    let s = state.lock().unwrap();
+    s.region_history
+        .iter()
+        .enumerate()
+        .map(|(index, (display_id, region))| {
+            let thumbnail = s.cached_snapshot.as_ref().and_then(|snapshot| {
+                let crop = crate::commands::export::crop_snapshot_region(
+                    snapshot,
+                    region,
+                    s.screen_x,
+                    s.screen_y,
+                    s.screen_scale,
+                )
+                .ok()?;
+                let thumb = if crop.width() > THUMB_MAX || crop.height() > THUMB_MAX {
+                    image::DynamicImage::ImageRgba8(crop)
+                        .thumbnail(THUMB_MAX, THUMB_MAX)
+                        .to_rgba8()
+                } else {
+                    crop
+                };
+                crate::annotate::encode_png_data_url(&thumb).ok()
+            });
+            RegionHistoryItem {
+                index,
+                region: region.clone(),
+                display_id: *display_id,
+                thumbnail,
+            }
+        })
+        .collect()
+}
+
+/// Load a region from get_region_history back as the current selection
+#[tauri::command]
+pub fn select_region_from_history(
+    state: tauri::State<SharedState>,
+    index: usize,
+) -> Result<Region, String> {
+    let mut s = state.lock().unwrap();
+    let (display_id, region) = s
+        .region_history
+        .get(index)
+        .cloned()
+        .ok_or("No region at that history index")?;
+    s.region_history.remove(index);
+    s.region_history.insert(0, (display_id, region.clone()));
+    s.regions = vec![region.clone()];
+    s.region = Some(region.clone());
+    Ok(region)
+}
+
+/// Rect for `preset` on a display with the given logical bounds
+fn preset_rect(preset: RegionPreset, display: &Region) -> Region {
+    let (w, h) = (display.width, display.height);
+    let (half_w, half_h) = (w / 2, h / 2);
+    let (x, y, width, height) = match preset {
+        RegionPreset::Full => (0, 0, w, h),
+        RegionPreset::LeftHalf => (0, 0, half_w, h),
+        RegionPreset::RightHalf => (half_w, 0, w - half_w, h),
+        RegionPreset::TopHalf => (0, 0, w, half_h),
+        RegionPreset::BottomHalf => (0, half_h, w, h - half_h),
+        RegionPreset::TopLeftQuarter => (0, 0, half_w, half_h),
+        RegionPreset::TopRightQuarter => (half_w, 0, w - half_w, half_h),
+        RegionPreset::BottomLeftQuarter => (0, half_h, half_w, h - half_h),
+        RegionPreset::BottomRightQuarter => (half_w, half_h, w - half_w, h - half_h),
+        RegionPreset::Centered { width, height } => {
+            let (width, height) = (width.min(w), height.min(h));
+            ((w - width) / 2, (h - height) / 2, width, height)
+        }
+    };
+    Region {
+        x: display.x + x as i32,
+        y: display.y + y as i32,
+        width,
+        height,
+    }
+}
+
+/// Logical bounds of the display under the cursor (falls back to the first display)
+fn display_bounds_at_cursor() -> Result<Region, String> {
+    let screens = Screen::all()?;
+    let (cx, cy) = match Mouse::get_mouse_position() {
+        Mouse::Position { x, y } => (x, y),
+        Mouse::Error => (i32::MIN, i32::MIN),
+    };
+    let info = screens
+        .iter()
+        .map(|screen| &screen.display_info)
+        .find(|d| {
+            cx >= d.x && cy >= d.y && cx < d.x + d.width as i32 && cy < d.y + d.height as i32
+        })
+        .or_else(|| screens.first().map(|screen| &screen.display_info))
+        .ok_or("No screens found")?;
+    Ok(Region {
+        x: info.x,
+        y: info.y,
+        width: info.width,
+        height: info.height,
+    })
+}
+
+/// Select a preset fraction of the display under the cursor without drawing.
+/// Goes through set_region, so history and the remembered region update as usual.
+/// `content_area_only` (default: the capture_content_area_only setting) measures the
+/// preset against the display minus the menu bar and Dock.
+#[tauri::command]
+pub fn apply_region_preset(
+    state: tauri::State<SharedState>,
+    preset: RegionPreset,
+    content_area_only: Option<bool>,
+) -> Result<Region, String> {
+    let mut display = display_bounds_at_cursor()?;
+    if content_area_only.unwrap_or_else(|| config::load_config().capture_content_area_only) {
+        display = crate::capture::content_area(display);
+    }
+    let region = preset_rect(preset, &display);
+    log::info!("[apply_region_preset] {:?}", preset);
+    set_region(state, region.clone(), None)?;
+    Ok(region)
+}
+
+// ============ Live Region Preview ============
+
+/// Preview thread is running
+static REGION_PREVIEW_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Bumped per started thread, so a thread outliving a quick stop/start exits
+static REGION_PREVIEW_GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Region the preview thread captures; updated by repeated start_region_preview calls
+static REGION_PREVIEW_RECT: Mutex<Option<Region>> = Mutex::new(None);
+
+/// Stream small JPEG previews of `region` as `region-preview` events (data URLs) so the
+/// selector can show live content in dynamic mode. Calling again while running just
+/// moves the region. `fps` is capped at 15; `max_height` defaults to 240 px.
+#[tauri::command]
+pub fn start_region_preview(
+    app: AppHandle,
+    region: Region,
+    fps: Option<u32>,
+    max_height: Option<u32>,
+) -> Result<(), String> {
+    if region.width == 0 || region.height == 0 {
+        return Err("Empty region".to_string());
+    }
+    *REGION_PREVIEW_RECT.lock().unwrap() = Some(region);
+    if REGION_PREVIEW_ACTIVE.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let interval = Duration::from_millis(1000 / fps.unwrap_or(8).clamp(1, 15) as u64);
+    let max_height = max_height.unwrap_or(240).max(16);
+    let generation = REGION_PREVIEW_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    log::info!("[region_preview] Started ({:?} interval)", interval);
+    std::thread::spawn(move || {
+        while REGION_PREVIEW_ACTIVE.load(Ordering::SeqCst)
+            && REGION_PREVIEW_GENERATION.load(Ordering::SeqCst) == generation
+        {
+            let started = std::time::Instant::now();
+            let region = REGION_PREVIEW_RECT.lock().unwrap().clone();
+            if let Some(r) = region {
+                let frame = Screen::all().and_then(|screens| {
+                    let black = image::Rgba([0, 0, 0, 255]);
+                    crate::capture::capture_region(&screens, r.x, r.y, r.width, r.height, black)
+                });
+                let preview = frame
+                    .and_then(|img| crate::commands::generate_preview_base64(&img, max_height));
+                match preview {
+                    Ok(data_url) => {
+                        let _ = app.emit("region-preview", data_url);
+                    }
+                    Err(e) => log::warn!("[region_preview] Capture failed: {}", e),
+                }
+            }
+            // Throttle: sleep out the rest of the interval
+            if let Some(rest) = interval.checked_sub(started.elapsed()) {
+                std::thread::sleep(rest);
+            }
+        }
+        log::info!("[region_preview] Stopped");
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_region_preview() {
+    REGION_PREVIEW_ACTIVE.store(false, Ordering::SeqCst);
+    *REGION_PREVIEW_RECT.lock().unwrap() = None;
+}
+
+/// Get all accumulated regions (batch capture)
+#[tauri::command]
+pub fn get_regions(state: tauri::State<SharedState>) -> Vec<Region> {
+    state.lock().unwrap().regions.clone()
+}
+
+/// Reset the whole region list
+#[tauri::command]
+pub fn clear_regions(state: tauri::State<SharedState>) {
+    let mut s = state.lock().unwrap();
+    s.regions.clear();
+    s.region = None;
 }
 
 #[tauri::command]
 pub fn get_pending_mode(state: tauri::State<SharedState>) -> Option<CaptureMode> {
     let mode = state.lock().unwrap().pending_mode;
-    println!("[DEBUG][get_pending_mode] 返回: {:?}", mode);
+    log::debug!("[get_pending_mode] 返回: {:?}", mode);
     mode
 }
 
@@ -194,6 +617,54 @@ pub fn get_magnifier_pixels(
     Some(pixels)
 }
 
+/// Get a size x size block of snapshot pixels centered on the cursor, plus the
+/// center pixel's color. Static (frozen) mode only: None without a cached snapshot.
+#[tauri::command]
+pub fn get_loupe_region(
+    state: tauri::State<SharedState>,
+    x: i32,
+    y: i32,
+    size: u32,
+) -> Result<Option<LoupeData>, String> {
+    let s = state.lock().unwrap();
+    let Some(img) = s.cached_snapshot.as_ref() else {
+        return Ok(None);
+    };
+    let scale = s.screen_scale;
+
+    // Convert logical to physical coordinates
+    let cx = ((x - s.screen_x) as f32 * scale) as i32;
+    let cy = ((y - s.screen_y) as f32 * scale) as i32;
+    if cx < 0 || cy < 0 || cx >= img.width() as i32 || cy >= img.height() as i32 {
+        return Ok(None);
+    }
+
+    // Odd size so there is a true center pixel; out-of-bounds stays transparent
+    let size = size.clamp(3, 101) | 1;
+    let half = (size / 2) as i32;
+    let loupe = RgbaImage::from_fn(size, size, |lx, ly| {
+        let sx = cx - half + lx as i32;
+        let sy = cy - half + ly as i32;
+        if sx >= 0 && sy >= 0 && (sx as u32) < img.width() && (sy as u32) < img.height() {
+            *img.get_pixel(sx as u32, sy as u32)
+        } else {
+            image::Rgba([0, 0, 0, 0])
+        }
+    });
+
+    let center = img.get_pixel(cx as u32, cy as u32);
+    let center_color = format!("#{:02X}{:02X}{:02X}", center[0], center[1], center[2]);
+    drop(s);
+
+    Ok(Some(LoupeData {
+        image_base64: crate::annotate::encode_png_data_url(&loupe)?,
+        size,
+        center_color,
+        center_x: cx as u32,
+        center_y: cy as u32,
+    }))
+}
+
 #[tauri::command]
 pub fn get_window_at_cursor() -> Option<Region> {
     #[cfg(target_os = "macos")]
@@ -205,6 +676,66 @@ pub fn get_window_at_cursor() -> Option<Region> {
     None
 }
 
+/// Capture the window under the cursor without its titlebar (macOS).
+/// `include_titlebar: true` captures the whole window frame instead. Returns the saved path.
+#[tauri::command]
+pub fn capture_window_content_at_cursor(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    include_titlebar: Option<bool>,
+) -> Result<String, String> {
+    let info = get_window_info_at_cursor().ok_or("No window under cursor")?;
+    let titlebar = if include_titlebar.unwrap_or(false) {
+        0
+    } else {
+        info.titlebar_height.min(info.height.saturating_sub(1))
+    };
+    let region = Region {
+        x: info.x,
+        y: info.y + titlebar as i32,
+        width: info.width,
+        height: info.height - titlebar,
+    };
+    log::info!(
+        "[capture_window_content_at_cursor] window ({}, {}) {}x{}, titlebar {} -> {}x{}",
+        info.x, info.y, info.width, info.height, titlebar, region.width, region.height
+    );
+
+    {
+        let mut s = state.lock().unwrap();
+        s.regions = vec![region.clone()];
+        s.region = Some(region);
+    }
+    crate::commands::save_screenshot(app, state, None)
+}
+
+/// Snap a rough selection's edges to nearby window boundaries (default within 12px).
+/// macOS only; other platforms return the region unchanged.
+#[tauri::command]
+pub fn snap_region_to_elements(region: Region, threshold: Option<u32>) -> Region {
+    #[cfg(target_os = "macos")]
+    {
+        let snapped = window_detect::snap_region_to_windows(&region, threshold.unwrap_or(12));
+        log::debug!(
+            "[snap_region_to_elements] ({}, {}) {}x{} -> ({}, {}) {}x{}",
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            snapped.x,
+            snapped.y,
+            snapped.width,
+            snapped.height
+        );
+        snapped
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = threshold;
+        region
+    }
+}
+
 /// Get window info at cursor including titlebar height (for exclude-titlebar feature)
 #[tauri::command]
 pub fn get_window_info_at_cursor() -> Option<WindowInfo> {
@@ -218,6 +749,7 @@ pub fn get_window_info_at_cursor() -> Option<WindowInfo> {
                     width: info.width,
                     height: info.height,
                     titlebar_height: info.titlebar_height,
+                    window_id: Some(info.window_id).filter(|&id| id != 0),
                 });
             }
         }
@@ -229,12 +761,202 @@ pub fn get_window_info_at_cursor() -> Option<WindowInfo> {
 
 #[tauri::command]
 pub fn clear_pending_mode(state: tauri::State<SharedState>) {
-    state.lock().unwrap().pending_mode = None;
+    let mut s = state.lock().unwrap();
+    // Keep the mode around so repeat_last_capture can rerun it
+    if let Some(mode) = s.pending_mode.take() {
+        s.last_pending_mode = Some(mode);
+    }
+}
+
+/// Set the mode the next selector session uses (dashboard mode buttons)
+#[tauri::command]
+pub fn set_capture_mode(state: tauri::State<SharedState>, mode: CaptureMode) -> Result<(), String> {
+    if mode == CaptureMode::Scroll && !config::load_config().scroll_capture_enabled {
+        return Err("Scroll capture is disabled in settings".to_string());
+    }
+
+    let mut s = state.lock().unwrap();
+    let previous = s.pending_mode.or(s.last_pending_mode);
+    let was_static = previous == Some(CaptureMode::StaticImage);
+    let is_static = mode == CaptureMode::StaticImage;
+    if previous.is_some() && was_static != is_static {
+        // A region/snapshot from the frozen screen doesn't match the live one (and vice versa)
+        log::info!("[set_capture_mode] static/dynamic switch, clearing region and snapshot");
+        s.region = None;
+        s.regions.clear();
+        s.cached_snapshot = None;
+    }
+
+    log::info!("[set_capture_mode] {:?} -> {:?}", previous, mode);
+    s.pending_mode = Some(mode);
+    Ok(())
+}
+
+/// Screen grab for a frozen (static) selection, without lovshot's own windows unless
+/// `exclude_own_windows` is off
+#[cfg(target_os = "macos")]
+fn capture_static_cgimage() -> Option<native_screenshot::CGImageRef> {
+    if config::load_config().exclude_own_windows {
+        native_screenshot::capture_cgimage_excluding_own()
+    } else {
+        native_screenshot::capture_cgimage_with_retry()
+    }
+}
+
+/// Fresh full-screen grab into cached_snapshot (static recapture without the selector)
+fn refresh_cached_snapshot(state: &SharedState) -> Result<(), String> {
+    let screens = Screen::all().map_err(|e| e.to_string())?;
+    let screen = screens.first().ok_or("No screens found")?;
+
+    #[cfg(target_os = "macos")]
+    let rgba = capture_static_cgimage()
+        .as_ref()
+        .and_then(native_screenshot::cgimage_to_rgba);
+    #[cfg(target_os = "linux")]
+    let rgba = crate::linux_capture::capture_rgba(screen.display_info.x, screen.display_info.y);
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    let rgba = screen.capture().ok();
+
+    let rgba = rgba.ok_or("Failed to capture screen")?;
+    let mut s = state.lock().unwrap();
+    s.cached_snapshot = Some(rgba);
+    s.screen_x = screen.display_info.x;
+    s.screen_y = screen.display_info.y;
+    s.screen_scale = screen.display_info.scale_factor;
+    Ok(())
+}
+
+/// Rerun the last capture (same mode and region) without the selector.
+/// Falls back to opening the selector when nothing has been captured yet.
+pub fn repeat_last_capture_internal(app: AppHandle) -> Result<(), String> {
+    let (mode, region) = {
+        let state = app.state::<SharedState>();
+        let s = state.lock().unwrap();
+        if s.recording || s.scroll_capturing {
+            return Err("Capture already in progress".to_string());
+        }
+        (s.last_pending_mode, s.region.clone())
+    };
+
+    let (Some(mode), Some(region)) = (mode, region) else {
+        log::info!("[repeat_last_capture] No previous capture, opening selector");
+        return open_selector_internal(app);
+    };
+    // Displays may have changed since; the capture paths below read the vetted region
+    let region = {
+        let region = ensure_region_on_screen(&app, region)?;
+        let state = app.state::<SharedState>();
+        let mut s = state.lock().unwrap();
+        s.region = Some(region);
+        s.effective_region().ok_or("No region selected")?
+    };
+    log::info!(
+        "[repeat_last_capture] {:?} at ({}, {}) {}x{}",
+        mode, region.x, region.y, region.width, region.height
+    );
+    capture_region_with_mode(&app, mode, region)
+}
+
+/// Run a capture of `mode` over the region already stored in state, as if the selector
+/// had just confirmed it; `region` is the effective one (scroll overlay placement)
+fn capture_region_with_mode(
+    app: &AppHandle,
+    mode: CaptureMode,
+    region: Region,
+) -> Result<(), String> {
+    match mode {
+        CaptureMode::Image | CaptureMode::StaticImage => {
+            let is_static = mode == CaptureMode::StaticImage;
+            if is_static {
+                refresh_cached_snapshot(&app.state::<SharedState>())?;
+            }
+            let options = ScreenshotOptions { use_cached: is_static, ..Default::default() };
+            crate::commands::save_screenshot(app.clone(), app.state(), Some(options))?;
+        }
+        CaptureMode::Gif | CaptureMode::Video => {
+            crate::commands::start_recording(app.clone(), app.state())?;
+        }
+        CaptureMode::Scroll => {
+            let progress = crate::commands::start_scroll_capture(app.state())?;
+            crate::commands::open_scroll_overlay(app.clone(), app.state(), region)?;
+            let _ = app.emit("scroll-preview-update", &progress);
+        }
+    }
+    Ok(())
+}
+
+/// Select a region by typed logical coordinates instead of dragging, e.g. from a design
+/// spec. The displays must cover the whole region, though it may span several (captures
+/// composite them); when `mode` is given, capture it right away. Returns the stored region.
+#[tauri::command]
+pub fn set_region_exact(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    mode: Option<CaptureMode>,
+) -> Result<Region, String> {
+    if width == 0 || height == 0 {
+        return Err(format!("Region must not be empty ({}x{})", width, height));
+    }
+    {
+        let s = state.lock().unwrap();
+        if s.recording || s.scroll_capturing {
+            return Err("Capture already in progress".to_string());
+        }
+    }
+    let region = Region { x, y, width, height };
+    let validation = crate::capture::validate_region(&region)?;
+    match (validation.visibility, validation.suggested) {
+        (RegionVisibility::Visible, _) => {}
+        (RegionVisibility::Clipped, Some(s)) => {
+            return Err(format!(
+                "Region extends past the display edge; closest fit is ({}, {}) {}x{}",
+                s.x, s.y, s.width, s.height
+            ));
+        }
+        _ => return Err(format!("Region ({}, {}) {}x{} is off-screen", x, y, width, height)),
+    }
+    set_region(app.state(), region.clone(), None)?;
+    log::info!("[set_region_exact] ({}, {}) {}x{} mode={:?}", x, y, width, height, mode);
+
+    let Some(mode) = mode else {
+        return Ok(region);
+    };
+    let effective = {
+        let mut s = state.lock().unwrap();
+        s.last_pending_mode = Some(mode);
+        s.effective_region().ok_or("No region selected")?
+    };
+    capture_region_with_mode(&app, mode, effective)?;
+    Ok(region)
+}
+
+/// Capture again with the same mode and region as last time
+#[tauri::command]
+pub fn repeat_last_capture(app: AppHandle) -> Result<(), String> {
+    repeat_last_capture_internal(app)
 }
 
 /// Freeze screen as window background (for dynamic -> static mode switch)
 #[tauri::command]
-pub fn capture_screen_now(app: AppHandle, state: tauri::State<SharedState>) -> bool {
+pub fn capture_screen_now(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    all_displays: Option<bool>,
+) -> bool {
+    let captured = capture_primary_now(&app, &state);
+    // After the primary snapshot is cached, so display 0 can reuse it
+    if all_displays.unwrap_or(false) {
+        let max = config::load_config().snapshot_cache_max_displays as usize;
+        cache_all_displays(state.inner().clone(), max);
+    }
+    captured
+}
+
+fn capture_primary_now(app: &AppHandle, state: &SharedState) -> bool {
     #[cfg(target_os = "macos")]
     {
         use tauri::Manager;
@@ -245,11 +967,11 @@ pub fn capture_screen_now(app: AppHandle, state: tauri::State<SharedState>) -> b
         };
 
         let start = std::time::Instant::now();
-        let cg_image = match native_screenshot::capture_cgimage() {
+        let cg_image = match capture_static_cgimage() {
             Some(img) => img,
             None => return false,
         };
-        println!("[capture_screen_now] 截图 {}ms", start.elapsed().as_millis());
+        log::debug!("[capture_screen_now] 截图 {}ms", start.elapsed().as_millis());
 
         // Set as window background (GPU accelerated, no encoding needed)
         let bg_start = std::time::Instant::now();
@@ -258,14 +980,14 @@ pub fn capture_screen_now(app: AppHandle, state: tauri::State<SharedState>) -> b
             let ns_window = webview.ns_window() as *mut objc::runtime::Object;
             native_screenshot::set_window_background_cgimage_raw(ns_window, cg_ptr);
         });
-        println!("[capture_screen_now] 设置背景 {}ms", bg_start.elapsed().as_millis());
+        log::debug!("[capture_screen_now] 设置背景 {}ms", bg_start.elapsed().as_millis());
 
         // Convert to RGBA and cache (for magnifier and saving)
         let convert_start = std::time::Instant::now();
         if let Some(rgba) = native_screenshot::cgimage_to_rgba(&cg_image) {
             let mut s = state.lock().unwrap();
             s.cached_snapshot = Some(rgba);
-            println!("[capture_screen_now] RGBA缓存 {}ms", convert_start.elapsed().as_millis());
+            log::debug!("[capture_screen_now] RGBA缓存 {}ms", convert_start.elapsed().as_millis());
         }
 
         true
@@ -276,6 +998,85 @@ pub fn capture_screen_now(app: AppHandle, state: tauri::State<SharedState>) -> b
     }
 }
 
+/// Freeze every display into `display_snapshots` in the background, at most `max` of them.
+/// Display 0 and the one under the cursor are kept first; beyond them, displays over the cap
+/// are dropped largest first, so they're never grabbed at all. Display 0 reuses the
+/// selector's own snapshot when it is ready. The result is discarded if the snapshots were
+/// cleared meanwhile (see AppState::clear_display_snapshots).
+fn cache_all_displays(state: SharedState, max: usize) {
+    let generation = state.lock().unwrap().display_snapshots_generation;
+    std::thread::spawn(move || {
+        let start = std::time::Instant::now();
+        let mut screens = Screen::all().unwrap_or_default();
+        if screens.len() < 2 {
+            return; // the selector's snapshot already covers the only display
+        }
+        let cursor = match Mouse::get_mouse_position() {
+            Mouse::Position { x, y } => Some((x, y)),
+            Mouse::Error => None,
+        };
+        let pinned = |s: &Screen| {
+            let d = &s.display_info;
+            let under_cursor = |(cx, cy): (i32, i32)| {
+                cx >= d.x && cy >= d.y && cx < d.x + d.width as i32 && cy < d.y + d.height as i32
+            };
+            d.id == 0 || cursor.is_some_and(under_cursor)
+        };
+        let px = |s: &Screen| {
+            let d = &s.display_info;
+            d.width as f32 * d.height as f32 * d.scale_factor * d.scale_factor
+        };
+        screens.sort_by(|a, b| pinned(b).cmp(&pinned(a)).then(px(a).total_cmp(&px(b))));
+        screens.truncate(max);
+
+        let primary = state.lock().unwrap().cached_snapshot.clone();
+        let mut snapshots = HashMap::new();
+        for screen in &screens {
+            let id = screen.display_info.id;
+            let img = match (&primary, id) {
+                (Some(img), 0) => Some(img.clone()),
+                _ => screen.capture().ok(),
+            };
+            if let Some(img) = img {
+                snapshots.insert(id, img);
+            }
+        }
+        log::debug!(
+            "[cache_all_displays] {} displays in {}ms",
+            snapshots.len(),
+            start.elapsed().as_millis()
+        );
+        let mut s = state.lock().unwrap();
+        if s.display_snapshots_generation != generation {
+            log::debug!("[cache_all_displays] snapshots cleared meanwhile, discarding");
+            return;
+        }
+        s.display_snapshots = snapshots;
+    });
+}
+
+/// Switch static-mode cropping to frozen display `index` from capture_screen_now's
+/// all-displays cache. Returns false when that display isn't cached (grab it instead).
+#[tauri::command]
+pub fn use_cached_display(state: tauri::State<SharedState>, index: u32) -> Result<bool, String> {
+    let screens = Screen::all()?;
+    let info = &screens
+        .iter()
+        .find(|s| s.display_info.id == index)
+        .ok_or_else(|| format!("Display {} not found", index))?
+        .display_info;
+    let mut s = state.lock().unwrap();
+    let Some(img) = s.display_snapshots.get(&index).cloned() else {
+        return Ok(false);
+    };
+    s.cached_snapshot = Some(img);
+    s.screen_x = info.x;
+    s.screen_y = info.y;
+    s.screen_scale = info.scale_factor;
+    log::info!("[use_cached_display] display {}", index);
+    Ok(true)
+}
+
 /// Clear window background (for static -> dynamic mode switch)
 #[tauri::command]
 pub fn clear_screen_background(app: AppHandle, state: tauri::State<SharedState>) {
@@ -293,7 +1094,75 @@ pub fn clear_screen_background(app: AppHandle, state: tauri::State<SharedState>)
         // Clear cached snapshot
         let mut s = state.lock().unwrap();
         s.cached_snapshot = None;
+        s.clear_display_snapshots();
+    }
+}
+
+/// Switch the selector between a frozen snapshot (static) and the live screen, returning
+/// whether it ended up frozen; freezing fails without a selector window or on capture error.
+/// Freezing also snapshots the other displays for use_cached_display, which repoints
+/// cached_snapshot and its origin/scale at another display; unfreezing restores the
+/// selector's own display as the crop origin.
+#[tauri::command]
+pub fn set_selector_frozen(app: AppHandle, frozen: bool) -> bool {
+    if frozen {
+        capture_screen_now(app.clone(), app.state(), Some(true));
+    } else {
+        clear_screen_background(app.clone(), app.state());
+        // The selector always covers the first display (see open_selector)
+        if let Some(screen) = Screen::all().ok().and_then(|s| s.into_iter().next()) {
+            let state = app.state::<SharedState>();
+            let mut s = state.lock().unwrap();
+            s.screen_x = screen.display_info.x;
+            s.screen_y = screen.display_info.y;
+            s.screen_scale = screen.display_info.scale_factor;
+        }
+    }
+    get_selector_frozen(app.state())
+}
+
+/// Whether the selector shows a frozen snapshot (`cached_snapshot` is populated)
+#[tauri::command]
+pub fn get_selector_frozen(state: tauri::State<SharedState>) -> bool {
+    state.lock().unwrap().cached_snapshot.is_some()
+}
+
+/// Recovery path ("start over"): stop any recording, timelapse or scroll capture, drop all
+/// transient capture data and close the capture windows, as on a fresh launch. Settings
+/// and the main window stay as they are.
+#[tauri::command]
+pub fn reset_state(app: AppHandle, state: tauri::State<SharedState>) {
+    log::info!("[reset_state] Resetting capture state");
+    // Stops the scroll listener and closes the scroll/recording overlays
+    crate::commands::discard_scroll_capture(app.clone(), app.state());
+    // Recording threads see the cleared flags and wind down with an empty buffer
+    state.lock().unwrap().reset_capture_data();
+    crate::shortcuts::unregister_stop_shortcuts(&app);
+    crate::tray::update_tray_icon(&app, false);
+
+    for (label, win) in app.webview_windows() {
+        let transient = matches!(
+            label.as_str(),
+            "selector" | "recording-overlay" | "scroll-overlay" | "review"
+        ) || label.starts_with("editor-");
+        if transient {
+            let _ = win.destroy();
+        }
     }
+
+    // Dock icon only while the main window is showing
+    let main_visible = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+    crate::windows::set_activation_policy(if main_visible { 0 } else { 1 });
+    let _ = app.emit("state-reset", ());
+}
+
+/// Activation policy in effect (0 = Regular, 1 = Accessory, -1 = not set yet)
+#[tauri::command]
+pub fn get_activation_policy() -> i64 {
+    crate::windows::current_activation_policy()
 }
 
 /// Activate the window under cursor so it can receive scroll events
@@ -310,20 +1179,20 @@ pub fn activate_window_under_cursor() -> bool {
 
 /// Internal function to open selector (called from shortcut handler)
 pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
-    println!("[DEBUG][open_selector_internal] 入口");
+    log::debug!("[open_selector_internal] 入口");
 
     // Check screen recording permission first (macOS only)
     #[cfg(target_os = "macos")]
     {
         if !permission::has_screen_recording_permission() {
-            println!("[DEBUG][open_selector_internal] 无屏幕录制权限，打开权限窗口");
-            let _ = open_permission_window(&app);
+            log::debug!("[open_selector_internal] 无屏幕录制权限");
+            crate::commands::handle_missing_permission(&app);
             return Ok(());
         }
     }
 
     if let Some(win) = app.get_webview_window("selector") {
-        println!("[DEBUG][open_selector_internal] selector 窗口已存在，跳过");
+        log::debug!("[open_selector_internal] selector 窗口已存在，跳过");
         let _ = win.show();
         let _ = win.set_focus();
         return Ok(());
@@ -340,6 +1209,7 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
     {
         let mut s = state.lock().unwrap();
         s.cached_snapshot = None;
+        s.clear_display_snapshots();
     }
 
     let should_hide = !has_frames
@@ -348,16 +1218,11 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
             Some(CaptureMode::Gif) | Some(CaptureMode::Video)
         );
     if should_hide {
-        if let Some(main_win) = app.get_webview_window("main") {
-            let _ = main_win.hide();
-            set_activation_policy(1);
-        }
+        hide_main_for_capture(&app);
     }
 
-    let screens = Screen::all().map_err(|e| e.to_string())?;
-    if screens.is_empty() {
-        return Err("No screens found".to_string());
-    }
+    // Displays can briefly vanish while waking from sleep
+    let screens = Screen::all_nonempty()?;
 
     let screen = &screens[0];
     let screen_x = screen.display_info.x;
@@ -373,8 +1238,12 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     let cg_image = {
         let start = std::time::Instant::now();
-        let img = native_screenshot::capture_cgimage();
-        println!("[DEBUG][open_selector_internal] 原生截屏 {}ms", start.elapsed().as_millis());
+        let img = if is_static_mode {
+            capture_static_cgimage()
+        } else {
+            native_screenshot::capture_cgimage_with_retry()
+        };
+        log::debug!("[open_selector_internal] 原生截屏 {}ms", start.elapsed().as_millis());
 
         // Cache RGBA for magnifier (no base64 encoding needed - use get_magnifier_pixels instead)
         if let Some(ref cg_img) = img {
@@ -382,7 +1251,7 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
                 let state = app.state::<SharedState>();
                 let mut s = state.lock().unwrap();
                 s.cached_snapshot = Some(rgba);
-                println!("[DEBUG] RGBA缓存完成，放大镜就绪");
+                log::debug!("[open_selector_internal] RGBA缓存完成，放大镜就绪");
             }
         }
 
@@ -392,6 +1261,18 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
     #[cfg(not(target_os = "macos"))]
     let cg_image: Option<()> = None;
 
+    // Linux fast path: cache a full-screen grab for magnifier / static-mode cropping
+    #[cfg(target_os = "linux")]
+    {
+        let start = std::time::Instant::now();
+        if let Some(rgba) = crate::linux_capture::capture_rgba(screen_x, screen_y) {
+            let state = app.state::<SharedState>();
+            let mut s = state.lock().unwrap();
+            s.cached_snapshot = Some(rgba);
+            log::debug!("[open_selector_internal] Linux 截屏缓存 {}ms", start.elapsed().as_millis());
+        }
+    }
+
     {
         let state = app.state::<SharedState>();
         let mut s = state.lock().unwrap();
@@ -402,6 +1283,7 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
 
     let win = WebviewWindowBuilder::new(&app, "selector", WebviewUrl::App("/selector.html".into()))
         .title("Select Region")
+        .initialization_script(&selector_style_script())
         .decorations(false)
         .always_on_top(true)
         .skip_taskbar(true)
@@ -412,14 +1294,12 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
         .build()
         .map_err(|e| e.to_string())?;
 
-    let physical_width = (width as f32 * scale) as u32;
-    let physical_height = (height as f32 * scale) as u32;
-    let physical_x = (screen_x as f32 * scale) as i32;
-    let physical_y = (screen_y as f32 * scale) as i32;
+    let display = Region { x: screen_x, y: screen_y, width, height };
+    let physical = logical_to_physical(&display, (0, 0), scale);
 
-    win.set_size(PhysicalSize::new(physical_width, physical_height))
+    win.set_size(PhysicalSize::new(physical.width, physical.height))
         .map_err(|e| e.to_string())?;
-    win.set_position(PhysicalPosition::new(physical_x, physical_y))
+    win.set_position(PhysicalPosition::new(physical.x, physical.y))
         .map_err(|e| e.to_string())?;
 
     #[cfg(target_os = "macos")]
@@ -441,7 +1321,7 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
                     let ns_window = webview.ns_window() as *mut objc::runtime::Object;
                     native_screenshot::set_window_background_cgimage_raw(ns_window, cg_ptr);
                 });
-                println!("[DEBUG][open_selector_internal] 设置背景 {}ms", start.elapsed().as_millis());
+                log::debug!("[open_selector_internal] 设置背景 {}ms", start.elapsed().as_millis());
             }
         }
     }
@@ -452,7 +1332,7 @@ pub fn open_selector_internal(app: AppHandle) -> Result<(), String> {
 /// Open devtools for current window
 #[tauri::command]
 pub fn open_devtools(window: tauri::WebviewWindow) {
-    println!("[devtools] Opening devtools for window: {}", window.label());
+    log::info!("[devtools] Opening devtools for window: {}", window.label());
     window.open_devtools();
 }
 
@@ -467,7 +1347,7 @@ pub fn set_selector_mouse_passthrough(app: AppHandle, enabled: bool) -> Result<(
     win.set_ignore_cursor_events(enabled)
         .map_err(|e| e.to_string())?;
 
-    println!(
+    log::debug!(
         "[set_selector_mouse_passthrough] passthrough={}",
         enabled
     );
@@ -503,7 +1383,7 @@ pub fn setup_scroll_capture_passthrough(
             std::thread::sleep(Duration::from_millis(50));
 
             let Some(win) = app_clone.get_webview_window("selector") else {
-                println!("[scroll_passthrough] Window closed, stopping monitor");
+                log::info!("[scroll_passthrough] Window closed, stopping monitor");
                 break;
             };
 
@@ -552,7 +1432,7 @@ pub fn setup_scroll_capture_passthrough(
         }
     });
 
-    println!(
+    log::info!(
         "[setup_scroll_capture_passthrough] Started mouse monitor, panel=({},{},{},{})",
         panel_x, panel_y, panel_width, panel_height
     );
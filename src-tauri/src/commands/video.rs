@@ -0,0 +1,196 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::ShellExt;
+
+use crate::state::{RecordedFrame, SharedState};
+use crate::types::{ExportProgress, SaveResult, VideoCodec, VideoExportOptions};
+
+/// ffmpeg shipped as a Tauri sidecar (`bundle.externalBin: binaries/ffmpeg`, installed per
+/// target triple by scripts/fetch-ffmpeg.cjs), resolved through the shell plugin. Converted
+/// to a std Command so export_video can stream frames into its stdin.
+fn ffmpeg_sidecar(app: &AppHandle) -> Option<Command> {
+    let sidecar = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| log::warn!("[export_video] ffmpeg sidecar unavailable: {}", e))
+        .ok()?;
+    let command = Command::from(sidecar);
+    Path::new(command.get_program()).is_file().then_some(command)
+}
+
+/// Whether export_video can run (the ffmpeg sidecar is present)
+#[tauri::command]
+pub fn has_video_encoder(app: AppHandle) -> bool {
+    ffmpeg_sidecar(&app).is_some()
+}
+
+/// ffmpeg arguments for raw `pix_fmt` frames of `width`x`height` on stdin at `input_fps`
+fn ffmpeg_args(
+    options: &VideoExportOptions,
+    pix_fmt: &str,
+    (width, height): (u32, u32),
+    input_fps: u32,
+    output: &str,
+) -> Vec<String> {
+    let (encoder, default_crf, tag) = match options.codec {
+        VideoCodec::H264 => ("libx264", 23, "avc1"),
+        VideoCodec::H265 => ("libx265", 28, "hvc1"), // hvc1 so QuickTime plays it
+    };
+    let crf = options.crf.unwrap_or(default_crf).min(51);
+    let scale = options.output_scale.unwrap_or(1.0).clamp(0.1, 1.0);
+    // yuv420p needs even dimensions
+    let filter = format!("scale=trunc(iw*{scale}/2)*2:trunc(ih*{scale}/2)*2");
+
+    let size = format!("{}x{}", width, height);
+    let input_fps = input_fps.max(1).to_string();
+    let crf = crf.to_string();
+
+    let mut args: Vec<String> = [
+        "-y", "-hide_banner", "-loglevel", "error",
+        "-f", "rawvideo", "-pix_fmt", pix_fmt, "-s", &size, "-framerate", &input_fps, "-i", "-",
+        "-vf", &filter, "-c:v", encoder, "-crf", &crf, "-pix_fmt", "yuv420p", "-tag:v", tag,
+        "-movflags", "+faststart",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    if let Some(fps) = options.fps.filter(|&fps| fps > 0) {
+        args.extend(["-r".to_string(), fps.to_string()]);
+    }
+    args.push(output.to_string());
+    args
+}
+
+/// Encode the recorded frames to an H.264/H.265 MP4 by piping them to the ffmpeg sidecar.
+/// Progress is reported via `export-progress`, the result via `export-complete`.
+#[tauri::command]
+pub fn export_video(
+    app: AppHandle,
+    state: tauri::State<SharedState>,
+    options: VideoExportOptions,
+) -> Result<(), String> {
+    let mut ffmpeg = ffmpeg_sidecar(&app)
+        .ok_or("ffmpeg sidecar not found; video export needs ffmpeg bundled with the app")?;
+
+    let (frames, recording_fps) = {
+        let s = state.lock().unwrap();
+        let total = s.frames.len();
+        let start = options.start_frame.unwrap_or(0).min(total);
+        let end = options.end_frame.unwrap_or(total).min(total);
+        if end <= start {
+            return Err("No frames to export".to_string());
+        }
+        (s.frames[start..end].to_vec(), s.recording_fps)
+    };
+
+    let output = match &options.output_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let dir = dirs::picture_dir()
+                .or_else(dirs::home_dir)
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("lovshot");
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+            dir.join(format!("recording_{}.mp4", timestamp))
+        }
+    };
+    let output = output.to_string_lossy().to_string();
+
+    // Frames share one layout: the format is fixed when a recording starts
+    let pix_fmt = match frames[0] {
+        RecordedFrame::Rgba(_) => "rgba",
+        RecordedFrame::Rgb(_) => "rgb24",
+    };
+    let dims = frames[0].dimensions();
+    let args = ffmpeg_args(&options, pix_fmt, dims, recording_fps, &output);
+    log::info!(
+        "[export_video] {} frames {}x{} @ {}fps, {:?} -> {}",
+        frames.len(),
+        dims.0,
+        dims.1,
+        recording_fps,
+        options.codec,
+        output
+    );
+
+    thread::spawn(move || {
+        let frame_count = frames.len();
+        let result = (|| -> Result<String, String> {
+            let mut child = ffmpeg
+                .args(&args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(|e| format!("Failed to start ffmpeg: {}", e))?;
+
+            // Drain stderr concurrently so a chatty ffmpeg can't block on a full pipe
+            let mut stderr = child.stderr.take().ok_or("ffmpeg stderr unavailable")?;
+            let stderr_reader = thread::spawn(move || {
+                let mut log = String::new();
+                let _ = stderr.read_to_string(&mut log);
+                log
+            });
+
+            let mut stdin = child.stdin.take().ok_or("ffmpeg stdin unavailable")?;
+            for (i, frame) in frames.iter().enumerate() {
+                if let Err(e) = stdin.write_all(frame.as_raw()) {
+                    log::info!("[export_video] ffmpeg closed its input: {}", e);
+                    break; // ffmpeg exited early; its status and log say why
+                }
+                let _ = app.emit(
+                    "export-progress",
+                    ExportProgress {
+                        current: i + 1,
+                        total: frame_count,
+                        stage: "encoding".to_string(),
+                    },
+                );
+            }
+            drop(stdin); // EOF lets ffmpeg finish the file
+
+            let status = child.wait().map_err(|e| e.to_string())?;
+            let log = stderr_reader.join().unwrap_or_default();
+            if !status.success() {
+                return Err(format!("ffmpeg failed ({}): {}", status, log.trim()));
+            }
+            Ok(output)
+        })();
+
+        match result {
+            Ok(path) => {
+                log::info!("[export_video] Done: {}", path);
+                let _ = app.emit(
+                    "export-complete",
+                    SaveResult {
+                        success: true,
+                        path: Some(path),
+                        error: None,
+                        frame_count: Some(frame_count),
+                        note: None,
+                    },
+                );
+            }
+            Err(e) => {
+                log::warn!("[export_video] Error: {}", e);
+                let _ = app.emit(
+                    "export-complete",
+                    SaveResult {
+                        success: false,
+                        path: None,
+                        error: Some(e),
+                        frame_count: None,
+                        note: None,
+                    },
+                );
+            }
+        }
+    });
+
+    Ok(())
+}
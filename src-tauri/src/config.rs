@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::types::{CaptureMode, ExportWatermark, Region};
+
 /// Shortcut configuration for a single shortcut binding
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ShortcutConfig {
@@ -63,6 +65,140 @@ fn default_watermark_position() -> WatermarkPosition {
     WatermarkPosition::BottomRight
 }
 
+/// What the recorder does once the frame buffer hits its cap
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingLimitPolicy {
+    #[default]
+    Stop,    // Stop recording and open the editor
+    DropFps, // Thin out the buffer and keep recording at half the fps
+}
+
+/// Pixel layout recorded frames are buffered in
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingFrameFormat {
+    #[default]
+    Rgba,
+    Rgb, // drops alpha (screen content is opaque), 25% less memory per frame
+}
+
+/// Minimum severity written to the log
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn to_filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Off => log::LevelFilter::Off,
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// File format a capture is saved in
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Apng,
+}
+
+impl CaptureFormat {
+    /// Still modes save images, recording modes save animations
+    pub fn supports(self, mode: CaptureMode) -> bool {
+        match mode {
+            CaptureMode::Image | CaptureMode::StaticImage | CaptureMode::Scroll => {
+                matches!(self, CaptureFormat::Png | CaptureFormat::Jpeg)
+            }
+            CaptureMode::Gif | CaptureMode::Video => {
+                matches!(self, CaptureFormat::Gif | CaptureFormat::Apng)
+            }
+        }
+    }
+}
+
+/// Format each capture mode saves in without asking
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DefaultFormats {
+    pub image: CaptureFormat,
+    pub static_image: CaptureFormat,
+    pub gif: CaptureFormat,
+    pub video: CaptureFormat,
+    pub scroll: CaptureFormat,
+}
+
+impl Default for DefaultFormats {
+    fn default() -> Self {
+        Self {
+            image: CaptureFormat::Png,
+            static_image: CaptureFormat::Png,
+            gif: CaptureFormat::Gif,
+            video: CaptureFormat::Gif,
+            scroll: CaptureFormat::Png,
+        }
+    }
+}
+
+impl DefaultFormats {
+    pub fn get(&self, mode: CaptureMode) -> CaptureFormat {
+        match mode {
+            CaptureMode::Image => self.image,
+            CaptureMode::StaticImage => self.static_image,
+            CaptureMode::Gif => self.gif,
+            CaptureMode::Video => self.video,
+            CaptureMode::Scroll => self.scroll,
+        }
+    }
+
+    pub fn set(&mut self, mode: CaptureMode, format: CaptureFormat) -> Result<(), String> {
+        if !format.supports(mode) {
+            return Err(format!("{:?} mode can't be saved as {:?}", mode, format));
+        }
+        let slot = match mode {
+            CaptureMode::Image => &mut self.image,
+            CaptureMode::StaticImage => &mut self.static_image,
+            CaptureMode::Gif => &mut self.gif,
+            CaptureMode::Video => &mut self.video,
+            CaptureMode::Scroll => &mut self.scroll,
+        };
+        *slot = format;
+        Ok(())
+    }
+}
+
+/// Color management applied to saved still captures
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorProfile {
+    #[default]
+    Untagged,      // raw display pixels, no color chunk
+    TagSrgb,       // raw pixels, PNG marked as sRGB
+    ConvertToSrgb, // wide-gamut display pixels converted to sRGB, PNG marked as sRGB
+}
+
+impl ColorProfile {
+    pub fn tags_srgb(self) -> bool {
+        self != ColorProfile::Untagged
+    }
+}
+
 /// Application configuration (v2 - supports multiple shortcuts per action)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -82,6 +218,153 @@ pub struct AppConfig {
     pub watermark_position: WatermarkPosition,
     #[serde(default = "default_show_caption_editor")]
     pub show_caption_editor: bool,
+    #[serde(default = "default_recording_max_memory_mb")]
+    pub recording_max_memory_mb: u32, // 0 = unlimited
+    #[serde(default)]
+    pub recording_max_frames: u32, // 0 = unlimited
+    #[serde(default)]
+    pub recording_limit_policy: RecordingLimitPolicy,
+    #[serde(default)]
+    pub recording_frame_format: RecordingFrameFormat,
+    #[serde(default)]
+    pub output_dir: Option<String>, // None = ~/Pictures/lovshot
+    #[serde(default = "default_filename_template")]
+    pub filename_template: String, // tokens: {date} {time} {counter} {mode}
+    #[serde(default)]
+    pub filename_counter: u64, // last {counter} handed out (0 = not started)
+    #[serde(default = "default_selector_dim_opacity")]
+    pub selector_dim_opacity: f32, // 0.0-1.0 dim outside the selection
+    #[serde(default = "default_selector_accent_color")]
+    pub selector_border_color: String, // CSS color
+    #[serde(default = "default_selector_accent_color")]
+    pub selector_guide_color: String, // CSS color for crosshair guidelines
+    #[serde(default = "default_restore_main_after_capture")]
+    pub restore_main_after_capture: bool, // false = stay in background after GIF/Video
+    #[serde(default = "default_accessory_during_capture")]
+    pub accessory_during_capture: bool, // false = keep dashboard + Dock icon during GIF/Video
+    #[serde(default)]
+    pub upload_endpoint: Option<String>, // HTTP upload URL; {filename} is substituted
+    #[serde(default)]
+    pub upload_token: Option<String>, // sent as "Authorization: Bearer <token>"
+    #[serde(default = "default_upload_method")]
+    pub upload_method: String, // "post" (raw body) or "put" (S3-style presigned URL)
+    #[serde(default)]
+    pub ocr_languages: Vec<String>, // BCP-47 codes for extract_text, empty = automatic
+    #[serde(default = "default_min_region_size")]
+    pub min_region_width: u32, // set_region rejects smaller selections (logical px)
+    #[serde(default = "default_min_region_size")]
+    pub min_region_height: u32,
+    #[serde(default = "default_region_history_size")]
+    pub region_history_size: usize, // regions kept per display for select_region_from_history
+    #[serde(default)]
+    pub local_api_enabled: bool, // opt-in HTTP capture API on 127.0.0.1
+    #[serde(default = "default_local_api_port")]
+    pub local_api_port: u16,
+    #[serde(default)]
+    pub local_api_token: String, // bearer token the local API requires; generated when enabled
+    #[serde(default)]
+    pub capture_sound_enabled: bool, // shutter sound after a still capture
+    #[serde(default)]
+    pub capture_flash_enabled: bool, // white flash over the captured region
+    #[serde(default)]
+    pub default_formats: DefaultFormats, // per capture mode, used by quick_save / export
+    #[serde(default)]
+    pub color_profile: ColorProfile,
+    #[serde(default = "default_max_export_dimension")]
+    pub max_export_width: u32, // scroll exports beyond these warn or split (px)
+    #[serde(default = "default_max_export_dimension")]
+    pub max_export_height: u32,
+    #[serde(default = "default_snapshot_cache_max_displays")]
+    pub snapshot_cache_max_displays: u32, // displays frozen at once by capture_screen_now
+    #[serde(default)]
+    pub auto_request_permission: bool, // system prompt first; permission window if not granted
+    #[serde(default = "default_exclude_own_windows")]
+    pub exclude_own_windows: bool, // leave lovshot windows out of static captures (macOS)
+    #[serde(default = "default_capture_retry_attempts")]
+    pub capture_retry_attempts: u32, // native screen grab tries before giving up (macOS)
+    #[serde(default = "default_capture_retry_backoff_ms")]
+    pub capture_retry_backoff_ms: u64, // delay before the first retry, doubles each time
+    #[serde(default = "default_capture_retry_timeout_ms")]
+    pub capture_retry_timeout_ms: u64, // total time retries may take
+    #[serde(default)]
+    pub log_level: LogLevel, // overridden by the LOVSHOT_LOG env var
+    #[serde(default)]
+    pub log_to_file: bool, // also append to lovshot.log in the config dir
+    #[serde(default)]
+    pub capture_content_area_only: bool, // presets / whole-screen captures skip menu bar + Dock
+    #[serde(default = "default_recording_shortcut_toggle")]
+    pub recording_shortcut_toggle: bool, // any shortcut stops a recording, not just stop keys
+    #[serde(default)]
+    pub export_watermark: Option<ExportWatermark>, // logo/text stamped on saved files
+}
+
+fn default_region_history_size() -> usize {
+    5
+}
+
+fn default_snapshot_cache_max_displays() -> u32 {
+    2
+}
+
+/// Some viewers and encoders refuse images above 65535 px on either side
+fn default_max_export_dimension() -> u32 {
+    65500
+}
+
+fn default_local_api_port() -> u16 {
+    17321
+}
+
+fn default_min_region_size() -> u32 {
+    8
+}
+
+fn default_upload_method() -> String {
+    "post".to_string()
+}
+
+fn default_capture_retry_attempts() -> u32 {
+    3
+}
+
+fn default_capture_retry_backoff_ms() -> u64 {
+    50
+}
+
+fn default_capture_retry_timeout_ms() -> u64 {
+    1000
+}
+
+fn default_exclude_own_windows() -> bool {
+    true
+}
+
+fn default_recording_shortcut_toggle() -> bool {
+    true
+}
+
+fn default_restore_main_after_capture() -> bool {
+    true
+}
+
+fn default_accessory_during_capture() -> bool {
+    true
+}
+
+fn default_selector_dim_opacity() -> f32 {
+    0.4
+}
+
+fn default_selector_accent_color() -> String {
+    "#CC785C".to_string()
+}
+
+fn default_filename_template() -> String {
+    "{mode}_{date}_{time}".to_string()
+}
+
+fn default_recording_max_memory_mb() -> u32 {
+    2048
 }
 
 fn default_show_caption_editor() -> bool {
@@ -128,6 +411,45 @@ impl From<OldAppConfig> for AppConfig {
             image_export_format: default_image_export_format(),
             watermark_position: default_watermark_position(),
             show_caption_editor: default_show_caption_editor(),
+            recording_max_memory_mb: default_recording_max_memory_mb(),
+            recording_max_frames: 0,
+            recording_limit_policy: RecordingLimitPolicy::default(),
+            recording_frame_format: RecordingFrameFormat::default(),
+            output_dir: None,
+            filename_template: default_filename_template(),
+            filename_counter: 0,
+            selector_dim_opacity: default_selector_dim_opacity(),
+            selector_border_color: default_selector_accent_color(),
+            selector_guide_color: default_selector_accent_color(),
+            restore_main_after_capture: default_restore_main_after_capture(),
+            accessory_during_capture: default_accessory_during_capture(),
+            upload_endpoint: None,
+            upload_token: None,
+            upload_method: default_upload_method(),
+            ocr_languages: Vec::new(),
+            min_region_width: default_min_region_size(),
+            min_region_height: default_min_region_size(),
+            region_history_size: default_region_history_size(),
+            local_api_enabled: false,
+            local_api_port: default_local_api_port(),
+            local_api_token: String::new(),
+            capture_sound_enabled: false,
+            capture_flash_enabled: false,
+            default_formats: DefaultFormats::default(),
+            color_profile: ColorProfile::default(),
+            max_export_width: default_max_export_dimension(),
+            max_export_height: default_max_export_dimension(),
+            snapshot_cache_max_displays: default_snapshot_cache_max_displays(),
+            auto_request_permission: false,
+            exclude_own_windows: default_exclude_own_windows(),
+            capture_retry_attempts: default_capture_retry_attempts(),
+            capture_retry_backoff_ms: default_capture_retry_backoff_ms(),
+            capture_retry_timeout_ms: default_capture_retry_timeout_ms(),
+            log_level: LogLevel::default(),
+            log_to_file: false,
+            capture_content_area_only: false,
+            recording_shortcut_toggle: default_recording_shortcut_toggle(),
+            export_watermark: None,
         }
     }
 }
@@ -207,6 +529,26 @@ impl Default for AppConfig {
             }],
         );
 
+        // Recapture with the last mode + region, skipping the selector
+        shortcuts.insert(
+            "repeat_capture".to_string(),
+            vec![ShortcutConfig {
+                modifiers: vec!["Alt".to_string()],
+                key: "R".to_string(),
+                enabled: true,
+            }],
+        );
+
+        // Pause/resume scroll capture, or start one on the current region
+        shortcuts.insert(
+            "toggle_scroll_listener".to_string(),
+            vec![ShortcutConfig {
+                modifiers: vec!["Shift".to_string(), "Alt".to_string()],
+                key: "S".to_string(),
+                enabled: true,
+            }],
+        );
+
         shortcuts.insert(
             "show_main".to_string(),
             vec![ShortcutConfig {
@@ -226,6 +568,45 @@ impl Default for AppConfig {
             image_export_format: default_image_export_format(),
             watermark_position: default_watermark_position(),
             show_caption_editor: default_show_caption_editor(),
+            recording_max_memory_mb: default_recording_max_memory_mb(),
+            recording_max_frames: 0,
+            recording_limit_policy: RecordingLimitPolicy::default(),
+            recording_frame_format: RecordingFrameFormat::default(),
+            output_dir: None,
+            filename_template: default_filename_template(),
+            filename_counter: 0,
+            selector_dim_opacity: default_selector_dim_opacity(),
+            selector_border_color: default_selector_accent_color(),
+            selector_guide_color: default_selector_accent_color(),
+            restore_main_after_capture: default_restore_main_after_capture(),
+            accessory_during_capture: default_accessory_during_capture(),
+            upload_endpoint: None,
+            upload_token: None,
+            upload_method: default_upload_method(),
+            ocr_languages: Vec::new(),
+            min_region_width: default_min_region_size(),
+            min_region_height: default_min_region_size(),
+            region_history_size: default_region_history_size(),
+            local_api_enabled: false,
+            local_api_port: default_local_api_port(),
+            local_api_token: String::new(),
+            capture_sound_enabled: false,
+            capture_flash_enabled: false,
+            default_formats: DefaultFormats::default(),
+            color_profile: ColorProfile::default(),
+            max_export_width: default_max_export_dimension(),
+            max_export_height: default_max_export_dimension(),
+            snapshot_cache_max_displays: default_snapshot_cache_max_displays(),
+            auto_request_permission: false,
+            exclude_own_windows: default_exclude_own_windows(),
+            capture_retry_attempts: default_capture_retry_attempts(),
+            capture_retry_backoff_ms: default_capture_retry_backoff_ms(),
+            capture_retry_timeout_ms: default_capture_retry_timeout_ms(),
+            log_level: LogLevel::default(),
+            log_to_file: false,
+            capture_content_area_only: false,
+            recording_shortcut_toggle: default_recording_shortcut_toggle(),
+            export_watermark: None,
         }
     }
 }
@@ -268,7 +649,7 @@ fn ensure_stop_recording_defaults(config: &mut AppConfig, default_config: &AppCo
                 sc.modifiers == default_sc.modifiers && sc.key == default_sc.key
             });
             if !exists {
-                println!(
+                log::info!(
                     "[config] Adding missing stop_recording shortcut: {}",
                     default_sc.to_shortcut_string()
                 );
@@ -280,6 +661,34 @@ fn ensure_stop_recording_defaults(config: &mut AppConfig, default_config: &AppCo
     updated
 }
 
+/// Add default shortcuts for actions the config doesn't know yet. Defaults whose combo is
+/// already taken by another action are left out (the action is still added, so this
+/// doesn't repeat on every load); returns whether anything was added.
+fn add_missing_shortcuts(config: &mut AppConfig, default_config: &AppConfig) -> bool {
+    let mut updated = false;
+    for (key, defaults) in &default_config.shortcuts {
+        if config.shortcuts.contains_key(key) {
+            continue;
+        }
+        let mut added = Vec::new();
+        for sc in defaults {
+            match conflicting_action(&config.shortcuts, key, sc) {
+                Some(other) if sc.enabled => log::warn!(
+                    "[config] Skipping default {} for {}: used by {}",
+                    sc.to_shortcut_string(),
+                    key,
+                    other
+                ),
+                _ => added.push(sc.clone()),
+            }
+        }
+        log::info!("[config] Adding missing shortcut: {}", key);
+        config.shortcuts.insert(key.clone(), added);
+        updated = true;
+    }
+    updated
+}
+
 /// Load configuration from file, or return default if not exists
 /// Also ensures any missing shortcuts from default config are added
 /// Handles migration from v1 (single shortcut) to v2 (multiple shortcuts)
@@ -292,39 +701,25 @@ pub fn load_config() -> AppConfig {
             Ok(content) => {
                 // Check if old format and migrate
                 if is_old_format(&content) {
-                    println!("[config] Detected old format, migrating to v2...");
+                    log::info!("[config] Detected old format, migrating to v2...");
                     match serde_json::from_str::<OldAppConfig>(&content) {
                         Ok(old_config) => {
                             let mut config: AppConfig = old_config.into();
-                            // Add any missing shortcuts
-                            for (key, value) in &default_config.shortcuts {
-                                if !config.shortcuts.contains_key(key) {
-                                    println!("[config] Adding missing shortcut: {}", key);
-                                    config.shortcuts.insert(key.clone(), value.clone());
-                                }
-                            }
+                            add_missing_shortcuts(&mut config, &default_config);
                             // Ensure stop_recording has all defaults
                             ensure_stop_recording_defaults(&mut config, &default_config);
                             let _ = save_config(&config);
                             return config;
                         }
                         Err(e) => {
-                            eprintln!("[config] Failed to parse old config: {}", e);
+                            log::error!("[config] Failed to parse old config: {}", e);
                         }
                     }
                 } else {
                     // Try parsing as new format
                     match serde_json::from_str::<AppConfig>(&content) {
                         Ok(mut config) => {
-                            // Add any missing shortcuts from default config
-                            let mut updated = false;
-                            for (key, value) in &default_config.shortcuts {
-                                if !config.shortcuts.contains_key(key) {
-                                    println!("[config] Adding missing shortcut: {}", key);
-                                    config.shortcuts.insert(key.clone(), value.clone());
-                                    updated = true;
-                                }
-                            }
+                            let mut updated = add_missing_shortcuts(&mut config, &default_config);
                             // Ensure stop_recording has all defaults
                             if ensure_stop_recording_defaults(&mut config, &default_config) {
                                 updated = true;
@@ -335,13 +730,13 @@ pub fn load_config() -> AppConfig {
                             return config;
                         }
                         Err(e) => {
-                            eprintln!("[config] Failed to parse config: {}", e);
+                            log::error!("[config] Failed to parse config: {}", e);
                         }
                     }
                 }
             }
             Err(e) => {
-                eprintln!("[config] Failed to read config file: {}", e);
+                log::error!("[config] Failed to read config file: {}", e);
             }
         }
     }
@@ -363,13 +758,53 @@ pub fn save_config(config: &AppConfig) -> Result<(), String> {
     let content = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
     fs::write(&path, content).map_err(|e| e.to_string())?;
 
-    println!("[config] Saved to {:?}", path);
+    log::info!("[config] Saved to {:?}", path);
+    Ok(())
+}
+
+/// Same key combo, ignoring modifier order and letter case
+fn same_combo(a: &ShortcutConfig, b: &ShortcutConfig) -> bool {
+    let normalize = |s: &ShortcutConfig| {
+        let mut mods: Vec<String> = s.modifiers.iter().map(|m| m.to_lowercase()).collect();
+        mods.sort();
+        (mods, s.key.to_lowercase())
+    };
+    normalize(a) == normalize(b)
+}
+
+/// Another action whose enabled shortcuts already use `shortcut`'s combo. A global
+/// shortcut can only be registered once, so one combo can't trigger two actions.
+/// stop_recording / stop_scroll are registered only while their capture runs and may share.
+fn conflicting_action(
+    shortcuts: &HashMap<String, Vec<ShortcutConfig>>,
+    action: &str,
+    shortcut: &ShortcutConfig,
+) -> Option<String> {
+    let dynamic = |a: &str| a == "stop_recording" || a == "stop_scroll";
+    shortcuts
+        .iter()
+        .filter(|(other, _)| other.as_str() != action && !(dynamic(other) && dynamic(action)))
+        .find(|(_, list)| list.iter().any(|s| s.enabled && same_combo(s, shortcut)))
+        .map(|(other, _)| other.clone())
+}
+
+fn check_conflicts(
+    config: &AppConfig,
+    action: &str,
+    shortcuts: &[ShortcutConfig],
+) -> Result<(), String> {
+    for sc in shortcuts.iter().filter(|s| s.enabled) {
+        if let Some(other) = conflicting_action(&config.shortcuts, action, sc) {
+            return Err(format!("{} is already used by {}", sc.to_shortcut_string(), other));
+        }
+    }
     Ok(())
 }
 
 /// Update shortcuts for an action (replaces all shortcuts for that action)
 pub fn update_shortcuts(action: &str, shortcuts: Vec<ShortcutConfig>) -> Result<AppConfig, String> {
     let mut config = load_config();
+    check_conflicts(&config, action, &shortcuts)?;
     config.shortcuts.insert(action.to_string(), shortcuts);
     save_config(&config)?;
     Ok(config)
@@ -378,6 +813,7 @@ pub fn update_shortcuts(action: &str, shortcuts: Vec<ShortcutConfig>) -> Result<
 /// Add a shortcut to an action
 pub fn add_shortcut(action: &str, shortcut: ShortcutConfig) -> Result<AppConfig, String> {
     let mut config = load_config();
+    check_conflicts(&config, action, std::slice::from_ref(&shortcut))?;
     let shortcuts = config.shortcuts.entry(action.to_string()).or_insert_with(Vec::new);
     // Avoid duplicates
     if !shortcuts.iter().any(|s| s.modifiers == shortcut.modifiers && s.key == shortcut.key) {
@@ -387,6 +823,34 @@ pub fn add_shortcut(action: &str, shortcut: ShortcutConfig) -> Result<AppConfig,
     Ok(config)
 }
 
+/// Flip `enabled` on every shortcut of an action, keeping its keys.
+/// Returns false if the action has no shortcuts.
+fn set_action_enabled(
+    shortcuts: &mut HashMap<String, Vec<ShortcutConfig>>,
+    action: &str,
+    enabled: bool,
+) -> bool {
+    match shortcuts.get_mut(action) {
+        Some(list) if !list.is_empty() => {
+            for s in list.iter_mut() {
+                s.enabled = enabled;
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Enable or disable all shortcuts of an action without deleting them
+pub fn set_shortcut_enabled(action: &str, enabled: bool) -> Result<AppConfig, String> {
+    let mut config = load_config();
+    if !set_action_enabled(&mut config.shortcuts, action, enabled) {
+        return Err(format!("No shortcuts configured for {}", action));
+    }
+    save_config(&config)?;
+    Ok(config)
+}
+
 /// Remove a shortcut from an action by index
 pub fn remove_shortcut(action: &str, index: usize) -> Result<AppConfig, String> {
     let mut config = load_config();
@@ -433,3 +897,193 @@ pub fn count_screenshots() -> u64 {
     // Return current count + 1 (for the new screenshot being saved)
     count_images_recursive(&output_dir) + 1
 }
+
+// ============ Last Region ============
+
+/// Geometry of the display a region was confirmed on
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DisplayKey {
+    pub id: u32,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale: f32,
+}
+
+/// Last confirmed capture region, remembered per display
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LastRegion {
+    pub region: Region,
+    pub display: DisplayKey,
+}
+
+/// Stored next to config.json so editing settings never clobbers it
+fn get_last_region_path() -> PathBuf {
+    get_config_path().with_file_name("last_region.json")
+}
+
+/// Load remembered regions keyed by display id (empty if missing or unreadable)
+pub fn load_last_regions() -> HashMap<u32, LastRegion> {
+    fs::read_to_string(get_last_region_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Remember `last` for its display, replacing any previous entry
+pub fn save_last_region(last: LastRegion) -> Result<(), String> {
+    let path = get_last_region_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut all = load_last_regions();
+    all.insert(last.display.id, last);
+    let content = serde_json::to_string_pretty(&all).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+// ============ Output Location ============
+
+/// Directory captures are saved to (configured `output_dir`, or ~/Pictures/lovshot)
+pub fn get_output_dir(config: &AppConfig) -> PathBuf {
+    match config.output_dir.as_deref() {
+        Some(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => dirs::picture_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("lovshot"),
+    }
+}
+
+/// Short name used for the {mode} filename token
+pub fn mode_file_label(mode: CaptureMode) -> &'static str {
+    match mode {
+        CaptureMode::Image | CaptureMode::StaticImage => "screenshot",
+        CaptureMode::Gif => "gif",
+        CaptureMode::Video => "video",
+        CaptureMode::Scroll => "scroll",
+    }
+}
+
+/// Next `{counter}` value for `template`, or 0 when it doesn't use the token. Persisted and
+/// only ever increasing, so deleting captures never makes a number come round again; the
+/// first one continues after the captures already in the output folder.
+pub fn next_filename_counter(template: &str) -> u64 {
+    if !template.contains("{counter}") {
+        return 0;
+    }
+    let mut cfg = load_config();
+    cfg.filename_counter = match cfg.filename_counter {
+        0 => count_screenshots(),
+        n => n + 1,
+    };
+    if let Err(e) = save_config(&cfg) {
+        log::warn!("[config] Failed to persist filename counter: {}", e);
+    }
+    cfg.filename_counter
+}
+
+/// Expand a filename template (without extension)
+/// Supported tokens: {date} (YYYYMMDD), {time} (HHMMSS), {counter}, {mode}
+pub fn render_filename_template(
+    template: &str,
+    mode: CaptureMode,
+    counter: u64,
+    now: &chrono::DateTime<chrono::Local>,
+) -> String {
+    let rendered = template
+        .replace("{date}", &now.format("%Y%m%d").to_string())
+        .replace("{time}", &now.format("%H%M%S").to_string())
+        .replace("{counter}", &counter.to_string())
+        .replace("{mode}", mode_file_label(mode));
+
+    // Path separators would escape the output directory
+    let sanitized: String = rendered
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':') { '-' } else { c })
+        .collect();
+    let trimmed = sanitized.trim();
+    if trimmed.is_empty() {
+        format!("lovshot_{}", now.format("%Y%m%d_%H%M%S"))
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_filename_template() {
+        let now = chrono::Local.with_ymd_and_hms(2024, 3, 5, 9, 7, 1).unwrap();
+        let name = render_filename_template("lovshot-{date}-{time}-{mode}-{counter}", CaptureMode::Gif, 42, &now);
+        assert_eq!(name, "lovshot-20240305-090701-gif-42");
+    }
+
+    #[test]
+    fn test_set_action_enabled_keeps_keys() {
+        let mut shortcuts = AppConfig::default().shortcuts;
+        let original = shortcuts["gif"][0].to_shortcut_string();
+
+        assert!(set_action_enabled(&mut shortcuts, "gif", false));
+        assert!(shortcuts["gif"].iter().all(|s| !s.enabled));
+        assert!(set_action_enabled(&mut shortcuts, "gif", true));
+        assert!(shortcuts["gif"][0].enabled);
+        assert_eq!(shortcuts["gif"][0].to_shortcut_string(), original);
+
+        assert!(!set_action_enabled(&mut shortcuts, "no_such_action", false));
+    }
+
+    #[test]
+    fn test_conflicting_action_across_actions() {
+        let shortcuts = AppConfig::default().shortcuts;
+        let gif = shortcuts["gif"][0].clone();
+        assert_eq!(conflicting_action(&shortcuts, "screenshot", &gif).as_deref(), Some("gif"));
+        // Re-binding an action's own combo, or sharing between the stop actions, is fine
+        assert_eq!(conflicting_action(&shortcuts, "gif", &gif), None);
+        let esc = ShortcutConfig::from_shortcut_string("Escape").unwrap();
+        assert_eq!(conflicting_action(&shortcuts, "stop_scroll", &esc), None);
+        // Modifier order and case don't matter
+        let mut reordered = gif.clone();
+        reordered.modifiers.reverse();
+        reordered.key = reordered.key.to_lowercase();
+        assert!(same_combo(&gif, &reordered));
+    }
+
+    #[test]
+    fn test_add_missing_shortcuts_skips_conflicts() {
+        let defaults = AppConfig::default();
+        let mut config = AppConfig::default();
+        config.shortcuts.remove("show_main");
+        config.shortcuts.remove("toggle_scroll_listener");
+        let taken = ShortcutConfig::from_shortcut_string("Alt+O").unwrap();
+        config.shortcuts.insert("screenshot".to_string(), vec![taken]);
+
+        assert!(add_missing_shortcuts(&mut config, &defaults));
+        assert!(config.shortcuts["show_main"].is_empty());
+        let scroll = "toggle_scroll_listener";
+        assert_eq!(config.shortcuts[scroll], defaults.shortcuts[scroll]);
+        assert!(!add_missing_shortcuts(&mut config, &defaults));
+    }
+
+    #[test]
+    fn test_default_formats_validate_mode() {
+        let mut formats = DefaultFormats::default();
+        assert!(formats.set(CaptureMode::Video, CaptureFormat::Png).is_err());
+        assert!(formats.set(CaptureMode::Image, CaptureFormat::Gif).is_err());
+        assert!(formats.set(CaptureMode::Scroll, CaptureFormat::Jpeg).is_ok());
+        assert_eq!(formats.get(CaptureMode::Scroll), CaptureFormat::Jpeg);
+        assert_eq!(formats.get(CaptureMode::Gif), CaptureFormat::Gif);
+    }
+
+    #[test]
+    fn test_render_filename_template_strips_separators() {
+        let now = chrono::Local.with_ymd_and_hms(2024, 3, 5, 9, 7, 1).unwrap();
+        let name = render_filename_template("../{mode}", CaptureMode::Image, 1, &now);
+        assert_eq!(name, "..-screenshot");
+    }
+}
@@ -0,0 +1,130 @@
+//! Display configuration change detection
+//!
+//! Unplugging a monitor or changing resolution leaves `screen_x/screen_y/screen_scale`
+//! and the cached snapshot pointing at the old layout. On change we drop the cached
+//! snapshot, close an open selector and emit `displays-changed`; the next capture
+//! re-reads `Screen::all()`. macOS uses CGDisplayRegisterReconfigurationCallback,
+//! other platforms poll the display list.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::state::SharedState;
+
+/// A change is already scheduled (macOS fires the callback once per display)
+static CHANGE_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Coalesce a burst of notifications into one invalidation
+fn schedule_change(app: AppHandle) {
+    if CHANGE_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(300));
+        CHANGE_PENDING.store(false, Ordering::SeqCst);
+        handle_change(&app);
+    });
+}
+
+fn handle_change(app: &AppHandle) {
+    log::info!("[display_watch] Display configuration changed");
+    if let Some(state) = app.try_state::<SharedState>() {
+        let mut s = state.lock().unwrap();
+        s.cached_snapshot = None;
+        s.clear_display_snapshots();
+        s.screen_snapshot = None;
+        s.magnifier_snapshot = None;
+    }
+    if let Some(win) = app.get_webview_window("selector") {
+        let _ = win.close();
+    }
+    let _ = app.emit("displays-changed", crate::commands::get_screens());
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::c_void;
+    use std::sync::OnceLock;
+
+    use tauri::AppHandle;
+
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+
+    /// kCGDisplayBeginConfigurationFlag: sent before the change is applied
+    const BEGIN_CONFIGURATION_FLAG: u32 = 1;
+
+    type ReconfigurationCallback = extern "C" fn(display: u32, flags: u32, user_info: *mut c_void);
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGDisplayRegisterReconfigurationCallback(
+            callback: ReconfigurationCallback,
+            user_info: *mut c_void,
+        ) -> i32;
+    }
+
+    extern "C" fn on_reconfigure(_display: u32, flags: u32, _user_info: *mut c_void) {
+        if flags & BEGIN_CONFIGURATION_FLAG != 0 {
+            return; // wait for the matching "done" notification
+        }
+        if let Some(app) = APP.get() {
+            super::schedule_change(app.clone());
+        }
+    }
+
+    pub fn install(app: AppHandle) {
+        if APP.set(app).is_err() {
+            return; // already installed
+        }
+        let err = unsafe {
+            CGDisplayRegisterReconfigurationCallback(on_reconfigure, std::ptr::null_mut())
+        };
+        if err != 0 {
+            log::warn!("[display_watch] Failed to register callback: {}", err);
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use std::thread;
+    use std::time::Duration;
+
+    use tauri::AppHandle;
+
+    use crate::capture::Screen;
+
+    /// Geometry that matters for capture placement
+    fn layout() -> Vec<(u32, i32, i32, u32, u32, u32)> {
+        Screen::all()
+            .unwrap_or_default()
+            .iter()
+            .map(|s| {
+                let d = &s.display_info;
+                (d.id, d.x, d.y, d.width, d.height, (d.scale_factor * 100.0) as u32)
+            })
+            .collect()
+    }
+
+    pub fn install(app: AppHandle) {
+        thread::spawn(move || {
+            let mut last = layout();
+            loop {
+                thread::sleep(Duration::from_secs(2));
+                let current = layout();
+                if current != last {
+                    last = current;
+                    super::schedule_change(app.clone());
+                }
+            }
+        });
+    }
+}
+
+/// Start watching for display changes (call once at startup)
+pub fn install(app: AppHandle) {
+    platform::install(app);
+}
@@ -181,6 +181,29 @@ pub fn detect_scroll_delta_fft(
     best_offset
 }
 
+/// Pick a matching downscale factor from frame width (4K frames are matched at half size)
+pub fn auto_match_downscale(width: u32) -> u32 {
+    if width > 2500 {
+        2
+    } else {
+        1
+    }
+}
+
+/// Shrink a frame by an integer factor for faster matching
+pub fn downscale_for_matching(img: &RgbaImage, factor: u32) -> RgbaImage {
+    if factor <= 1 {
+        return img.clone();
+    }
+    let (w, h) = img.dimensions();
+    image::imageops::resize(
+        img,
+        (w / factor).max(1),
+        (h / factor).max(1),
+        image::imageops::FilterType::Triangle,
+    )
+}
+
 /// Convert RGBA image to grayscale (single channel f32)
 fn to_grayscale(img: &RgbaImage) -> Vec<f32> {
     let (w, h) = img.dimensions();
@@ -0,0 +1,108 @@
+//! Pixel statistics for captures
+//!
+//! Per-channel 256-bucket histograms plus mean / min / max, for checking exposure or
+//! verifying that an area is a solid color.
+
+use image::RgbaImage;
+
+use crate::annotate::source_image;
+use crate::state::SharedState;
+use crate::types::{CaptureHistogram, ChannelHistogram, ImageSource, Region};
+
+fn channel(buckets: [u64; 256], total: u64) -> ChannelHistogram {
+    let sum: u64 = buckets.iter().enumerate().map(|(v, &n)| v as u64 * n).sum();
+    let min = buckets.iter().position(|&n| n > 0).unwrap_or(0) as u8;
+    let max = buckets.iter().rposition(|&n| n > 0).unwrap_or(0) as u8;
+    ChannelHistogram {
+        buckets: buckets.to_vec(),
+        mean: if total > 0 { sum as f64 / total as f64 } else { 0.0 },
+        min,
+        max,
+    }
+}
+
+/// Histogram of `img`, or of `region` (image px, intersected with the image bounds)
+pub fn histogram(img: &RgbaImage, region: Option<&Region>) -> Result<CaptureHistogram, String> {
+    let (img_w, img_h) = img.dimensions();
+    let (x, y, w, h) = match region {
+        Some(r) => {
+            let x0 = (r.x as i64).max(0);
+            let y0 = (r.y as i64).max(0);
+            let x1 = (r.x as i64 + r.width as i64).min(img_w as i64);
+            let y1 = (r.y as i64 + r.height as i64).min(img_h as i64);
+            if r.width == 0 || r.height == 0 {
+                return Err("Empty histogram region".to_string());
+            }
+            if x1 <= x0 || y1 <= y0 {
+                return Err("Histogram region outside image".to_string());
+            }
+            (x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32)
+        }
+        None => (0, 0, img_w, img_h),
+    };
+    if w == 0 || h == 0 {
+        return Err("Empty histogram region".to_string());
+    }
+
+    let mut buckets = [[0u64; 256]; 4];
+    for row in y..y + h {
+        for col in x..x + w {
+            let px = img.get_pixel(col, row);
+            for (c, counts) in buckets.iter_mut().enumerate() {
+                counts[px[c] as usize] += 1;
+            }
+        }
+    }
+
+    let total = w as u64 * h as u64;
+    let [red, green, blue, alpha] = buckets.map(|b| channel(b, total));
+    Ok(CaptureHistogram { width: w, height: h, red, green, blue, alpha })
+}
+
+/// Per-channel histograms and mean / min / max of the selected image or a sub-region of it
+#[tauri::command]
+pub fn capture_histogram(
+    state: tauri::State<SharedState>,
+    source: Option<ImageSource>,
+    region: Option<Region>,
+) -> Result<CaptureHistogram, String> {
+    let mut s = state.lock().unwrap();
+    let img = source_image(&mut s, source.unwrap_or_default()).ok_or("No image to measure")?;
+    let result = histogram(img, region.as_ref())?;
+    log::info!(
+        "[capture_histogram] {}x{} mean rgb=({:.1}, {:.1}, {:.1})",
+        result.width, result.height, result.red.mean, result.green.mean, result.blue.mean
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_histogram_of_sub_region() {
+        let mut img = RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 255]));
+        img.put_pixel(3, 3, Rgba([250, 20, 30, 255]));
+
+        let region = Region { x: 2, y: 2, width: 10, height: 10 };
+        let h = histogram(&img, Some(&region)).unwrap();
+        assert_eq!((h.width, h.height), (2, 2));
+        assert_eq!(h.red.buckets[10], 3);
+        assert_eq!(h.red.buckets[250], 1);
+        assert_eq!((h.red.min, h.red.max), (10, 250));
+        assert!((h.red.mean - 70.0).abs() < 1e-9);
+        assert_eq!((h.green.min, h.green.max), (20, 20));
+        assert_eq!(h.alpha.buckets[255], 4);
+
+        // A negative origin only keeps the part that overlaps the image
+        let partial = Region { x: -2, y: -2, width: 3, height: 3 };
+        assert_eq!(histogram(&img, Some(&partial)).unwrap().red.buckets[10], 1);
+
+        let outside = Region { x: 4, y: 0, width: 1, height: 1 };
+        assert!(histogram(&img, Some(&outside)).is_err());
+        let before = Region { x: -3, y: 0, width: 2, height: 2 };
+        assert!(histogram(&img, Some(&before)).is_err());
+    }
+}
@@ -13,14 +13,27 @@ mod native_screenshot;
 mod scroll_event;
 #[cfg(target_os = "macos")]
 mod window_detect;
+#[cfg(target_os = "linux")]
+mod linux_capture;
 
+mod annotate;
 mod capture;
+mod capture_feedback;
+mod color;
 mod commands;
 mod config;
+mod display_watch;
 mod fft_match;
+mod histogram;
 mod row_hash;
+mod local_api;
+mod logging;
+mod ocr;
 mod permission;
+mod schedule;
 mod share_compose;
+mod ssim;
+mod upload;
 mod shortcuts;
 mod state;
 mod tray;
@@ -28,7 +41,7 @@ mod types;
 mod windows;
 
 use commands::open_selector_internal;
-use shortcuts::{get_action_for_shortcut, is_show_main_shortcut, is_stop_recording_shortcut, register_shortcuts_from_config, unregister_stop_shortcuts, unregister_stop_scroll_shortcuts};
+use shortcuts::{get_action_for_shortcut, is_repeat_capture_shortcut, is_show_main_shortcut, is_stop_recording_shortcut, is_toggle_scroll_listener_shortcut, register_shortcuts_from_config, stops_recording, unregister_stop_shortcuts, unregister_stop_scroll_shortcuts};
 use state::{AppState, SharedState};
 use tray::{build_tray_menu, load_tray_icon};
 pub use types::*;
@@ -48,8 +61,16 @@ fn quit_app(app: AppHandle) {
     app.exit(0);
 }
 
+/// Restart the app, e.g. so a just-granted screen recording permission takes effect
+#[tauri::command]
+fn relaunch_app(app: AppHandle) {
+    app.restart();
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    logging::init(&config::load_config());
+
     let state: SharedState = Arc::new(Mutex::new(AppState::default()));
 
     let state_for_shortcut = state.clone();
@@ -68,10 +89,14 @@ pub fn run() {
                         return;
                     }
 
-                    // If recording, any registered shortcut stops it
+                    // If recording, a shortcut stops it (toggle mode) or is ignored
                     let is_recording = state_for_shortcut.lock().unwrap().recording;
                     if is_recording {
-                        println!("[DEBUG][shortcut] 停止录制");
+                        if !stops_recording(shortcut) {
+                            log::warn!("[shortcut] Ignored while recording: {:?}", shortcut);
+                            return;
+                        }
+                        log::debug!("[shortcut] 停止录制");
                         state_for_shortcut.lock().unwrap().recording = false;
                         // IMPORTANT: Unregister in spawned thread to avoid deadlock
                         let app_clone = app.clone();
@@ -82,23 +107,39 @@ pub fn run() {
                         return;
                     }
 
+                    // Pause/resume (or start) scroll capture; must run before the
+                    // scroll-overlay check below, which treats any shortcut as "close"
+                    if is_toggle_scroll_listener_shortcut(shortcut) {
+                        let app_clone = app.clone();
+                        // May open the overlay window; keep it off the handler thread
+                        std::thread::spawn(move || {
+                            if let Err(e) = commands::toggle_scroll_listener_internal(app_clone) {
+                                log::warn!("[shortcut] toggle_scroll_listener 失败: {}", e);
+                            }
+                        });
+                        return;
+                    }
+
                     // Check if scroll-overlay window exists - if so, close it directly
                     // This is more reliable than depending on frontend event listeners
                     {
                         let scroll_overlay_exists = app.get_webview_window("scroll-overlay").is_some();
 
                         if scroll_overlay_exists {
-                            println!("[DEBUG][shortcut] 检测到滚动截图窗口，直接关闭");
+                            log::debug!("[shortcut] 检测到滚动截图窗口，直接关闭");
 
                             // Try to clean up state (non-blocking)
                             if let Ok(mut s) = state_for_shortcut.try_lock() {
                                 s.scroll_capturing = false;
                                 s.scroll_frames.clear();
+                                s.scroll_last_frame = None;
                                 s.scroll_offsets.clear();
                                 s.scroll_stitched = None;
                             }
                             #[cfg(target_os = "macos")]
                             crate::scroll_event::stop_scroll_listener();
+                            #[cfg(all(target_os = "linux", feature = "linux-scroll"))]
+                            crate::linux_capture::stop_scroll_listener();
 
                             // IMPORTANT: Do NOT call unregister() here - it causes deadlock!
                             // The shortcut handler callback cannot call unregister() on itself.
@@ -118,7 +159,7 @@ pub fn run() {
                                 let _ = win.destroy();
                             }
 
-                            println!("[DEBUG][shortcut] 滚动截图窗口已关闭");
+                            log::debug!("[shortcut] 滚动截图窗口已关闭");
                             return;
                         }
                     }
@@ -128,7 +169,7 @@ pub fn run() {
                         // Close selector window if open
                         if let Some(selector_win) = app.get_webview_window("selector") {
                             if selector_win.is_visible().unwrap_or(false) {
-                                println!("[DEBUG][shortcut] 关闭选择器");
+                                log::debug!("[shortcut] 关闭选择器");
                                 let _ = selector_win.close();
                             }
                         }
@@ -137,7 +178,7 @@ pub fn run() {
 
                     // Check if this is show_main shortcut (Alt+O)
                     if is_show_main_shortcut(shortcut) {
-                        println!("[DEBUG][shortcut] 打开主窗口");
+                        log::debug!("[shortcut] 打开主窗口");
                         if let Some(win) = app.get_webview_window("main") {
                             let _ = win.show();
                             let _ = win.set_focus();
@@ -146,8 +187,21 @@ pub fn run() {
                         return;
                     }
 
+                    // Check if this is repeat_capture shortcut (Alt+R)
+                    if is_repeat_capture_shortcut(shortcut) {
+                        log::debug!("[shortcut] 重复上次截图");
+                        let app_clone = app.clone();
+                        // Capture off the handler thread; it may open windows
+                        std::thread::spawn(move || {
+                            if let Err(e) = commands::repeat_last_capture_internal(app_clone) {
+                                log::warn!("[shortcut] repeat_last_capture 失败: {}", e);
+                            }
+                        });
+                        return;
+                    }
+
                     if let Some(mode) = get_action_for_shortcut(shortcut) {
-                        println!("[DEBUG][shortcut] {:?} triggered -> {:?}", shortcut, mode);
+                        log::debug!("[shortcut] {:?} triggered -> {:?}", shortcut, mode);
                         state_for_shortcut.lock().unwrap().pending_mode = Some(mode);
                         let _ = open_selector_internal(app.clone());
                     }
@@ -156,26 +210,53 @@ pub fn run() {
         )
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_shell::init())
         .manage(state)
         .invoke_handler(tauri::generate_handler![
             commands::get_screens,
+            commands::convert_coords,
             commands::get_mouse_position,
             commands::capture_screenshot,
+            commands::capture_all_displays,
             commands::open_selector,
             commands::set_region,
+            commands::set_region_exact,
+            commands::set_capture_padding,
+            commands::get_capture_padding,
+            commands::get_regions,
+            commands::get_last_region,
+            commands::get_region_history,
+            commands::select_region_from_history,
+            commands::apply_region_preset,
+            commands::start_region_preview,
+            commands::stop_region_preview,
+            commands::use_last_region,
+            commands::validate_region,
+            commands::repeat_last_capture,
+            commands::clear_regions,
             commands::get_pending_mode,
             commands::get_screen_snapshot,
             commands::get_magnifier_snapshot,
             commands::get_magnifier_pixels,
+            commands::get_loupe_region,
             commands::clear_pending_mode,
+            commands::set_capture_mode,
             commands::capture_screen_now,
+            commands::use_cached_display,
             commands::clear_screen_background,
+            commands::set_selector_frozen,
+            commands::get_selector_frozen,
+            commands::reset_state,
             commands::get_window_at_cursor,
             commands::get_window_info_at_cursor,
+            commands::capture_window_content_at_cursor,
+            commands::get_activation_policy,
+            commands::snap_region_to_elements,
             commands::get_shortcuts_config,
             commands::save_shortcut,
             commands::add_shortcut,
             commands::remove_shortcut,
+            commands::toggle_shortcut,
             commands::reset_shortcuts_to_default,
             commands::pause_shortcuts,
             commands::resume_shortcuts,
@@ -189,11 +270,45 @@ pub fn run() {
             commands::set_watermark_position,
             commands::get_show_caption_editor,
             commands::set_show_caption_editor,
+            commands::set_recording_limits,
+            commands::set_recording_frame_format,
+            commands::set_quick_save_options,
+            commands::set_upload_settings,
+            commands::set_ocr_languages,
+            commands::set_min_region_size,
+            commands::set_region_history_size,
+            commands::set_local_api,
+            commands::set_capture_feedback,
+            commands::get_default_formats,
+            commands::set_default_format,
+            commands::set_color_profile,
+            commands::set_max_export_size,
+            commands::set_snapshot_cache_max_displays,
+            commands::set_auto_request_permission,
+            commands::set_exclude_own_windows,
+            commands::set_capture_retry,
+            commands::set_capture_content_area_only,
+            commands::set_recording_shortcut_toggle,
+            commands::set_export_watermark,
+            commands::set_log_level,
+            commands::set_log_to_file,
+            commands::set_selector_appearance,
+            commands::set_restore_main_after_capture,
+            commands::set_accessory_during_capture,
             commands::start_recording,
             commands::stop_recording,
+            commands::set_recording_max_duration,
+            commands::set_recording_window_tracking,
+            commands::start_interval_capture,
+            commands::stop_interval_capture,
+            commands::capture_timed_gif,
             commands::get_recording_info,
+            commands::get_recording_buffer_info,
             commands::estimate_export_size,
             commands::export_gif,
+            commands::export_video,
+            commands::has_video_encoder,
+            commands::export_frame_sequence,
             commands::discard_recording,
             commands::get_frame_thumbnail,
             commands::get_filmstrip,
@@ -205,9 +320,11 @@ pub fn run() {
             commands::delete_file,
             commands::open_caption_editor,
             commands::open_zoom_viewer,
+            commands::open_review_window,
             commands::copy_image_to_clipboard,
             commands::copy_rgba_to_clipboard,
             commands::save_rgba_to_file,
+            commands::quick_save,
             // Folder commands
             commands::get_folders,
             commands::create_folder,
@@ -220,56 +337,98 @@ pub fn run() {
             commands::start_scroll_capture,
             commands::capture_scroll_frame_auto,
             commands::get_scroll_preview,
+            commands::estimate_scroll_progress,
             commands::copy_scroll_to_clipboard,
             commands::finish_scroll_capture,
             commands::stop_scroll_capture,
             commands::cancel_scroll_capture,
+            commands::discard_scroll_capture,
+            commands::trim_scroll_tail,
+            commands::start_auto_scroll_capture,
+            commands::detect_scroll_region,
+            commands::stop_auto_scroll_capture,
             commands::open_scroll_overlay,
             commands::start_scroll_capture_inline,
+            commands::set_scroll_axis,
+            commands::merge_captures,
+            commands::diff_captures,
+            commands::export_scroll_metadata,
+            commands::set_scroll_max_height,
+            commands::set_scroll_match_downscale,
+            commands::set_scroll_match_inset,
+            commands::get_scroll_match_inset,
+            commands::set_scroll_seam_blend,
+            commands::set_scroll_store_strips,
+            commands::set_scroll_change_tolerance,
+            commands::get_scroll_change_tolerance,
+            commands::pause_scroll_listener,
+            commands::resume_scroll_listener,
             commands::get_history,
             commands::get_stats,
             commands::get_autostart_enabled,
             commands::set_autostart_enabled,
             commands::check_screen_permission,
             commands::request_screen_permission,
+            commands::ensure_permission_then,
             commands::open_permission_settings,
+            commands::check_accessibility_permission,
+            commands::open_accessibility_settings,
+            commands::run_diagnostics,
             // Annotation commands
             commands::capture_region_preview,
+            commands::capture_window_isolated,
+            commands::capture_all_regions,
+            commands::recrop_capture,
+            commands::export_heic,
+            commands::supports_heic_export,
+            commands::capture_to_temp_file,
+            commands::capture_and_open,
+            commands::cleanup_temp_captures,
+            commands::get_capture_rgba,
             commands::save_annotated_screenshot,
             // Share compose
             share_compose::compose_share,
+            share_compose::frame_capture,
+            share_compose::make_contact_sheet,
+            upload::upload_capture,
+            ocr::extract_text,
+            schedule::schedule_capture,
+            schedule::cancel_scheduled_capture,
+            schedule::list_scheduled_captures,
+            // Annotation
+            annotate::apply_annotations,
+            annotate::redact_region,
+            annotate::adjust_image,
+            annotate::export_annotated_svg,
+            annotate::transform_capture,
+            annotate::load_image_for_editing,
+            histogram::capture_histogram,
+            logging::get_log_path,
             // Debug commands
             commands::open_devtools,
             commands::set_selector_mouse_passthrough,
             commands::setup_scroll_capture_passthrough,
             show_main_window,
             quit_app,
+            relaunch_app,
         ])
         .on_window_event(|window, event| {
             if let WindowEvent::CloseRequested { api, .. } = event {
                 if window.label() == "main" {
-                    println!("[DEBUG][close] CloseRequested for main window");
-                    println!("[DEBUG][close] Step 1: set_activation_policy(1)");
+                    log::debug!("[close] CloseRequested for main window");
+                    log::debug!("[close] Step 1: set_activation_policy(1)");
                     windows::set_activation_policy(1);
-                    println!("[DEBUG][close] Step 2: window.hide()");
+                    log::debug!("[close] Step 2: window.hide()");
                     window.hide().unwrap();
-                    println!("[DEBUG][close] Step 3: prevent_close");
+                    log::debug!("[close] Step 3: prevent_close");
                     api.prevent_close();
-                    println!("[DEBUG][close] Done");
+                    log::debug!("[close] Done");
                 }
             }
         })
         .setup(move |app| {
-            #[cfg(target_os = "macos")]
-            {
-                use objc::{class, msg_send, sel, sel_impl};
-                unsafe {
-                    let app_class = class!(NSApplication);
-                    let ns_app: *mut objc::runtime::Object =
-                        msg_send![app_class, sharedApplication];
-                    let _: () = msg_send![ns_app, setActivationPolicy: 1_i64];
-                }
-            }
+            // Start as a menu bar app (Accessory) until the dashboard is shown
+            windows::set_activation_policy(1);
 
             let tray_menu = build_tray_menu(app.handle())?;
 
@@ -299,16 +458,16 @@ pub fn run() {
                     "devtools" => {
                         // Toggle devtools - prioritize selector, fallback to main (and show it)
                         if let Some(win) = app.get_webview_window("selector") {
-                            println!("[devtools] Opening devtools for selector");
+                            log::info!("[devtools] Opening devtools for selector");
                             win.open_devtools();
                         } else if let Some(win) = app.get_webview_window("main") {
-                            println!("[devtools] Opening devtools for main (showing window first)");
+                            log::info!("[devtools] Opening devtools for main (showing it first)");
                             let _ = win.show();
                             let _ = win.set_focus();
                             windows::set_activation_policy(0);
                             win.open_devtools();
                         } else {
-                            println!("[devtools] No window found");
+                            log::warn!("[devtools] No window found");
                         }
                     }
                     "screenshot" => {
@@ -327,6 +486,13 @@ pub fn run() {
                         state_for_menu.lock().unwrap().pending_mode = Some(CaptureMode::Video);
                         let _ = open_selector_internal(app.clone());
                     }
+                    "stop_recording" => {
+                        if state_for_menu.lock().unwrap().interval_capturing {
+                            commands::stop_interval_capture(app.state());
+                        } else {
+                            commands::stop_recording(app.clone(), app.state());
+                        }
+                    }
                     "settings" => {
                         let _ = open_settings_window(app.clone());
                     }
@@ -373,6 +539,12 @@ pub fn run() {
                 }
             }
 
+            display_watch::install(app.handle().clone());
+
+            if let Err(e) = local_api::sync_with_config(app.handle()) {
+                log::warn!("[local_api] Failed to start: {}", e);
+            }
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,297 @@
+//! Linux capture backend (X11 / Wayland via xcap)
+//!
+//! Fast-path full-screen grab for the selector, monitor geometry for `capture::Screen`,
+//! and an optional evdev global scroll listener (feature `linux-scroll`) mirroring the
+//! macOS CGEventTap one.
+
+use image::RgbaImage;
+use xcap::Monitor;
+
+/// Primary monitor, falling back to the first one reported
+fn primary_monitor() -> Option<Monitor> {
+    let monitors = Monitor::all().ok()?;
+    let primary = monitors
+        .iter()
+        .position(|m| m.is_primary().unwrap_or(false))
+        .unwrap_or(0);
+    monitors.into_iter().nth(primary)
+}
+
+/// Capture the display containing (x, y), e.g. the one the selector covers, as RGBA
+/// (physical pixels). Falls back to the primary display.
+pub fn capture_rgba(x: i32, y: i32) -> Option<RgbaImage> {
+    let monitor = Monitor::from_point(x, y).ok().or_else(primary_monitor)?;
+    match monitor.capture_image() {
+        Ok(img) => Some(img),
+        Err(e) => {
+            log::warn!("[linux_capture] capture failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Monitor origin in the virtual desktop
+pub fn monitor_position(monitor: &Monitor) -> (i32, i32) {
+    (monitor.x().unwrap_or(0), monitor.y().unwrap_or(0))
+}
+
+/// Monitor scale factor (1.0 when the compositor doesn't report one)
+pub fn monitor_scale_factor(monitor: &Monitor) -> f32 {
+    monitor.scale_factor().unwrap_or(1.0).max(1.0)
+}
+
+#[cfg(feature = "linux-scroll")]
+pub use scroll::*;
+
+/// Global scroll listener using evdev (needs read access to /dev/input, e.g. `input` group)
+#[cfg(feature = "linux-scroll")]
+mod scroll {
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use evdev::{InputEventKind, RelativeAxisType};
+    use image::RgbaImage;
+    use tauri::{AppHandle, Emitter, Manager};
+
+    use crate::capture::Screen;
+    use crate::state::SharedState;
+    use crate::types::{ScrollAxis, ScrollCaptureProgress};
+
+    static SCROLL_LISTENER_ACTIVE: AtomicBool = AtomicBool::new(false);
+    static SCROLL_LISTENER_PAUSED: AtomicBool = AtomicBool::new(false);
+
+    /// Bumped on every start and stop. Device threads block in fetch_events, so one started
+    /// by an earlier session only notices on its next event; it exits once this moved on.
+    static LISTENER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+    /// Result of one listener-driven capture
+    enum Frame {
+        /// Matched and stitched
+        Captured(ScrollCaptureProgress),
+        /// Stitched image exceeded scroll_max_height, capture stopped
+        LimitReached(ScrollCaptureProgress),
+        /// Content hasn't moved (within scroll_change_tolerance)
+        Identical,
+        /// Changed, but no offset matched
+        NoMatch,
+    }
+
+    /// Grab the region and stitch it onto the capture, with the same match downscale,
+    /// change tolerance and max-height stop as the macOS listener
+    fn capture_frame(state: &SharedState) -> Result<Frame, String> {
+        use crate::commands::{
+            detect_scroll_delta, frames_nearly_identical, generate_preview_base64,
+            inset_for_matching, stitch_scroll,
+        };
+        use crate::fft_match::{auto_match_downscale, downscale_for_matching};
+
+        let (region, last_frame, stitched, axis, downscale, tolerance, inset, seam_blend) = {
+            let s = state.lock().unwrap();
+            if !s.scroll_capturing {
+                return Err("Not in scroll capture mode".to_string());
+            }
+            (
+                s.scroll_region.clone().ok_or("No region selected")?,
+                s.last_scroll_frame().cloned().ok_or("No previous frame")?,
+                s.scroll_stitched.clone().ok_or("No stitched image")?,
+                s.scroll_axis,
+                s.scroll_match_downscale,
+                s.scroll_change_tolerance.clone(),
+                s.scroll_match_inset,
+                s.scroll_seam_blend,
+            )
+        };
+
+        let screens = Screen::all().map_err(|e| e.to_string())?;
+        let screen = screens.first().ok_or("No screens found")?;
+        let captured = screen
+            .capture_area(region.x, region.y, region.width, region.height)
+            .map_err(|e| e.to_string())?;
+        let new_frame =
+            RgbaImage::from_raw(captured.width(), captured.height(), captured.into_raw())
+                .ok_or("Failed to convert image")?;
+
+        let prev_match = inset_for_matching(&last_frame, inset, region.width);
+        let curr_match = inset_for_matching(&new_frame, inset, region.width);
+
+        // Match on downscaled frames for large (4K) captures, stitch at full resolution
+        let factor = match downscale {
+            0 => auto_match_downscale(new_frame.width()),
+            f => f,
+        };
+        let delta = if factor > 1 {
+            let prev_small = downscale_for_matching(&prev_match, factor);
+            let curr_small = downscale_for_matching(&curr_match, factor);
+            detect_scroll_delta(axis, &prev_small, &curr_small, 0, None) * factor as i32
+        } else {
+            detect_scroll_delta(axis, &prev_match, &curr_match, 0, None)
+        };
+        if delta == 0 {
+            return Ok(if frames_nearly_identical(&prev_match, &curr_match, &tolerance) {
+                Frame::Identical
+            } else {
+                Frame::NoMatch
+            });
+        }
+
+        let stitched = stitch_scroll(axis, &stitched, &new_frame, delta, seam_blend)?;
+        let preview = generate_preview_base64(&stitched, 600)?;
+        let (total_width, total_height) = stitched.dimensions();
+
+        let mut s = state.lock().unwrap();
+        if !s.scroll_capturing {
+            return Err("Scroll capture was cancelled".to_string());
+        }
+        let offset = s.scroll_offsets.last().copied().unwrap_or(0) + delta;
+        s.push_scroll_frame(new_frame, delta, offset);
+        s.scroll_stitched = Some(stitched);
+        s.scroll_identical_streak = 0;
+
+        let progress = ScrollCaptureProgress {
+            frame_count: s.scroll_frames.len(),
+            total_height,
+            total_width,
+            preview_base64: preview,
+            estimated_complete: false,
+        };
+
+        // Endless feeds: stop once the growing dimension passes the limit
+        let extent = match axis {
+            ScrollAxis::Vertical => total_height,
+            ScrollAxis::Horizontal => total_width,
+        };
+        if s.scroll_max_height > 0 && extent > s.scroll_max_height {
+            log::warn!("[linux_scroll] limit reached: {}px > {}px", extent, s.scroll_max_height);
+            s.scroll_capturing = false;
+            return Ok(Frame::LimitReached(progress));
+        }
+        Ok(Frame::Captured(progress))
+    }
+
+    /// Count an unchanged frame; at the likely-complete threshold emit `scroll-likely-complete`
+    fn note_identical_frame(app: &AppHandle, state: &SharedState) {
+        use crate::commands::{scroll_progress_estimate, SCROLL_LIKELY_COMPLETE_FRAMES};
+
+        let estimate = {
+            let mut s = state.lock().unwrap();
+            s.scroll_identical_streak += 1;
+            if s.scroll_identical_streak != SCROLL_LIKELY_COMPLETE_FRAMES {
+                return;
+            }
+            scroll_progress_estimate(&s)
+        };
+        if let Ok(estimate) = estimate {
+            let _ = app.emit("scroll-likely-complete", &estimate);
+        }
+    }
+
+    /// Start listening for wheel events on every input device that has one
+    pub fn start_scroll_listener(app: AppHandle) {
+        if SCROLL_LISTENER_ACTIVE.swap(true, Ordering::SeqCst) {
+            log::info!("[linux_scroll] Listener already active");
+            return;
+        }
+        SCROLL_LISTENER_PAUSED.store(false, Ordering::Relaxed);
+
+        let axis = app
+            .try_state::<SharedState>()
+            .and_then(|s| s.lock().ok().map(|s| s.scroll_axis))
+            .unwrap_or_default();
+        let wheel = match axis {
+            ScrollAxis::Vertical => RelativeAxisType::REL_WHEEL,
+            ScrollAxis::Horizontal => RelativeAxisType::REL_HWHEEL,
+        };
+
+        let devices: Vec<_> = evdev::enumerate()
+            .map(|(_, d)| d)
+            .filter(|d| d.supported_relative_axes().is_some_and(|axes| axes.contains(wheel)))
+            .collect();
+
+        if devices.is_empty() {
+            log::error!("[linux_scroll] No readable wheel devices (user not in `input` group?)");
+            SCROLL_LISTENER_ACTIVE.store(false, Ordering::SeqCst);
+            let _ = app.emit("scroll-listener-failed", ());
+            return;
+        }
+
+        log::info!("[linux_scroll] Listening on {} device(s)", devices.len());
+        let generation = LISTENER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        let last_capture = Arc::new(Mutex::new(Instant::now() - Duration::from_millis(300)));
+
+        for mut device in devices {
+            let app = app.clone();
+            let last_capture = last_capture.clone();
+            // Threads block in fetch_events and exit on the first event after their session
+            thread::spawn(move || {
+                let current = || LISTENER_GENERATION.load(Ordering::SeqCst) == generation;
+                while current() {
+                    let scrolled = match device.fetch_events() {
+                        Ok(mut events) => {
+                            events.any(|ev| ev.kind() == InputEventKind::RelAxis(wheel))
+                        }
+                        Err(e) => {
+                            log::error!("[linux_scroll] Device read failed: {}", e);
+                            return;
+                        }
+                    };
+                    if !scrolled || !current() || SCROLL_LISTENER_PAUSED.load(Ordering::Relaxed) {
+                        continue;
+                    }
+
+                    {
+                        let mut last = last_capture.lock().unwrap();
+                        if last.elapsed() < Duration::from_millis(120) {
+                            continue;
+                        }
+                        *last = Instant::now();
+                    }
+
+                    // Give the target app a moment to repaint before grabbing
+                    thread::sleep(Duration::from_millis(30));
+                    let Some(state) = app.try_state::<SharedState>() else {
+                        continue;
+                    };
+                    match capture_frame(&state) {
+                        Ok(Frame::Captured(progress)) => {
+                            let _ = app.emit("scroll-preview-update", &progress);
+                        }
+                        Ok(Frame::LimitReached(progress)) => {
+                            stop_scroll_listener();
+                            let _ = app.emit("scroll-preview-update", &progress);
+                            let _ = app.emit("scroll-limit-reached", &progress);
+                        }
+                        Ok(Frame::Identical) => note_identical_frame(&app, &state),
+                        Ok(Frame::NoMatch) => {}
+                        Err(e) => log::warn!("[linux_scroll] capture skipped: {}", e),
+                    }
+                }
+            });
+        }
+
+        let _ = app.emit("scroll-listener-started", ());
+    }
+
+    /// Stop the global scroll listener
+    pub fn stop_scroll_listener() {
+        log::info!("[linux_scroll] Stopping scroll listener");
+        LISTENER_GENERATION.fetch_add(1, Ordering::SeqCst);
+        SCROLL_LISTENER_ACTIVE.store(false, Ordering::SeqCst);
+        SCROLL_LISTENER_PAUSED.store(false, Ordering::SeqCst);
+    }
+
+    /// Temporarily ignore scroll events
+    pub fn pause_scroll_listener() {
+        SCROLL_LISTENER_PAUSED.store(true, Ordering::SeqCst);
+    }
+
+    /// Resume after pause_scroll_listener
+    pub fn resume_scroll_listener() {
+        SCROLL_LISTENER_PAUSED.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_scroll_listener_paused() -> bool {
+        SCROLL_LISTENER_PAUSED.load(Ordering::SeqCst)
+    }
+}
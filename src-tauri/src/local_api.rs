@@ -0,0 +1,225 @@
+//! Opt-in local HTTP API for triggering captures from scripts
+//!
+//! Bound to 127.0.0.1 only and off by default (`local_api_enabled`). Every request must
+//! carry `Authorization: Bearer <local_api_token>` (generated into config.json when the API
+//! is enabled) and a loopback `Host`, so neither other local users' processes nor web
+//! pages (DNS rebinding) can read the screen through lovshot's permission. Requests run
+//! the same command functions the UI uses:
+//!
+//! - `GET /health` -> `{"ok": true}`
+//! - `POST /capture` with `{"region": {x, y, width, height}, "response": "path" | "bytes"}`
+//!   captures a still image of the region (whole primary display when omitted) without
+//!   touching the user's selection, and returns `{"path": ...}` or the PNG bytes.
+
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::capture::primary_screen_region;
+use crate::config::AppConfig;
+use crate::state::SharedState;
+use crate::types::{CaptureMode, Region, ScreenshotOptions};
+
+/// Running server and its port, kept so a config change can stop it
+static SERVER: Mutex<Option<(u16, Arc<Server>)>> = Mutex::new(None);
+/// Token requests must present; set whenever the server (re)starts
+static TOKEN: Mutex<String> = Mutex::new(String::new());
+/// Capture requests are tiny JSON documents
+const MAX_BODY_BYTES: u64 = 16 * 1024;
+
+#[derive(Deserialize)]
+struct CaptureRequest {
+    #[serde(default)]
+    mode: CaptureMode,
+    region: Option<Region>,
+    #[serde(default)]
+    response: CaptureResponse,
+}
+
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum CaptureResponse {
+    #[default]
+    Path,
+    Bytes,
+}
+
+type HttpResponse = Response<std::io::Cursor<Vec<u8>>>;
+
+fn json_response(status: u16, body: serde_json::Value) -> HttpResponse {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header)
+}
+
+fn error_response(status: u16, message: &str) -> HttpResponse {
+    json_response(status, serde_json::json!({ "error": message }))
+}
+
+/// Give `cfg` a random API token if it has none; returns whether one was generated
+pub fn ensure_token(cfg: &mut AppConfig) -> Result<bool, String> {
+    if !cfg.local_api_token.is_empty() {
+        return Ok(false);
+    }
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Local API token: {}", e))?;
+    cfg.local_api_token = hex::encode(bytes);
+    Ok(true)
+}
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str())
+}
+
+/// Loopback `Host` only: a rebinding page's requests carry its own host name
+fn host_is_loopback(request: &Request) -> bool {
+    let Some(host) = header(request, "Host") else {
+        return false;
+    };
+    let name = host.rsplit_once(':').map_or(host, |(name, _)| name);
+    matches!(name, "127.0.0.1" | "localhost")
+}
+
+/// Constant-time comparison of the `Authorization: Bearer` value with the token
+fn has_valid_token(request: &Request) -> bool {
+    let token = TOKEN.lock().unwrap();
+    let Some(given) = header(request, "Authorization").and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    !token.is_empty()
+        && given.len() == token.len()
+        && given.bytes().zip(token.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn handle_capture(app: &AppHandle, request: &mut Request) -> HttpResponse {
+    let mut body = String::new();
+    let mut reader = request.as_reader().take(MAX_BODY_BYTES + 1);
+    if let Err(e) = reader.read_to_string(&mut body) {
+        return error_response(400, &e.to_string());
+    }
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return error_response(413, "Request body too large");
+    }
+    let req: CaptureRequest = if body.trim().is_empty() {
+        CaptureRequest {
+            mode: CaptureMode::Image,
+            region: None,
+            response: CaptureResponse::Path,
+        }
+    } else {
+        match serde_json::from_str(&body) {
+            Ok(r) => r,
+            Err(e) => return error_response(400, &format!("Invalid JSON: {}", e)),
+        }
+    };
+    if !matches!(req.mode, CaptureMode::Image | CaptureMode::StaticImage) {
+        return error_response(400, "Only image captures are supported");
+    }
+
+    let region = match req.region.map(Ok).unwrap_or_else(primary_screen_region) {
+        Ok(r) => r,
+        Err(e) => return error_response(500, &e),
+    };
+    log::info!(
+        "[local_api] capture ({}, {}) {}x{}",
+        region.x, region.y, region.width, region.height
+    );
+
+    // Capture the requested rect directly; the user's selection stays as it was
+    let options = ScreenshotOptions { region: Some(region), ..Default::default() };
+    let state = app.state::<SharedState>();
+    let path = match crate::commands::save_screenshot(app.clone(), state, Some(options)) {
+        Ok(p) => p,
+        Err(e) => return error_response(500, &e),
+    };
+
+    if req.response == CaptureResponse::Bytes {
+        return match std::fs::read(&path) {
+            Ok(bytes) => {
+                let header = Header::from_bytes(&b"Content-Type"[..], &b"image/png"[..]).unwrap();
+                Response::from_data(bytes).with_header(header)
+            }
+            Err(e) => error_response(500, &e.to_string()),
+        };
+    }
+    json_response(200, serde_json::json!({ "path": path }))
+}
+
+fn handle(app: &AppHandle, mut request: Request) {
+    // Browsers always send Origin; scripts don't. Refusing it keeps web pages from
+    // driving captures through the user's browser.
+    let from_browser = header(&request, "Origin").is_some();
+    let response = if from_browser || !host_is_loopback(&request) {
+        error_response(403, "Only local scripts may use this API")
+    } else if !has_valid_token(&request) {
+        error_response(401, "Missing or wrong bearer token (local_api_token in config.json)")
+    } else {
+        let (method, url) = (request.method().clone(), request.url().to_string());
+        match (method, url.as_str()) {
+            (Method::Get, "/health") => json_response(200, serde_json::json!({ "ok": true })),
+            (Method::Post, "/capture") => handle_capture(app, &mut request),
+            _ => error_response(404, "Not found"),
+        }
+    };
+    let _ = request.respond(response);
+}
+
+/// Start the server on 127.0.0.1:`port` accepting `token` (no-op when already running there)
+pub fn start(app: AppHandle, port: u16, token: &str) -> Result<(), String> {
+    if token.is_empty() {
+        return Err("Local API: no token configured".to_string());
+    }
+    *TOKEN.lock().unwrap() = token.to_string();
+    let mut slot = SERVER.lock().unwrap();
+    if slot.as_ref().is_some_and(|(running_port, _)| *running_port == port) {
+        return Ok(());
+    }
+    if let Some((_, old)) = slot.take() {
+        old.unblock(); // port changed
+    }
+
+    let server = Arc::new(
+        Server::http(("127.0.0.1", port)).map_err(|e| format!("Local API: {}", e))?,
+    );
+    *slot = Some((port, server.clone()));
+    log::info!("[local_api] Listening on 127.0.0.1:{}", port);
+
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            handle(&app, request);
+        }
+        log::info!("[local_api] Stopped");
+    });
+    Ok(())
+}
+
+/// Stop the server if running
+pub fn stop() {
+    if let Some((_, server)) = SERVER.lock().unwrap().take() {
+        server.unblock();
+    }
+}
+
+/// Apply `local_api_enabled` / `local_api_port` / `local_api_token` from config
+pub fn sync_with_config(app: &AppHandle) -> Result<(), String> {
+    let mut cfg = crate::config::load_config();
+    if cfg.local_api_enabled {
+        if ensure_token(&mut cfg)? {
+            crate::config::save_config(&cfg)?;
+        }
+        start(app.clone(), cfg.local_api_port, &cfg.local_api_token)
+    } else {
+        stop();
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::config::{self, AppConfig, LogLevel};
+
+/// Env var that overrides the configured level, e.g. `LOVSHOT_LOG=debug`
+const LOG_ENV: &str = "LOVSHOT_LOG";
+/// The log file is rotated to `lovshot.log.old` past this size on startup
+const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Writes to stdout/stderr and, when enabled, to the log file
+struct Logger {
+    file: Mutex<Option<File>>,
+}
+
+static LOGGER: Logger = Logger {
+    file: Mutex::new(None),
+};
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}",
+            chrono::Local::now().format("%H:%M:%S%.3f"),
+            record.level(),
+            record.args()
+        );
+        if record.level() <= log::Level::Warn {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = writeln!(file, "{} {}", chrono::Local::now().format("%Y-%m-%d"), line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Where file logging writes, next to config.json
+pub fn log_path() -> PathBuf {
+    config::get_config_path().with_file_name("lovshot.log")
+}
+
+fn level_from_env() -> Option<LevelFilter> {
+    std::env::var(LOG_ENV).ok()?.parse().ok()
+}
+
+fn open_log_file() -> Result<File, String> {
+    let path = log_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    if fs::metadata(&path).map(|m| m.len() > MAX_LOG_FILE_BYTES).unwrap_or(false) {
+        let _ = fs::rename(&path, path.with_extension("log.old"));
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+/// Install the logger; call once at startup, before anything logs
+pub fn init(config: &AppConfig) {
+    if log::set_logger(&LOGGER).is_err() {
+        return;
+    }
+    log::set_max_level(level_from_env().unwrap_or_else(|| config.log_level.to_filter()));
+    if config.log_to_file {
+        if let Err(e) = set_file_logging(true) {
+            log::warn!("[logging] {}", e);
+        }
+    }
+}
+
+/// Change the level at runtime; the env var, when set, still wins
+pub fn set_level(level: LogLevel) {
+    log::set_max_level(level_from_env().unwrap_or_else(|| level.to_filter()));
+}
+
+/// Start or stop mirroring the log to `log_path()`
+pub fn set_file_logging(enabled: bool) -> Result<(), String> {
+    let file = if enabled { Some(open_log_file()?) } else { None };
+    *LOGGER.file.lock().unwrap() = file;
+    Ok(())
+}
+
+/// Path of the log file, for attaching to bug reports
+#[tauri::command]
+pub fn get_log_path() -> String {
+    log_path().to_string_lossy().to_string()
+}
@@ -1,6 +1,11 @@
 //! Native macOS screenshot with hardware-accelerated display
 //! Uses CGImage → NSImageView pipeline (GPU to GPU, no CPU encoding)
 
+use core_foundation::base::TCFType;
+use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
+use core_foundation::number::CFNumber;
+use core_foundation::string::{CFString, CFStringRef};
+use core_foundation::url::{CFURLRef, CFURL};
 use image::RgbaImage;
 use objc::runtime::Object;
 use objc::{class, msg_send, sel, sel_impl};
@@ -19,8 +24,64 @@ extern "C" {
     fn CFDataGetLength(data: *const c_void) -> isize;
     fn CFDataGetBytePtr(data: *const c_void) -> *const u8;
     fn CFRelease(cf: *const c_void);
+    fn CFDataCreate(allocator: *const c_void, bytes: *const u8, length: isize) -> *mut c_void;
+    fn CGColorSpaceCreateDeviceRGB() -> *mut c_void;
+    fn CGDataProviderCreateWithCFData(data: *const c_void) -> *mut c_void;
+    fn CGImageCreate(
+        width: usize,
+        height: usize,
+        bits_per_component: usize,
+        bits_per_pixel: usize,
+        bytes_per_row: usize,
+        space: *const c_void,
+        bitmap_info: u32,
+        provider: *const c_void,
+        decode: *const f64,
+        should_interpolate: bool,
+        intent: i32,
+    ) -> *mut c_void;
+    fn CGWindowListCreateImage(
+        screen_bounds: CGRectFFI,
+        list_option: u32,
+        window_id: u32,
+        image_option: u32,
+    ) -> *mut c_void;
+    fn CGWindowListCreateImageFromArray(
+        screen_bounds: CGRectFFI,
+        window_array: *const c_void,
+        image_option: u32,
+    ) -> *mut c_void;
+    fn CGDisplayBounds(display_id: u32) -> CGRectFFI;
 }
 
+// FFI declarations for ImageIO (HEIC encoding)
+#[link(name = "ImageIO", kind = "framework")]
+extern "C" {
+    static kCGImageDestinationLossyCompressionQuality: CFStringRef;
+    fn CGImageDestinationCreateWithURL(
+        url: CFURLRef,
+        uti: CFStringRef,
+        count: usize,
+        options: *const c_void,
+    ) -> *mut c_void;
+    fn CGImageDestinationAddImage(dest: *mut c_void, image: *const c_void, properties: CFDictionaryRef);
+    fn CGImageDestinationFinalize(dest: *mut c_void) -> bool;
+}
+
+/// CGRect layout for FFI by value
+#[repr(C)]
+struct CGRectFFI {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+const K_CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW: u32 = 1 << 3;
+const K_CG_WINDOW_IMAGE_DEFAULT: u32 = 0;
+const K_CG_WINDOW_IMAGE_BOUNDS_IGNORE_FRAMING: u32 = 1 << 0;
+const K_CG_IMAGE_ALPHA_LAST: u32 = 3; // non-premultiplied RGBA
+
 /// Raw CGImage handle
 pub struct CGImageRef(*mut c_void);
 
@@ -61,6 +122,95 @@ pub fn capture_cgimage() -> Option<CGImageRef> {
     }
 }
 
+/// capture_cgimage, retried for transient failures (e.g. right after waking from sleep):
+/// up to `capture_retry_attempts` tries, waiting `capture_retry_backoff_ms` (doubling)
+/// in between, and never longer than `capture_retry_timeout_ms` overall
+pub fn capture_cgimage_with_retry() -> Option<CGImageRef> {
+    use std::time::{Duration, Instant};
+
+    let cfg = crate::config::load_config();
+    let attempts = cfg.capture_retry_attempts.max(1);
+    let deadline = Instant::now() + Duration::from_millis(cfg.capture_retry_timeout_ms);
+    let mut backoff = Duration::from_millis(cfg.capture_retry_backoff_ms);
+    for attempt in 1..=attempts {
+        if let Some(img) = capture_cgimage() {
+            if attempt > 1 {
+                log::info!("[capture_cgimage] Attempt {}/{} succeeded", attempt, attempts);
+            }
+            return Some(img);
+        }
+        log::warn!("[capture_cgimage] Attempt {}/{} failed", attempt, attempts);
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if attempt == attempts || remaining.is_zero() {
+            break;
+        }
+        std::thread::sleep(backoff.min(remaining));
+        backoff *= 2;
+    }
+    None
+}
+
+/// Main display capture composited from every window except this app's own, so the
+/// dashboard, pins and other overlays never end up in the shot
+pub fn capture_cgimage_excluding_own() -> Option<CGImageRef> {
+    let ids = crate::window_detect::foreign_window_ids();
+    if ids.is_empty() {
+        return capture_cgimage_with_retry();
+    }
+    // CGWindowListCreateImageFromArray takes CGWindowIDs stored directly as array values
+    let values: Vec<*const c_void> = ids.iter().map(|&id| id as usize as *const c_void).collect();
+    unsafe {
+        let array = core_foundation::array::CFArrayCreate(
+            std::ptr::null(),
+            values.as_ptr(),
+            values.len() as isize,
+            std::ptr::null(),
+        );
+        if array.is_null() {
+            return capture_cgimage_with_retry();
+        }
+        let bounds = CGDisplayBounds(CGMainDisplayID());
+        let cg_image = CGWindowListCreateImageFromArray(
+            bounds,
+            array as *const c_void,
+            K_CG_WINDOW_IMAGE_DEFAULT,
+        );
+        CFRelease(array as *const c_void);
+        if cg_image.is_null() {
+            capture_cgimage_with_retry()
+        } else {
+            Some(CGImageRef(cg_image))
+        }
+    }
+}
+
+/// Capture a single window by CGWindowID, excluding anything overlapping it
+pub fn capture_window_cgimage(window_id: u32) -> Option<CGImageRef> {
+    unsafe {
+        // CGRectNull = capture the window's own bounds
+        let null_rect = CGRectFFI {
+            x: f64::INFINITY,
+            y: f64::INFINITY,
+            width: 0.0,
+            height: 0.0,
+        };
+        let cg_image = CGWindowListCreateImage(
+            null_rect,
+            K_CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW,
+            window_id,
+            K_CG_WINDOW_IMAGE_BOUNDS_IGNORE_FRAMING,
+        );
+        if cg_image.is_null() || CGImageGetWidth(cg_image) == 0 {
+            if !cg_image.is_null() {
+                CFRelease(cg_image);
+            }
+            None
+        } else {
+            Some(CGImageRef(cg_image))
+        }
+    }
+}
+
 /// Set window background to CGImage using NSImageView (hardware accelerated)
 /// Takes CGImagePtr for use in 'static Send closures
 pub unsafe fn set_window_background_cgimage_raw(ns_window: *mut Object, cg_image_ptr: CGImagePtr) {
@@ -84,8 +234,10 @@ pub unsafe fn set_window_background_cgimage_raw(ns_window: *mut Object, cg_image
         height: pixel_height / backing_scale
     };
 
-    println!("[native_screenshot] CGImage: {}x{} px, scale: {}, NSImage size: {}x{} pt",
-             pixel_width, pixel_height, backing_scale, img_size.width, img_size.height);
+    log::debug!(
+        "[native_screenshot] CGImage: {}x{} px, scale: {}, NSImage size: {}x{} pt",
+        pixel_width, pixel_height, backing_scale, img_size.width, img_size.height
+    );
 
     let ns_image: *mut Object = msg_send![class!(NSImage), alloc];
     let ns_image: *mut Object = msg_send![ns_image, initWithCGImage:cg_image_ptr.0 size:img_size];
@@ -94,7 +246,7 @@ pub unsafe fn set_window_background_cgimage_raw(ns_window: *mut Object, cg_image
     let content_view: *mut Object = msg_send![ns_window, contentView];
     let frame: NSRect = msg_send![content_view, bounds];
 
-    println!("[native_screenshot] content view bounds: {}x{} pt", frame.width, frame.height);
+    log::debug!("[native_screenshot] content view bounds: {}x{} pt", frame.width, frame.height);
 
     // Create NSImageView
     let image_view: *mut Object = msg_send![class!(NSImageView), alloc];
@@ -166,3 +318,78 @@ pub fn cgimage_to_rgba(cg_image: &CGImageRef) -> Option<RgbaImage> {
         RgbaImage::from_raw(width, height, rgba_data)
     }
 }
+
+/// Wrap an RgbaImage as a CGImage (copies the pixels into a CFData)
+pub fn rgba_to_cgimage(img: &RgbaImage) -> Option<CGImageRef> {
+    unsafe {
+        let raw = img.as_raw();
+        let data = CFDataCreate(std::ptr::null(), raw.as_ptr(), raw.len() as isize);
+        if data.is_null() {
+            return None;
+        }
+        let provider = CGDataProviderCreateWithCFData(data);
+        CFRelease(data);
+        if provider.is_null() {
+            return None;
+        }
+
+        let color_space = CGColorSpaceCreateDeviceRGB();
+        let cg_image = CGImageCreate(
+            img.width() as usize,
+            img.height() as usize,
+            8,
+            32,
+            img.width() as usize * 4,
+            color_space,
+            K_CG_IMAGE_ALPHA_LAST,
+            provider,
+            std::ptr::null(),
+            false,
+            0, // kCGRenderingIntentDefault
+        );
+        CFRelease(color_space);
+        CFRelease(provider);
+
+        if cg_image.is_null() {
+            None
+        } else {
+            Some(CGImageRef(cg_image))
+        }
+    }
+}
+
+/// Encode a CGImage as HEIC via CGImageDestination (quality 0.0-1.0)
+pub fn write_heic(
+    cg_image: &CGImageRef,
+    path: &std::path::Path,
+    quality: f32,
+) -> Result<(), String> {
+    let url = CFURL::from_path(path, false).ok_or("Invalid output path")?;
+    let uti = CFString::from_static_string("public.heic");
+    let quality_key =
+        unsafe { CFString::wrap_under_get_rule(kCGImageDestinationLossyCompressionQuality) };
+    let properties = CFDictionary::from_CFType_pairs(&[(
+        quality_key,
+        CFNumber::from(quality.clamp(0.0, 1.0) as f64),
+    )]);
+
+    unsafe {
+        let dest = CGImageDestinationCreateWithURL(
+            url.as_concrete_TypeRef(),
+            uti.as_concrete_TypeRef(),
+            1,
+            std::ptr::null(),
+        );
+        if dest.is_null() {
+            return Err("HEIC encoding is not supported on this system".to_string());
+        }
+        CGImageDestinationAddImage(dest, cg_image.0, properties.as_concrete_TypeRef());
+        let ok = CGImageDestinationFinalize(dest);
+        CFRelease(dest);
+        if ok {
+            Ok(())
+        } else {
+            Err("Failed to write HEIC file".to_string())
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Text recognition on captures (macOS Vision framework)
+//!
+//! Returns recognized lines with pixel bounding boxes so the UI can offer
+//! click-to-copy regions. Other platforms report OCR as unsupported.
+
+use crate::state::SharedState;
+use crate::types::{ImageSource, OcrTextBlock};
+
+/// Recognize text in the selected image. `languages` (BCP-47, e.g. "en-US", "zh-Hans")
+/// overrides the configured `ocr_languages`; empty means automatic detection.
+#[tauri::command]
+pub async fn extract_text(
+    state: tauri::State<'_, SharedState>,
+    source: Option<ImageSource>,
+    languages: Option<Vec<String>>,
+) -> Result<Vec<OcrTextBlock>, String> {
+    let img = {
+        let mut s = state.lock().unwrap();
+        crate::annotate::source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image for text extraction")?
+            .clone()
+    };
+    let languages = languages.unwrap_or_else(|| crate::config::load_config().ocr_languages);
+
+    tokio::task::spawn_blocking(move || recognize_text(&img, &languages))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(not(target_os = "macos"))]
+fn recognize_text(
+    _img: &image::RgbaImage,
+    _languages: &[String],
+) -> Result<Vec<OcrTextBlock>, String> {
+    Err("Text extraction is unsupported on this platform".to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn recognize_text(
+    img: &image::RgbaImage,
+    languages: &[String],
+) -> Result<Vec<OcrTextBlock>, String> {
+    use objc::runtime::{Object, BOOL, YES};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::{CStr, CString};
+
+    #[link(name = "Vision", kind = "framework")]
+    extern "C" {}
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CGRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    }
+
+    unsafe fn nsstring(s: &str) -> *mut Object {
+        let cstr = CString::new(s).unwrap_or_default();
+        msg_send![class!(NSString), stringWithUTF8String: cstr.as_ptr()]
+    }
+
+    let cg_image =
+        crate::native_screenshot::rgba_to_cgimage(img).ok_or("Failed to create CGImage")?;
+    let cg_ptr = cg_image.as_send_ptr().0;
+    let (img_w, img_h) = (img.width() as f64, img.height() as f64);
+
+    unsafe {
+        let pool: *mut Object = msg_send![class!(NSAutoreleasePool), new];
+        let nil: *mut Object = std::ptr::null_mut();
+
+        let options: *mut Object = msg_send![class!(NSDictionary), dictionary];
+        let handler: *mut Object = msg_send![class!(VNImageRequestHandler), alloc];
+        let handler: *mut Object = msg_send![handler, initWithCGImage: cg_ptr options: options];
+
+        let request: *mut Object = msg_send![class!(VNRecognizeTextRequest), new];
+        let _: () = msg_send![request, setRecognitionLevel: 0_i64]; // accurate
+        let _: () = msg_send![request, setUsesLanguageCorrection: YES];
+        if languages.is_empty() {
+            // automaticallyDetectsLanguage is macOS 13+
+            let supported: BOOL =
+                msg_send![request, respondsToSelector: sel!(setAutomaticallyDetectsLanguage:)];
+            if supported == YES {
+                let _: () = msg_send![request, setAutomaticallyDetectsLanguage: YES];
+            }
+        } else {
+            let langs: *mut Object = msg_send![class!(NSMutableArray), array];
+            for lang in languages {
+                let _: () = msg_send![langs, addObject: nsstring(lang)];
+            }
+            let _: () = msg_send![request, setRecognitionLanguages: langs];
+        }
+
+        let requests: *mut Object = msg_send![class!(NSArray), arrayWithObject: request];
+        let mut error: *mut Object = nil;
+        let ok: BOOL = msg_send![handler, performRequests: requests error: &mut error];
+
+        let result = if ok != YES {
+            let message = if error.is_null() {
+                "Vision request failed".to_string()
+            } else {
+                let desc: *mut Object = msg_send![error, localizedDescription];
+                let cstr: *const std::os::raw::c_char = msg_send![desc, UTF8String];
+                CStr::from_ptr(cstr).to_string_lossy().to_string()
+            };
+            Err(message)
+        } else {
+            let observations: *mut Object = msg_send![request, results];
+            let count: usize = if observations.is_null() {
+                0
+            } else {
+                msg_send![observations, count]
+            };
+            let mut blocks = Vec::with_capacity(count);
+
+            for i in 0..count {
+                let observation: *mut Object = msg_send![observations, objectAtIndex: i];
+                let candidates: *mut Object = msg_send![observation, topCandidates: 1_usize];
+                let candidate_count: usize = msg_send![candidates, count];
+                if candidate_count == 0 {
+                    continue;
+                }
+                let candidate: *mut Object = msg_send![candidates, objectAtIndex: 0_usize];
+                let text: *mut Object = msg_send![candidate, string];
+                let cstr: *const std::os::raw::c_char = msg_send![text, UTF8String];
+                let confidence: f32 = msg_send![candidate, confidence];
+
+                // Normalized box with bottom-left origin -> image pixels, top-left origin
+                let bbox: CGRect = msg_send![observation, boundingBox];
+                blocks.push(OcrTextBlock {
+                    text: CStr::from_ptr(cstr).to_string_lossy().to_string(),
+                    confidence,
+                    x: (bbox.x * img_w).round() as i32,
+                    y: ((1.0 - bbox.y - bbox.height) * img_h).round() as i32,
+                    width: (bbox.width * img_w).round() as u32,
+                    height: (bbox.height * img_h).round() as u32,
+                });
+            }
+            Ok(blocks)
+        };
+
+        let _: () = msg_send![request, release];
+        let _: () = msg_send![handler, release];
+        let _: () = msg_send![pool, drain];
+        drop(cg_image);
+
+        if let Ok(ref blocks) = result {
+            log::info!("[extract_text] {} text blocks", blocks.len());
+        }
+        result
+    }
+}
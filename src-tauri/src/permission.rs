@@ -1,7 +1,8 @@
-//! macOS Screen Recording Permission Check
+//! macOS Screen Recording / Accessibility Permission Check
 //!
 //! Uses CGPreflightScreenCaptureAccess() and CGRequestScreenCaptureAccess()
-//! to check and request screen recording permission.
+//! to check and request screen recording permission, and AXIsProcessTrusted()
+//! for the Accessibility permission the scroll-capture CGEventTap needs.
 
 #[cfg(target_os = "macos")]
 use core_graphics::access::ScreenCaptureAccess;
@@ -37,6 +38,27 @@ pub fn open_screen_recording_settings() -> Result<(), String> {
     Ok(())
 }
 
+/// Check if Accessibility permission is granted (required for the global scroll event tap)
+#[cfg(target_os = "macos")]
+pub fn has_accessibility_permission() -> bool {
+    unsafe { accessibility_sys::AXIsProcessTrusted() }
+}
+
+/// Open System Preferences to Accessibility settings
+#[cfg(target_os = "macos")]
+pub fn open_accessibility_settings() -> Result<(), String> {
+    use std::process::Command;
+
+    let url = "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility";
+
+    Command::new("open")
+        .arg(url)
+        .spawn()
+        .map_err(|e| format!("Failed to open System Preferences: {}", e))?;
+
+    Ok(())
+}
+
 // Non-macOS stubs
 #[cfg(not(target_os = "macos"))]
 pub fn has_screen_recording_permission() -> bool {
@@ -52,3 +74,13 @@ pub fn request_screen_recording_permission() -> bool {
 pub fn open_screen_recording_settings() -> Result<(), String> {
     Ok(())
 }
+
+#[cfg(not(target_os = "macos"))]
+pub fn has_accessibility_permission() -> bool {
+    true
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn open_accessibility_settings() -> Result<(), String> {
+    Ok(())
+}
@@ -141,9 +141,84 @@ fn hash_row(img: &RgbaImage, y: u32, w: u32) -> u64 {
     hash
 }
 
-/// Detect scroll with tolerance for minor pixel differences
-/// Uses "fuzzy" row matching by quantizing pixel values
-pub fn detect_scroll_delta_fuzzy(prev: &RgbaImage, curr: &RgbaImage) -> i32 {
+/// Length of the repeated band at the bottom of `img`: the longest trailing run of rows
+/// that equal the row `p` above them, for any period `p` up to `max_period`
+/// (`p = 1` is a solid/blank band, larger `p` a re-stitched footer).
+/// Runs shorter than `min_run` return 0 so legit solid backgrounds survive.
+pub fn trailing_repeat_rows(img: &RgbaImage, max_period: u32, min_run: u32) -> u32 {
+    let hashes = hash_all_rows(img);
+    let h = hashes.len();
+    let mut best = 0;
+    for period in 1..=(max_period as usize).min(h / 2) {
+        let run = (period..h)
+            .rev()
+            .take_while(|&i| hashes[i] == hashes[i - period])
+            .count();
+        best = best.max(run);
+    }
+    // Keep at least one copy of whatever repeats
+    let best = best.min(h.saturating_sub(1)) as u32;
+    if best >= min_run.max(1) {
+        best
+    } else {
+        0
+    }
+}
+
+/// Sub-rectangle `(x, y, width, height)` of two same-size frames that moved between them,
+/// taken after a small vertical scroll. Rows unchanged at the top and bottom (toolbars,
+/// footers) and columns unchanged at either side (sidebars) are trimmed off, and what's
+/// left must line up as a scroll. None if nothing moved or the change isn't a scroll.
+pub fn detect_moving_rect(prev: &RgbaImage, curr: &RgbaImage) -> Option<(u32, u32, u32, u32)> {
+    let (w, h) = prev.dimensions();
+    if curr.dimensions() != (w, h) || h < 40 {
+        return None;
+    }
+
+    let prev_hashes = hash_all_rows(prev);
+    let curr_hashes = hash_all_rows(curr);
+    let same_row = |y: usize| prev_hashes[y] == curr_hashes[y];
+    let top = (0..h as usize).take_while(|&y| same_row(y)).count();
+    if top == h as usize {
+        return None;
+    }
+    let bottom = (top..h as usize).rev().take_while(|&y| same_row(y)).count();
+    let (y0, y1) = (top as u32, h - bottom as u32);
+
+    let column_changed = |x: u32| (y0..y1).any(|y| prev.get_pixel(x, y) != curr.get_pixel(x, y));
+    let x0 = (0..w).find(|&x| column_changed(x))?;
+    let x1 = (x0..w).rev().find(|&x| column_changed(x))? + 1;
+
+    let (rw, rh) = (x1 - x0, y1 - y0);
+    let band_prev = hash_all_rows(&image::imageops::crop_imm(prev, x0, y0, rw, rh).to_image());
+    let band_curr = hash_all_rows(&image::imageops::crop_imm(curr, x0, y0, rw, rh).to_image());
+    find_row_shift(&band_prev, &band_curr, 10).map(|_| (x0, y0, rw, rh))
+}
+
+/// Smallest row shift (positive = content moved up) that lines `curr` up with `prev`
+/// over at least `min_overlap` rows
+fn find_row_shift(prev: &[u64], curr: &[u64], min_overlap: usize) -> Option<i32> {
+    let h = prev.len();
+    (1..h.saturating_sub(min_overlap)).find_map(|d| {
+        if (0..h - d).all(|y| prev[y + d] == curr[y]) {
+            Some(d as i32)
+        } else if (0..h - d).all(|y| prev[y] == curr[y + d]) {
+            Some(-(d as i32))
+        } else {
+            None
+        }
+    })
+}
+
+/// Detect scroll with tolerance for minor pixel differences (antialiasing, subpixel text)
+/// Uses "fuzzy" row matching by quantizing pixel values. Returns the pixel shift like the
+/// FFT matcher: positive = scroll down (content moved up), negative = up, 0 = no match.
+/// Shifts are searched smallest first, up to `max_delta` (default half the height).
+pub fn detect_scroll_delta_fuzzy(
+    prev: &RgbaImage,
+    curr: &RgbaImage,
+    max_delta: Option<i32>,
+) -> i32 {
     let (w1, h1) = prev.dimensions();
     let (w2, h2) = curr.dimensions();
 
@@ -153,7 +228,9 @@ pub fn detect_scroll_delta_fuzzy(prev: &RgbaImage, curr: &RgbaImage) -> i32 {
 
     let h = h1 as usize;
     let min_overlap = 10;
-    let max_search = h / 2;
+    let max_shift = max_delta
+        .map_or(h / 2, |d| d.max(1) as usize)
+        .min(h - min_overlap);
 
     // Use quantized hashes (tolerant to small color differences)
     let prev_hashes = hash_all_rows_fuzzy(prev);
@@ -163,19 +240,18 @@ pub fn detect_scroll_delta_fuzzy(prev: &RgbaImage, curr: &RgbaImage) -> i32 {
         return 0;
     }
 
-    // Scroll DOWN
-    for overlap in (min_overlap..=max_search).rev() {
-        let prev_start = h - overlap;
-        if (0..overlap).all(|i| prev_hashes[prev_start + i] == curr_hashes[i]) {
-            return overlap as i32;
-        }
-    }
+    // Flat overlaps (blank page areas) match any shift; require some real content
+    let has_content = |rows: &[u64]| rows.iter().any(|&r| r != rows[0]);
 
-    // Scroll UP
-    for overlap in (min_overlap..=max_search).rev() {
-        let curr_start = h - overlap;
-        if (0..overlap).all(|i| prev_hashes[i] == curr_hashes[curr_start + i]) {
-            return -(overlap as i32);
+    for d in 1..=max_shift {
+        let overlap = h - d;
+        // Scroll DOWN: prev's rows d.. reappear at the top of curr
+        if prev_hashes[d..] == curr_hashes[..overlap] && has_content(&curr_hashes[..overlap]) {
+            return d as i32;
+        }
+        // Scroll UP: prev's top rows reappear d rows lower in curr
+        if prev_hashes[..overlap] == curr_hashes[d..] && has_content(&prev_hashes[..overlap]) {
+            return -(d as i32);
         }
     }
 
@@ -210,6 +286,80 @@ fn hash_row_fuzzy(img: &RgbaImage, y: u32, w: u32) -> u64 {
     hash
 }
 
+/// Detect horizontal scroll by matching quantized column hashes
+/// Returns positive for scroll right (content moves left), negative for scroll left,
+/// 0 for no match. The value is the pixel shift between the frames.
+pub fn detect_scroll_delta_horizontal(prev: &RgbaImage, curr: &RgbaImage) -> i32 {
+    let (w1, h1) = prev.dimensions();
+    let (w2, h2) = curr.dimensions();
+
+    if w1 != w2 || h1 != h2 || w1 < 40 {
+        return 0;
+    }
+
+    let w = w1 as usize;
+    let prev_hashes = hash_all_columns_fuzzy(prev);
+    let curr_hashes = hash_all_columns_fuzzy(curr);
+
+    if prev_hashes == curr_hashes {
+        return 0;
+    }
+
+    let min_delta = 2;
+    let max_delta = w / 2;
+    // Allow a few mismatched columns (cursor, antialiasing at edges)
+    let min_match_ratio = 0.95;
+
+    let match_ratio = |prev_start: usize, curr_start: usize, overlap: usize| -> f32 {
+        let matched = (0..overlap)
+            .filter(|&i| prev_hashes[prev_start + i] == curr_hashes[curr_start + i])
+            .count();
+        matched as f32 / overlap as f32
+    };
+
+    // Smallest shift first = largest overlap = most confident match
+    for delta in min_delta..=max_delta {
+        let overlap = w - delta;
+        // Scroll RIGHT: prev[delta..w] == curr[0..w-delta]
+        if match_ratio(delta, 0, overlap) >= min_match_ratio {
+            return delta as i32;
+        }
+        // Scroll LEFT: prev[0..w-delta] == curr[delta..w]
+        if match_ratio(0, delta, overlap) >= min_match_ratio {
+            return -(delta as i32);
+        }
+    }
+
+    0
+}
+
+/// Quantized hash of every column (vertical analog of `hash_all_rows_fuzzy`)
+fn hash_all_columns_fuzzy(img: &RgbaImage) -> Vec<u64> {
+    let (w, h) = img.dimensions();
+    (0..w).map(|x| hash_column_fuzzy(img, x, h)).collect()
+}
+
+#[inline]
+fn hash_column_fuzzy(img: &RgbaImage, x: u32, h: u32) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET;
+
+    // Sample every 4th pixel, quantize to 32 levels
+    for y in (0..h).step_by(4) {
+        let p = img.get_pixel(x, y);
+        let r = (p[0] >> 3) as u64;
+        let g = (p[1] >> 3) as u64;
+        let b = (p[2] >> 3) as u64;
+
+        hash ^= r | (g << 5) | (b << 10);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,4 +389,78 @@ mod tests {
         // Should detect ~80 overlap (100 - 20)
         assert!(delta > 0, "Expected positive delta, got {}", delta);
     }
+
+    #[test]
+    fn test_scroll_right() {
+        // Distinct column pattern so every column hashes differently
+        let content = |x: u32| image::Rgba([(x * 8 % 256) as u8, (x * 3 % 256) as u8, (x / 2) as u8, 255]);
+        let prev = RgbaImage::from_fn(120, 60, |x, _y| content(x));
+        let curr = RgbaImage::from_fn(120, 60, |x, _y| content(x + 24));
+
+        assert_eq!(detect_scroll_delta_horizontal(&prev, &curr), 24);
+        assert_eq!(detect_scroll_delta_horizontal(&curr, &prev), -24);
+    }
+
+    #[test]
+    fn test_fuzzy_tolerates_small_differences() {
+        let content = |y: u32| image::Rgba([(y * 7 % 256) as u8, (y * 13 % 256) as u8, 64, 255]);
+        let prev = RgbaImage::from_fn(60, 120, |_x, y| content(y));
+        // Scrolled 15px, with +-1 noise from re-rendering that breaks exact matching
+        let curr = RgbaImage::from_fn(60, 120, |x, y| {
+            let mut p = content(y + 15);
+            if (x + y) % 3 == 0 {
+                p[0] = p[0] & !7 | (p[0] & 7).saturating_sub(1);
+            }
+            p
+        });
+
+        assert_eq!(detect_scroll_delta_fuzzy(&prev, &curr, Some(60)), 15);
+        assert_eq!(detect_scroll_delta_fuzzy(&curr, &prev, Some(60)), -15);
+        assert_eq!(detect_scroll_delta_fuzzy(&prev, &prev, Some(60)), 0);
+    }
+
+    #[test]
+    fn test_detect_moving_rect() {
+        // 12-row toolbar, 10-row footer and an 8-px sidebar around content scrolled 15px
+        let content = |x: u32, y: u32| {
+            image::Rgba([(y * 7 % 256) as u8, (y * 13 % 256) as u8, (x * 2 % 256) as u8, 255])
+        };
+        let frame = |offset: u32| {
+            RgbaImage::from_fn(80, 120, |x, y| {
+                if y < 12 || y >= 110 || x < 8 {
+                    image::Rgba([30, 30, 30, 255])
+                } else {
+                    content(x, y + offset)
+                }
+            })
+        };
+
+        assert_eq!(detect_moving_rect(&frame(0), &frame(15)), Some((8, 12, 72, 98)));
+        assert_eq!(detect_moving_rect(&frame(0), &frame(0)), None);
+    }
+
+    #[test]
+    fn test_trailing_repeat_rows() {
+        let content = |y: u32| image::Rgba([(y * 7 % 256) as u8, (y * 13 % 256) as u8, 64, 255]);
+        // 100 rows of content, then a 10-row footer repeated 3 more times
+        let img = RgbaImage::from_fn(40, 140, |_x, y| {
+            if y < 100 {
+                content(y)
+            } else {
+                content(90 + (y - 100) % 10)
+            }
+        });
+        assert_eq!(trailing_repeat_rows(&img, 64, 8), 40);
+        assert_eq!(trailing_repeat_rows(&img, 64, 50), 0);
+
+        // Solid band of 30 rows
+        let blank = RgbaImage::from_fn(40, 130, |_x, y| {
+            if y < 100 {
+                content(y)
+            } else {
+                image::Rgba([255, 255, 255, 255])
+            }
+        });
+        assert_eq!(trailing_repeat_rows(&blank, 64, 8), 29);
+    }
 }
@@ -0,0 +1,143 @@
+//! Captures scheduled for a future wall-clock time
+//!
+//! Each job gets its own timer thread that wakes at least once a second, so a
+//! cancellation takes effect promptly and system sleep doesn't push the capture late by
+//! the time spent asleep. When the job fires it runs the same `save_screenshot` path as
+//! the UI and emits `scheduled-capture-done`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::capture::primary_screen_region;
+use crate::state::SharedState;
+use crate::types::{CaptureMode, Region, ScreenshotOptions};
+
+#[derive(Clone, Serialize)]
+pub struct ScheduledCapture {
+    pub id: u64,
+    pub at_unix_ms: u64,
+    pub mode: CaptureMode, // StaticImage crops the frozen snapshot when one is cached
+    pub region: Option<Region>, // None = primary display
+}
+
+/// Payload of `scheduled-capture-done`
+#[derive(Clone, Serialize)]
+pub struct ScheduledCaptureDone {
+    pub id: u64,
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Pending jobs; a job is removed when it fires or is cancelled
+static JOBS: Mutex<Vec<ScheduledCapture>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn now_unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Remove and return the job if it is still pending
+fn take_job(id: u64) -> Option<ScheduledCapture> {
+    let mut jobs = JOBS.lock().unwrap();
+    let idx = jobs.iter().position(|j| j.id == id)?;
+    Some(jobs.remove(idx))
+}
+
+fn run_capture(app: &AppHandle, job: &ScheduledCapture) -> Result<String, String> {
+    let region = match &job.region {
+        Some(r) => r.clone(),
+        None => primary_screen_region()?,
+    };
+    // Capture the job's rect directly; the user's selection stays as it was
+    let options = ScreenshotOptions {
+        region: Some(region),
+        use_cached: job.mode == CaptureMode::StaticImage,
+        ..Default::default()
+    };
+    let state = app.state::<SharedState>();
+    crate::commands::save_screenshot(app.clone(), state, Some(options))
+}
+
+fn spawn_timer(app: AppHandle, id: u64, at_unix_ms: u64) {
+    thread::spawn(move || {
+        loop {
+            if !JOBS.lock().unwrap().iter().any(|j| j.id == id) {
+                return; // cancelled
+            }
+            let remaining = at_unix_ms.saturating_sub(now_unix_ms());
+            if remaining == 0 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(remaining.min(1000)));
+        }
+        let Some(job) = take_job(id) else {
+            return;
+        };
+
+        log::info!("[schedule] Firing job {}", id);
+        let result = run_capture(&app, &job);
+        if let Err(e) = &result {
+            log::warn!("[schedule] Job {} failed: {}", id, e);
+        }
+        let (path, error) = match result {
+            Ok(p) => (Some(p), None),
+            Err(e) => (None, Some(e)),
+        };
+        let _ = app.emit("scheduled-capture-done", ScheduledCaptureDone { id, path, error });
+    });
+}
+
+/// Schedule a still capture of `region` (primary display when omitted) at `at_unix_ms`.
+/// Returns the job id for `cancel_scheduled_capture`.
+#[tauri::command]
+pub fn schedule_capture(
+    app: AppHandle,
+    at_unix_ms: u64,
+    mode: Option<CaptureMode>,
+    region: Option<Region>,
+) -> Result<u64, String> {
+    let mode = mode.unwrap_or_default();
+    if !matches!(mode, CaptureMode::Image | CaptureMode::StaticImage) {
+        return Err("Only image captures can be scheduled".to_string());
+    }
+    if at_unix_ms <= now_unix_ms() {
+        return Err("Scheduled time is in the past".to_string());
+    }
+
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    JOBS.lock().unwrap().push(ScheduledCapture {
+        id,
+        at_unix_ms,
+        mode,
+        region,
+    });
+    log::info!("[schedule] Job {} at {}", id, at_unix_ms);
+    spawn_timer(app, id, at_unix_ms);
+    Ok(id)
+}
+
+/// Cancel a pending job; returns false if it already fired or never existed
+#[tauri::command]
+pub fn cancel_scheduled_capture(id: u64) -> bool {
+    let cancelled = take_job(id).is_some();
+    if cancelled {
+        log::info!("[schedule] Cancelled job {}", id);
+    }
+    cancelled
+}
+
+/// Pending jobs, soonest first
+#[tauri::command]
+pub fn list_scheduled_captures() -> Vec<ScheduledCapture> {
+    let mut jobs = JOBS.lock().unwrap().clone();
+    jobs.sort_by_key(|j| j.at_unix_ms);
+    jobs
+}
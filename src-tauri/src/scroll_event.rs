@@ -3,7 +3,7 @@
 //! Listens for global scroll wheel events and triggers capture when scrolling occurs.
 
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -14,26 +14,128 @@ use core_graphics::event::{
 use tauri::{AppHandle, Emitter, Manager};
 
 use crate::state::SharedState;
-use crate::types::ScrollCaptureProgress;
+use crate::types::{ScrollAxis, ScrollCaptureProgress};
 
 /// Global flag to control the event tap
 static SCROLL_LISTENER_ACTIVE: AtomicBool = AtomicBool::new(false);
 
+/// While paused, scroll events are ignored but the tap and captured frames stay alive
+static SCROLL_LISTENER_PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Set on resume so the tap closure resets its debounce/accumulator state
+static SCROLL_LISTENER_RESUMED: AtomicBool = AtomicBool::new(false);
+
+/// Auto-scroll driver running (synthesized wheel events instead of the user's)
+static AUTO_SCROLL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
 /// Count consecutive "no match" results to avoid infinite retry
 static NO_MATCH_COUNT: AtomicU64 = AtomicU64::new(0);
 
+/// Frontmost window when the listener started (or resumed); scrolls are only captured
+/// while it is still in front, so a stray click elsewhere can't corrupt the stitch
+static TARGET_WINDOW: Mutex<Option<u32>> = Mutex::new(None);
+
+/// A different window is in front; `scroll-window-changed` was already emitted for it
+static WINDOW_MISMATCH: AtomicBool = AtomicBool::new(false);
+
+/// Adaptive match search range: a multiple of the recent average delta, clamped
+const RECENT_DELTA_WINDOW: usize = 5;
+const SEARCH_RANGE_MULTIPLIER: u32 = 3;
+const MIN_SEARCH_RANGE: i32 = 80;
+const MAX_SEARCH_RANGE: i32 = 600;
+const DEFAULT_SEARCH_RANGE: i32 = 300;
+
+/// Auto-scroll treats this many unchanged frames in a row as the end of the content
+const AUTO_SCROLL_END_FRAMES: u32 = 3;
+/// ...and gives up after this many consecutive failed matches
+const AUTO_SCROLL_MAX_NO_MATCH: u32 = 5;
+/// Time for the target to repaint after a probe_scroll wheel event
+const PROBE_SETTLE_MS: u64 = 250;
+
+/// Search range for the next match given recently matched deltas
+fn adaptive_search_range(recent: &[u32]) -> i32 {
+    if recent.is_empty() {
+        return DEFAULT_SEARCH_RANGE;
+    }
+    let avg = recent.iter().sum::<u32>() / recent.len() as u32;
+    ((avg * SEARCH_RANGE_MULTIPLIER) as i32).clamp(MIN_SEARCH_RANGE, MAX_SEARCH_RANGE)
+}
+
 /// Result of scroll capture attempt
 enum CaptureResult {
     /// Successfully captured and stitched
     Success(ScrollCaptureProgress),
+    /// Stitched image exceeded scroll_max_height, capture stopped
+    LimitReached(ScrollCaptureProgress),
     /// Frames are identical - content hasn't scrolled yet
     FramesIdentical,
     /// No match found in search range
     NoMatch,
+    /// Another window took focus since capture started; nothing was captured
+    WindowChanged { target: u32, frontmost: u32 },
     /// Not in capture mode or other error
     Error,
 }
 
+/// Remember the current frontmost window as the one being captured
+fn record_target_window() {
+    let id = crate::window_detect::frontmost_window_id();
+    log::info!("[scroll_event] Target window: {:?}", id);
+    *TARGET_WINDOW.lock().unwrap() = id;
+    WINDOW_MISMATCH.store(false, Ordering::Relaxed);
+}
+
+/// (target, frontmost) when a different window is in front; unknown ids never block
+fn target_window_changed() -> Option<(u32, u32)> {
+    let target = (*TARGET_WINDOW.lock().unwrap())?;
+    let frontmost = crate::window_detect::frontmost_window_id()?;
+    if frontmost == target {
+        WINDOW_MISMATCH.store(false, Ordering::Relaxed);
+        None
+    } else {
+        Some((target, frontmost))
+    }
+}
+
+/// Count an unchanged frame; once the streak reaches the likely-complete threshold, emit
+/// `scroll-likely-complete` so the UI can offer to finish
+fn note_identical_frame(app: &AppHandle, state: &SharedState) {
+    use crate::commands::{scroll_progress_estimate, SCROLL_LIKELY_COMPLETE_FRAMES};
+
+    let estimate = {
+        let Ok(mut s) = state.lock() else {
+            return;
+        };
+        s.scroll_identical_streak += 1;
+        if s.scroll_identical_streak != SCROLL_LIKELY_COMPLETE_FRAMES {
+            return;
+        }
+        scroll_progress_estimate(&s)
+    };
+    if let Ok(estimate) = estimate {
+        log::info!("[scroll_event] likely complete at {}px", estimate.total_height);
+        let _ = app.emit("scroll-likely-complete", &estimate);
+    }
+}
+
+/// Fallback for frames the FFT matcher rejects: quantized row matching over the full search
+/// range. Vertical only; a delta against the wheel direction is treated as no match.
+fn fuzzy_scroll_delta(
+    axis: ScrollAxis,
+    prev: &image::RgbaImage,
+    curr: &image::RgbaImage,
+    expected_direction: i32,
+) -> i32 {
+    if axis != ScrollAxis::Vertical {
+        return 0;
+    }
+    let delta = crate::row_hash::detect_scroll_delta_fuzzy(prev, curr, Some(MAX_SEARCH_RANGE));
+    if expected_direction != 0 && delta.signum() != expected_direction {
+        return 0;
+    }
+    delta
+}
+
 /// Perform a single scroll capture iteration
 fn do_scroll_capture(
     state: &SharedState,
@@ -42,12 +144,25 @@ fn do_scroll_capture(
     _use_fixed_delta: bool,
 ) -> CaptureResult {
     use crate::capture::Screen;
-    use crate::commands::{generate_preview_base64, stitch_scroll_image};
-    use crate::fft_match::detect_scroll_delta_fft;
+    use crate::commands::{
+        detect_scroll_delta, frames_nearly_identical, generate_preview_base64,
+        inset_for_matching, stitch_scroll,
+    };
+    use crate::fft_match::{auto_match_downscale, downscale_for_matching};
     use image::RgbaImage;
 
     // Get required data with minimal lock time
-    let (region, last_frame, scroll_stitched) = {
+    let (
+        region,
+        last_frame,
+        scroll_stitched,
+        axis,
+        downscale,
+        tolerance,
+        search_range,
+        inset,
+        seam_blend,
+    ) = {
         let s = match state.lock() {
             Ok(s) => s,
             Err(_) => return CaptureResult::Error,
@@ -55,12 +170,26 @@ fn do_scroll_capture(
         if !s.scroll_capturing {
             return CaptureResult::Error;
         }
-        match (s.region.clone(), s.scroll_frames.last().cloned(), s.scroll_stitched.clone()) {
-            (Some(r), Some(f), Some(st)) => (r, f, st),
+        match (s.scroll_region.clone(), s.last_scroll_frame().cloned(), s.scroll_stitched.clone()) {
+            (Some(r), Some(f), Some(st)) => (
+                r,
+                f,
+                st,
+                s.scroll_axis,
+                s.scroll_match_downscale,
+                s.scroll_change_tolerance.clone(),
+                adaptive_search_range(&s.scroll_recent_deltas),
+                s.scroll_match_inset,
+                s.scroll_seam_blend,
+            ),
             _ => return CaptureResult::Error,
         }
     };
 
+    if let Some((target, frontmost)) = target_window_changed() {
+        return CaptureResult::WindowChanged { target, frontmost };
+    }
+
     // Capture new frame
     let screens = match Screen::all() {
         Ok(s) => s,
@@ -80,29 +209,55 @@ fn do_scroll_capture(
         None => return CaptureResult::Error,
     };
 
-    // Use larger search range - don't limit based on delta estimate
-    // Real scroll can be much larger than event delta suggests
-    let max_delta = 300; // Search up to 300px
+    // Match without the inset columns (scrollbar), stitch the full frame
+    let prev_match = inset_for_matching(&last_frame, inset, region.width);
+    let curr_match = inset_for_matching(&new_frame, inset, region.width);
 
-    let scroll_delta =
-        detect_scroll_delta_fft(&last_frame, &new_frame, expected_direction, Some(max_delta));
+    // Match on downscaled frames for large (4K) captures, stitch at full resolution
+    let factor = match downscale {
+        0 => auto_match_downscale(new_frame.width()),
+        f => f,
+    };
+    let match_delta = |max_delta: i32| {
+        if factor > 1 {
+            let prev_small = downscale_for_matching(&prev_match, factor);
+            let curr_small = downscale_for_matching(&curr_match, factor);
+            let small_max = (max_delta / factor as i32).max(1);
+            detect_scroll_delta(axis, &prev_small, &curr_small, expected_direction, Some(small_max))
+                * factor as i32
+        } else {
+            detect_scroll_delta(axis, &prev_match, &curr_match, expected_direction, Some(max_delta))
+        }
+    };
+
+    // Search range follows recent deltas; widen once in case this was a big jump
+    let mut scroll_delta = match_delta(search_range);
+    if scroll_delta == 0 && search_range < MAX_SEARCH_RANGE {
+        scroll_delta = match_delta(MAX_SEARCH_RANGE);
+    }
 
     if scroll_delta == 0 {
         // Check if frames are nearly identical (content hasn't moved yet)
-        let identical = frames_nearly_identical(&last_frame, &new_frame);
+        let identical = frames_nearly_identical(&prev_match, &curr_match, &tolerance);
         if identical {
             return CaptureResult::FramesIdentical;
         }
-        return CaptureResult::NoMatch;
+        // Antialiasing / subpixel text can defeat the exact match; retry with quantized rows
+        scroll_delta = fuzzy_scroll_delta(axis, &prev_match, &curr_match, expected_direction);
+        if scroll_delta == 0 {
+            return CaptureResult::NoMatch;
+        }
+        log::debug!("[scroll_event] fuzzy match delta {}", scroll_delta);
     }
 
-    println!("[scroll_event] match delta {}", scroll_delta);
+    log::debug!("[scroll_event] match delta {}", scroll_delta);
 
     // Stitch the image
-    let stitched = match stitch_scroll_image(&scroll_stitched, &new_frame, scroll_delta) {
-        Ok(s) => s,
-        Err(_) => return CaptureResult::Error,
-    };
+    let stitched =
+        match stitch_scroll(axis, &scroll_stitched, &new_frame, scroll_delta, seam_blend) {
+            Ok(s) => s,
+            Err(_) => return CaptureResult::Error,
+        };
 
     // Calculate new offset
     let last_offset = {
@@ -129,63 +284,66 @@ fn do_scroll_capture(
         return CaptureResult::Error;
     }
 
-    s.scroll_frames.push(new_frame);
-    s.scroll_offsets.push(new_offset);
+    s.push_scroll_frame(new_frame, scroll_delta, new_offset);
     s.scroll_stitched = Some(stitched);
+    s.scroll_identical_streak = 0;
+    s.scroll_recent_deltas.push(scroll_delta.unsigned_abs());
+    if s.scroll_recent_deltas.len() > RECENT_DELTA_WINDOW {
+        s.scroll_recent_deltas.remove(0);
+    }
 
     let frame_count = s.scroll_frames.len();
-    let total_height = match s.scroll_stitched.as_ref() {
-        Some(img) => img.height(),
+    let (total_width, total_height) = match s.scroll_stitched.as_ref() {
+        Some(img) => img.dimensions(),
         None => return CaptureResult::Error,
     };
 
-    CaptureResult::Success(ScrollCaptureProgress {
+    let progress = ScrollCaptureProgress {
         frame_count,
         total_height,
+        total_width,
         preview_base64: preview,
-    })
-}
-
-/// Check if two frames are nearly identical (no visible change)
-fn frames_nearly_identical(a: &image::RgbaImage, b: &image::RgbaImage) -> bool {
-    let (w, h) = a.dimensions();
-    if b.dimensions() != (w, h) {
-        return false;
-    }
+        estimated_complete: false,
+    };
 
-    // Sample a few rows from middle of image
-    let sample_rows = [h / 4, h / 2, h * 3 / 4];
-    let mut total_diff = 0u64;
-    let mut samples = 0u64;
-
-    for &y in &sample_rows {
-        for x in (0..w).step_by(4) {
-            let pa = a.get_pixel(x, y);
-            let pb = b.get_pixel(x, y);
-            let diff = (pa[0] as i32 - pb[0] as i32).unsigned_abs()
-                + (pa[1] as i32 - pb[1] as i32).unsigned_abs()
-                + (pa[2] as i32 - pb[2] as i32).unsigned_abs();
-            total_diff += diff as u64;
-            samples += 1;
-        }
+    // Endless feeds: stop once the growing dimension passes the limit
+    let extent = match axis {
+        ScrollAxis::Vertical => total_height,
+        ScrollAxis::Horizontal => total_width,
+    };
+    if s.scroll_max_height > 0 && extent > s.scroll_max_height {
+        log::info!(
+            "[scroll_event] limit reached: {}px > {}px",
+            extent, s.scroll_max_height
+        );
+        s.scroll_capturing = false;
+        return CaptureResult::LimitReached(progress);
     }
 
-    let avg_diff = total_diff as f64 / samples.max(1) as f64;
-    avg_diff < 5.0 // Very similar = content hasn't scrolled
+    CaptureResult::Success(progress)
 }
 
 /// Start listening for global scroll events
 pub fn start_scroll_listener(app: AppHandle) {
     if SCROLL_LISTENER_ACTIVE.swap(true, Ordering::SeqCst) {
-        println!("[scroll_event] Listener already active");
+        log::info!("[scroll_event] Listener already active");
         return;
     }
 
     // Reset no-match counter
     NO_MATCH_COUNT.store(0, Ordering::Relaxed);
+    SCROLL_LISTENER_PAUSED.store(false, Ordering::Relaxed);
+    SCROLL_LISTENER_RESUMED.store(false, Ordering::Relaxed);
+    record_target_window();
+
+    // Axis is fixed for the whole capture session
+    let axis = app
+        .try_state::<SharedState>()
+        .and_then(|s| s.lock().ok().map(|s| s.scroll_axis))
+        .unwrap_or_default();
 
     thread::spawn(move || {
-        println!("[scroll_event] Starting global scroll listener");
+        log::info!("[scroll_event] Starting global scroll listener");
 
         // Debounce state
         let last_capture = Arc::new(std::sync::Mutex::new(
@@ -209,16 +367,35 @@ pub fn start_scroll_listener(app: AppHandle) {
                     return None;
                 }
 
+                if SCROLL_LISTENER_PAUSED.load(Ordering::Relaxed) {
+                    return None;
+                }
+
+                // Just resumed: start from a clean debounce window
+                if SCROLL_LISTENER_RESUMED.swap(false, Ordering::Relaxed) {
+                    *last_capture_clone.lock().unwrap() = Instant::now() - Duration::from_millis(300);
+                    *scroll_accum_clone.lock().unwrap() = 0.0;
+                    *scroll_dir_clone.lock().unwrap() = 0;
+                }
+
                 // Skip if too many consecutive failures
                 if NO_MATCH_COUNT.load(Ordering::Relaxed) > 10 {
                     return None;
                 }
 
-                // Get scroll delta
-                let point_delta = event
-                    .get_double_value_field(EventField::SCROLL_WHEEL_EVENT_POINT_DELTA_AXIS_1);
-                let fixed_delta = event
-                    .get_double_value_field(EventField::SCROLL_WHEEL_EVENT_FIXED_POINT_DELTA_AXIS_1);
+                // Get scroll delta (axis 1 = vertical, axis 2 = horizontal)
+                let (point_field, fixed_field) = match axis {
+                    ScrollAxis::Vertical => (
+                        EventField::SCROLL_WHEEL_EVENT_POINT_DELTA_AXIS_1,
+                        EventField::SCROLL_WHEEL_EVENT_FIXED_POINT_DELTA_AXIS_1,
+                    ),
+                    ScrollAxis::Horizontal => (
+                        EventField::SCROLL_WHEEL_EVENT_POINT_DELTA_AXIS_2,
+                        EventField::SCROLL_WHEEL_EVENT_FIXED_POINT_DELTA_AXIS_2,
+                    ),
+                };
+                let point_delta = event.get_double_value_field(point_field);
+                let fixed_delta = event.get_double_value_field(fixed_field);
                 let is_continuous = event
                     .get_integer_value_field(EventField::SCROLL_WHEEL_EVENT_IS_CONTINUOUS);
 
@@ -276,19 +453,41 @@ pub fn start_scroll_listener(app: AppHandle) {
                             CaptureResult::Success(progress) => {
                                 NO_MATCH_COUNT.store(0, Ordering::Relaxed);
                                 let _ = app_clone.emit("scroll-preview-update", &progress);
-                                println!(
-                                    "[scroll_event] frame {} height {}",
-                                    progress.frame_count, progress.total_height
+                                log::debug!(
+                                    "[scroll_event] frame {} size {}x{}",
+                                    progress.frame_count, progress.total_width, progress.total_height
                                 );
                             }
+                            CaptureResult::LimitReached(progress) => {
+                                SCROLL_LISTENER_ACTIVE.store(false, Ordering::SeqCst);
+                                let _ = app_clone.emit("scroll-preview-update", &progress);
+                                let _ = app_clone.emit("scroll-limit-reached", &progress);
+                            }
                             CaptureResult::FramesIdentical => {
                                 // Content hasn't moved yet - wait longer
                                 NO_MATCH_COUNT.fetch_add(1, Ordering::Relaxed);
+                                note_identical_frame(&app_clone, &state);
                             }
                             CaptureResult::NoMatch => {
                                 // Couldn't match - maybe dynamic content
                                 NO_MATCH_COUNT.fetch_add(1, Ordering::Relaxed);
-                                println!("[scroll_event] no match");
+                                log::debug!("[scroll_event] no match");
+                            }
+                            CaptureResult::WindowChanged { target, frontmost } => {
+                                // Scrolls in another window: skip without counting a failure
+                                if !WINDOW_MISMATCH.swap(true, Ordering::Relaxed) {
+                                    log::info!(
+                                        "[scroll_event] window {} in front of target {}",
+                                        frontmost, target
+                                    );
+                                    let _ = app_clone.emit(
+                                        "scroll-window-changed",
+                                        serde_json::json!({
+                                            "target_window_id": target,
+                                            "frontmost_window_id": frontmost,
+                                        }),
+                                    );
+                                }
                             }
                             CaptureResult::Error => {
                                 // Capture mode ended or other error
@@ -314,7 +513,7 @@ pub fn start_scroll_listener(app: AppHandle) {
                     tap.enable();
                     let _ = app.emit("scroll-listener-started", ());
 
-                    println!("[scroll_event] Scroll listener started successfully");
+                    log::info!("[scroll_event] Scroll listener started successfully");
 
                     while SCROLL_LISTENER_ACTIVE.load(Ordering::Relaxed) {
                         CFRunLoop::run_in_mode(
@@ -327,11 +526,11 @@ pub fn start_scroll_listener(app: AppHandle) {
                     run_loop.remove_source(&source, kCFRunLoopDefaultMode);
                 }
 
-                println!("[scroll_event] Scroll listener stopped");
+                log::info!("[scroll_event] Scroll listener stopped");
             }
             Err(e) => {
-                eprintln!("[scroll_event] Failed to create event tap: {:?}", e);
-                eprintln!("[scroll_event] This requires Accessibility permission");
+                log::error!("[scroll_event] Failed to create event tap: {:?}", e);
+                log::error!("[scroll_event] This requires Accessibility permission");
                 SCROLL_LISTENER_ACTIVE.store(false, Ordering::Relaxed);
                 let _ = app.emit("scroll-listener-failed", ());
             }
@@ -341,6 +540,183 @@ pub fn start_scroll_listener(app: AppHandle) {
 
 /// Stop the global scroll listener
 pub fn stop_scroll_listener() {
-    println!("[scroll_event] Stopping scroll listener");
+    log::info!("[scroll_event] Stopping scroll listener");
     SCROLL_LISTENER_ACTIVE.store(false, Ordering::SeqCst);
+    SCROLL_LISTENER_PAUSED.store(false, Ordering::SeqCst);
+    AUTO_SCROLL_ACTIVE.store(false, Ordering::SeqCst);
+}
+
+/// Forget consecutive match failures (discarded captures start fresh)
+pub fn reset_no_match_count() {
+    NO_MATCH_COUNT.store(0, Ordering::Relaxed);
+}
+
+/// Temporarily ignore scroll events without tearing down the tap
+pub fn pause_scroll_listener() {
+    log::info!("[scroll_event] Pausing scroll listener");
+    SCROLL_LISTENER_PAUSED.store(true, Ordering::SeqCst);
+}
+
+/// Resume after pause_scroll_listener, clearing failure count and debounce.
+/// The window in front now becomes the capture target.
+pub fn resume_scroll_listener() {
+    log::info!("[scroll_event] Resuming scroll listener");
+    NO_MATCH_COUNT.store(0, Ordering::Relaxed);
+    record_target_window();
+    SCROLL_LISTENER_RESUMED.store(true, Ordering::SeqCst);
+    SCROLL_LISTENER_PAUSED.store(false, Ordering::SeqCst);
+}
+
+pub fn is_scroll_listener_paused() -> bool {
+    SCROLL_LISTENER_PAUSED.load(Ordering::SeqCst)
+}
+
+/// Post one synthetic wheel event at `(x, y)` (pixel units; negative = scroll content forward)
+fn post_scroll_event(axis: ScrollAxis, amount: i32, x: f64, y: f64) -> bool {
+    use core_graphics::event::{CGEvent, ScrollEventUnit};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+    use core_graphics::geometry::CGPoint;
+
+    let Ok(source) = CGEventSource::new(CGEventSourceStateID::HIDSystemState) else {
+        return false;
+    };
+    let (wheel1, wheel2) = match axis {
+        ScrollAxis::Vertical => (amount, 0),
+        ScrollAxis::Horizontal => (0, amount),
+    };
+    let Ok(event) = CGEvent::new_scroll_event(source, ScrollEventUnit::PIXEL, 2, wheel1, wheel2, 0)
+    else {
+        return false;
+    };
+    // Wheel events go to the window under the event location, not the key window
+    event.set_location(CGPoint::new(x, y));
+    event.post(CGEventTapLocation::HID);
+    true
+}
+
+/// Grab `region` (physical px frames) before and after a synthetic scroll of `step_px`
+/// along `axis`, then scroll back. The event tap is paused so the probe isn't captured.
+pub fn probe_scroll(
+    region: &crate::types::Region,
+    axis: ScrollAxis,
+    step_px: i32,
+) -> Option<(image::RgbaImage, image::RgbaImage)> {
+    let screens = crate::capture::Screen::all().ok()?;
+    let screen = screens.first()?;
+    let grab = || screen.capture_area(region.x, region.y, region.width, region.height).ok();
+    let center_x = region.x as f64 + region.width as f64 / 2.0;
+    let center_y = region.y as f64 + region.height as f64 / 2.0;
+
+    let was_paused = SCROLL_LISTENER_PAUSED.swap(true, Ordering::SeqCst);
+    let frames = grab().and_then(|before| {
+        if !post_scroll_event(axis, -step_px, center_x, center_y) {
+            return None;
+        }
+        thread::sleep(Duration::from_millis(PROBE_SETTLE_MS));
+        let after = grab();
+        post_scroll_event(axis, step_px, center_x, center_y);
+        thread::sleep(Duration::from_millis(PROBE_SETTLE_MS));
+        Some((before, after?))
+    });
+    SCROLL_LISTENER_PAUSED.store(was_paused, Ordering::SeqCst);
+    frames
+}
+
+/// Drive a running scroll capture by synthesizing wheel events every `interval_ms`,
+/// stitching after each step until the content stops moving. The event tap is paused
+/// meanwhile so it doesn't double-capture our own events.
+pub fn start_auto_scroll(app: AppHandle, step_px: i32, interval_ms: u64) {
+    if AUTO_SCROLL_ACTIVE.swap(true, Ordering::SeqCst) {
+        log::info!("[auto_scroll] Already running");
+        return;
+    }
+    SCROLL_LISTENER_PAUSED.store(true, Ordering::SeqCst);
+    NO_MATCH_COUNT.store(0, Ordering::Relaxed);
+
+    thread::spawn(move || {
+        let Some(state) = app.try_state::<SharedState>() else {
+            AUTO_SCROLL_ACTIVE.store(false, Ordering::SeqCst);
+            return;
+        };
+        let Some((region, axis)) = state
+            .lock()
+            .ok()
+            .and_then(|s| s.scroll_region.clone().map(|r| (r, s.scroll_axis)))
+        else {
+            AUTO_SCROLL_ACTIVE.store(false, Ordering::SeqCst);
+            return;
+        };
+        let center_x = region.x as f64 + region.width as f64 / 2.0;
+        let center_y = region.y as f64 + region.height as f64 / 2.0;
+        log::info!("[auto_scroll] Started: {}px every {}ms", step_px, interval_ms);
+
+        let mut unchanged = 0;
+        let mut no_match = 0;
+        let mut last_progress = None;
+        while AUTO_SCROLL_ACTIVE.load(Ordering::Relaxed) {
+            if !post_scroll_event(axis, -step_px, center_x, center_y) {
+                log::warn!("[auto_scroll] Failed to post scroll event");
+                break;
+            }
+            thread::sleep(Duration::from_millis(interval_ms));
+            if !AUTO_SCROLL_ACTIVE.load(Ordering::Relaxed) {
+                break;
+            }
+
+            match do_scroll_capture(&state, 1, step_px as f64, false) {
+                CaptureResult::Success(progress) => {
+                    unchanged = 0;
+                    no_match = 0;
+                    let _ = app.emit("scroll-preview-update", &progress);
+                    last_progress = Some(progress);
+                }
+                CaptureResult::LimitReached(progress) => {
+                    let _ = app.emit("scroll-preview-update", &progress);
+                    let _ = app.emit("scroll-limit-reached", &progress);
+                    last_progress = None;
+                    break;
+                }
+                CaptureResult::FramesIdentical => {
+                    note_identical_frame(&app, &state);
+                    unchanged += 1;
+                    if unchanged >= AUTO_SCROLL_END_FRAMES {
+                        log::info!("[auto_scroll] Content stopped moving, end reached");
+                        break;
+                    }
+                }
+                CaptureResult::NoMatch => {
+                    no_match += 1;
+                    if no_match >= AUTO_SCROLL_MAX_NO_MATCH {
+                        log::warn!("[auto_scroll] Too many failed matches, giving up");
+                        break;
+                    }
+                }
+                CaptureResult::WindowChanged { target, frontmost } => {
+                    let _ = app.emit(
+                        "scroll-window-changed",
+                        serde_json::json!({
+                            "target_window_id": target,
+                            "frontmost_window_id": frontmost,
+                        }),
+                    );
+                    log::warn!("[auto_scroll] Target window lost focus, stopping");
+                    break;
+                }
+                CaptureResult::Error => break, // capture stopped or cancelled
+            }
+        }
+
+        let cancelled = !AUTO_SCROLL_ACTIVE.swap(false, Ordering::SeqCst);
+        SCROLL_LISTENER_PAUSED.store(false, Ordering::SeqCst);
+        SCROLL_LISTENER_RESUMED.store(true, Ordering::SeqCst);
+        if !cancelled {
+            let _ = app.emit("auto-scroll-finished", &last_progress);
+        }
+        log::info!("[auto_scroll] Stopped (cancelled: {})", cancelled);
+    });
+}
+
+/// Cancel the auto-scroll driver; manual scrolling resumes through the event tap
+pub fn stop_auto_scroll() {
+    AUTO_SCROLL_ACTIVE.store(false, Ordering::SeqCst);
 }
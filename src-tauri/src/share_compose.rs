@@ -20,7 +20,7 @@ const TEXT_MUTED: Rgba<u8> = Rgba([135, 134, 127, 255]);    // #87867F
 const ACCENT: Rgba<u8> = Rgba([204, 120, 92, 255]);         // #CC785C
 
 /// Load system font (PingFang on macOS)
-fn load_font() -> Option<FontRef<'static>> {
+pub(crate) fn load_font() -> Option<FontRef<'static>> {
     #[cfg(target_os = "macos")]
     {
         let font_paths = [
@@ -341,8 +341,347 @@ pub fn compose_share(
 
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
     let filename = output_dir.join(format!("share_{}.png", timestamp));
-    composed.save(&filename).map_err(|e| format!("Save error: {}", e))?;
+    // Sources are saved captures, already converted by the color profile; just tag them
+    write_png(&composed, &filename)?;
 
-    println!("[compose_share] Saved to {:?}", filename);
+    log::info!("[compose_share] Saved to {:?}", filename);
     Ok(filename.to_string_lossy().to_string())
 }
+
+// ============ Framing (rounded corners + drop shadow) ============
+
+/// Canvas background behind a framed capture
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FrameBackground {
+    Transparent,
+    Solid { color: [u8; 4] },
+    Gradient { from: [u8; 4], to: [u8; 4] }, // top → bottom
+}
+
+/// Options for frame_capture; all sizes in image pixels
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct FrameOptions {
+    pub corner_radius: u32,
+    pub padding: u32,
+    pub shadow_blur: f32,
+    pub shadow_offset: (i32, i32),
+    pub shadow_color: [u8; 4],
+    pub background: FrameBackground,
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        Self {
+            corner_radius: 12,
+            padding: 64,
+            shadow_blur: 20.0,
+            shadow_offset: (0, 12),
+            shadow_color: [0, 0, 0, 90],
+            background: FrameBackground::Transparent,
+        }
+    }
+}
+
+/// Anti-aliased coverage (0-1) of pixel (x, y) inside a w×h rounded rect at the origin
+fn rounded_rect_coverage(x: u32, y: u32, w: u32, h: u32, radius: u32) -> f32 {
+    let r = radius.min(w / 2).min(h / 2) as f32;
+    if r <= 0.0 {
+        return 1.0;
+    }
+    let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+    // Distance from the nearest corner circle center, only inside corner zones
+    let cx = if px < r { r } else if px > w as f32 - r { w as f32 - r } else { return 1.0 };
+    let cy = if py < r { r } else if py > h as f32 - r { h as f32 - r } else { return 1.0 };
+    let d = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+    (r - d + 0.5).clamp(0.0, 1.0)
+}
+
+/// Composite source onto a padded canvas with rounded corners and a soft shadow
+pub fn compose_framed(source: &RgbaImage, opts: &FrameOptions) -> RgbaImage {
+    let (src_w, src_h) = source.dimensions();
+    let pad = opts.padding;
+    let canvas_w = src_w + pad * 2;
+    let canvas_h = src_h + pad * 2;
+
+    // Background
+    let mut canvas = match opts.background {
+        FrameBackground::Transparent => RgbaImage::new(canvas_w, canvas_h),
+        FrameBackground::Solid { color } => RgbaImage::from_pixel(canvas_w, canvas_h, Rgba(color)),
+        FrameBackground::Gradient { from, to } => RgbaImage::from_fn(canvas_w, canvas_h, |_, y| {
+            let t = y as f32 / (canvas_h.max(2) - 1) as f32;
+            let mut c = [0u8; 4];
+            for (i, v) in c.iter_mut().enumerate() {
+                *v = (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8;
+            }
+            Rgba(c)
+        }),
+    };
+
+    // Shadow: rounded-rect alpha mask in shadow color, blurred, then blended under the image.
+    // The whole layer carries the shadow RGB so blurring only softens alpha.
+    if opts.shadow_color[3] > 0 {
+        let [r, g, b, a] = opts.shadow_color;
+        let mut shadow = RgbaImage::from_pixel(canvas_w, canvas_h, Rgba([r, g, b, 0]));
+        let (ox, oy) = opts.shadow_offset;
+        for y in 0..src_h {
+            for x in 0..src_w {
+                let sx = pad as i64 + x as i64 + ox as i64;
+                let sy = pad as i64 + y as i64 + oy as i64;
+                if sx < 0 || sy < 0 || sx >= canvas_w as i64 || sy >= canvas_h as i64 {
+                    continue;
+                }
+                let cov = rounded_rect_coverage(x, y, src_w, src_h, opts.corner_radius);
+                shadow.get_pixel_mut(sx as u32, sy as u32)[3] = (a as f32 * cov) as u8;
+            }
+        }
+        if opts.shadow_blur > 0.0 {
+            shadow = imageproc::filter::gaussian_blur_f32(&shadow, opts.shadow_blur);
+        }
+        image::imageops::overlay(&mut canvas, &shadow, 0, 0);
+    }
+
+    // Image with rounded corners
+    let mut rounded = source.clone();
+    if opts.corner_radius > 0 {
+        for (x, y, p) in rounded.enumerate_pixels_mut() {
+            let cov = rounded_rect_coverage(x, y, src_w, src_h, opts.corner_radius);
+            if cov < 1.0 {
+                p[3] = (p[3] as f32 * cov) as u8;
+            }
+        }
+    }
+    image::imageops::overlay(&mut canvas, &rounded, pad as i64, pad as i64);
+
+    canvas
+}
+
+/// Tauri command: frame the selected capture and return it as a PNG data URL (alpha kept)
+#[tauri::command]
+pub fn frame_capture(
+    state: tauri::State<crate::state::SharedState>,
+    source: Option<crate::types::ImageSource>,
+    options: Option<FrameOptions>,
+) -> Result<String, String> {
+    let base = {
+        let mut s = state.lock().unwrap();
+        crate::annotate::source_image(&mut s, source.unwrap_or_default())
+            .ok_or("No image to frame")?
+            .clone()
+    };
+
+    let options = options.unwrap_or_default();
+    log::info!("[frame_capture] {}x{} with {:?}", base.width(), base.height(), options);
+    let framed = compose_framed(&base, &options);
+    crate::annotate::encode_png_data_url(&framed)
+}
+
+// ============ Contact sheet (grid of captures) ============
+
+/// Options for make_contact_sheet; all sizes in image pixels
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct ContactSheetOptions {
+    pub cols: Option<u32>, // both None = near-square grid
+    pub rows: Option<u32>,
+    pub cell_width: Option<u32>,  // None = widest capture
+    pub cell_height: Option<u32>, // None = tallest capture
+    pub padding: u32,             // around and between cells
+    pub background: [u8; 4],
+    pub captions: Vec<String>, // per capture, in order; missing/empty = no caption
+    pub caption_size: f32,
+    pub caption_color: [u8; 4],
+    pub save: bool, // write a PNG to the output dir and return its path instead of a data URL
+}
+
+impl Default for ContactSheetOptions {
+    fn default() -> Self {
+        Self {
+            cols: None,
+            rows: None,
+            cell_width: None,
+            cell_height: None,
+            padding: 24,
+            background: BG_WARM.0,
+            captions: Vec::new(),
+            caption_size: 20.0,
+            caption_color: TEXT_DARK.0,
+            save: false,
+        }
+    }
+}
+
+/// Columns and rows holding `count` cells; rejects explicit grids that are too small
+fn contact_sheet_grid(
+    count: u32,
+    cols: Option<u32>,
+    rows: Option<u32>,
+) -> Result<(u32, u32), String> {
+    let (cols, rows) = match (cols.filter(|&c| c > 0), rows.filter(|&r| r > 0)) {
+        (Some(c), Some(r)) => (c, r),
+        (Some(c), None) => (c, count.div_ceil(c)),
+        (None, Some(r)) => (count.div_ceil(r), r),
+        (None, None) => {
+            let c = (count as f64).sqrt().ceil() as u32;
+            (c, count.div_ceil(c))
+        }
+    };
+    if cols * rows < count {
+        return Err(format!("A {}x{} grid can't hold {} captures", cols, rows, count));
+    }
+    Ok((cols, rows))
+}
+
+/// Lay `sources` out row by row, each scaled to fit (and centered in) a uniform cell
+pub fn compose_contact_sheet(
+    sources: &[RgbaImage],
+    opts: &ContactSheetOptions,
+) -> Result<RgbaImage, String> {
+    if sources.is_empty() {
+        return Err("No captures to arrange".to_string());
+    }
+    let (cols, rows) = contact_sheet_grid(sources.len() as u32, opts.cols, opts.rows)?;
+
+    let cell_w = opts
+        .cell_width
+        .unwrap_or_else(|| sources.iter().map(|s| s.width()).max().unwrap_or(1))
+        .max(1);
+    let cell_h = opts
+        .cell_height
+        .unwrap_or_else(|| sources.iter().map(|s| s.height()).max().unwrap_or(1))
+        .max(1);
+
+    let has_captions = opts.captions.iter().any(|c| !c.trim().is_empty());
+    let font = if has_captions { load_font() } else { None };
+    if has_captions && font.is_none() {
+        log::warn!("[contact_sheet] No font available, skipping captions");
+    }
+    let scale = PxScale::from(opts.caption_size.max(1.0));
+    let caption_h = if font.is_some() { (opts.caption_size * 1.5).ceil() as u32 } else { 0 };
+
+    let pad = opts.padding;
+    let slot_h = cell_h + caption_h;
+    let canvas_w = cols * cell_w + (cols + 1) * pad;
+    let canvas_h = rows * slot_h + (rows + 1) * pad;
+    let mut canvas = RgbaImage::from_pixel(canvas_w, canvas_h, Rgba(opts.background));
+
+    for (i, source) in sources.iter().enumerate() {
+        let (col, row) = (i as u32 % cols, i as u32 / cols);
+        let cell_x = pad + col * (cell_w + pad);
+        let cell_y = pad + row * (slot_h + pad);
+
+        let (src_w, src_h) = source.dimensions();
+        let fit = (cell_w as f64 / src_w as f64).min(cell_h as f64 / src_h as f64);
+        let w = ((src_w as f64 * fit).round() as u32).clamp(1, cell_w);
+        let h = ((src_h as f64 * fit).round() as u32).clamp(1, cell_h);
+        let scaled = if (w, h) == (src_w, src_h) {
+            source.clone()
+        } else {
+            image::imageops::resize(source, w, h, image::imageops::FilterType::Lanczos3)
+        };
+        let x = cell_x + (cell_w - w) / 2;
+        let y = cell_y + (cell_h - h) / 2;
+        image::imageops::overlay(&mut canvas, &scaled, x as i64, y as i64);
+
+        let (Some(font), Some(caption)) = (font.as_ref(), opts.captions.get(i)) else {
+            continue;
+        };
+        // Single line, truncated to the cell like the minimal template
+        let mut text = caption.replace('\n', " ");
+        let full_len = text.len();
+        while measure_text_width(&text, scale) > cell_w && !text.is_empty() {
+            text.pop();
+        }
+        if text.len() < full_len {
+            text.pop();
+            text.push('…');
+        }
+        if text.trim().is_empty() {
+            continue;
+        }
+        let text_x = cell_x + cell_w.saturating_sub(measure_text_width(&text, scale)) / 2;
+        let text_y = cell_y + cell_h + (caption_h - opts.caption_size as u32) / 2;
+        let color = Rgba(opts.caption_color);
+        draw_text_mut(&mut canvas, color, text_x as i32, text_y as i32, scale, font, &text);
+    }
+
+    Ok(canvas)
+}
+
+/// Tauri command: arrange captures (file paths) in a grid; returns a PNG data URL, or
+/// the saved file's path when `options.save` is set
+#[tauri::command]
+pub fn make_contact_sheet(
+    captures: Vec<String>,
+    options: Option<ContactSheetOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let sources = captures
+        .iter()
+        .map(|path| {
+            image::open(path)
+                .map(|img| img.to_rgba8())
+                .map_err(|e| format!("Failed to open {}: {}", path, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let sheet = compose_contact_sheet(&sources, &options)?;
+    log::info!(
+        "[contact_sheet] {} captures -> {}x{}",
+        sources.len(),
+        sheet.width(),
+        sheet.height()
+    );
+    if !options.save {
+        return crate::annotate::encode_png_data_url(&sheet);
+    }
+
+    let output_dir = crate::config::get_output_dir(&crate::config::load_config());
+    std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let filename = output_dir.join(format!("contact_sheet_{}.png", timestamp));
+    write_png(&sheet, &filename)?;
+    log::info!("[contact_sheet] Saved to {:?}", filename);
+    Ok(filename.to_string_lossy().to_string())
+}
+
+/// PNG with the default encoder settings and the sRGB chunk when the color profile asks
+fn write_png(img: &RgbaImage, path: &std::path::Path) -> Result<(), String> {
+    crate::commands::write_png(
+        img,
+        path,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        crate::config::load_config().color_profile.tags_srgb(),
+    )
+    .map_err(|e| format!("Save error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contact_sheet_grid() {
+        assert_eq!(contact_sheet_grid(5, None, None), Ok((3, 2)));
+        assert_eq!(contact_sheet_grid(4, None, None), Ok((2, 2)));
+        assert_eq!(contact_sheet_grid(5, Some(1), None), Ok((1, 5)));
+        assert_eq!(contact_sheet_grid(5, None, Some(2)), Ok((3, 2)));
+        assert!(contact_sheet_grid(5, Some(2), Some(2)).is_err());
+    }
+
+    #[test]
+    fn test_contact_sheet_uniform_cells() {
+        let wide = RgbaImage::from_pixel(200, 100, Rgba([255, 0, 0, 255]));
+        let tall = RgbaImage::from_pixel(50, 100, Rgba([0, 0, 255, 255]));
+        let opts = ContactSheetOptions { padding: 10, ..Default::default() };
+        let sheet = compose_contact_sheet(&[wide, tall], &opts).unwrap();
+        // Cells are 200x100 (widest x tallest), side by side
+        assert_eq!(sheet.dimensions(), (2 * 200 + 3 * 10, 100 + 2 * 10));
+        // Tall capture keeps its aspect and is centered in its cell
+        assert_eq!(sheet.get_pixel(220 + 100, 60), &Rgba([0, 0, 255, 255]));
+        assert_eq!(sheet.get_pixel(220 + 10, 60), &Rgba(BG_WARM.0));
+    }
+}
@@ -121,11 +121,27 @@ pub fn is_stop_recording_shortcut(shortcut: &Shortcut) -> bool {
     is_shortcut_for_action(shortcut, "stop_recording")
 }
 
+/// Whether `shortcut` ends a running recording: any shortcut in toggle mode,
+/// only stop_recording shortcuts otherwise
+pub fn stops_recording(shortcut: &Shortcut) -> bool {
+    config::load_config().recording_shortcut_toggle || is_stop_recording_shortcut(shortcut)
+}
+
 /// Check if a shortcut is a show_main shortcut
 pub fn is_show_main_shortcut(shortcut: &Shortcut) -> bool {
     is_shortcut_for_action(shortcut, "show_main")
 }
 
+/// Check if a shortcut is a toggle_scroll_listener shortcut
+pub fn is_toggle_scroll_listener_shortcut(shortcut: &Shortcut) -> bool {
+    is_shortcut_for_action(shortcut, "toggle_scroll_listener")
+}
+
+/// Check if a shortcut is a repeat_capture shortcut
+pub fn is_repeat_capture_shortcut(shortcut: &Shortcut) -> bool {
+    is_shortcut_for_action(shortcut, "repeat_capture")
+}
+
 /// Generic check if a shortcut matches an action
 fn is_shortcut_for_action(shortcut: &Shortcut, action: &str) -> bool {
     let config = config::load_config();
@@ -153,7 +169,7 @@ pub fn register_shortcuts_from_config(app: &AppHandle) -> Result<(), String> {
     let config = config::load_config();
 
     if let Err(e) = app.global_shortcut().unregister_all() {
-        eprintln!("[shortcuts] Failed to unregister all: {}", e);
+        log::error!("[shortcuts] Failed to unregister all: {}", e);
     }
 
     for (action, shortcuts) in &config.shortcuts {
@@ -164,7 +180,9 @@ pub fn register_shortcuts_from_config(app: &AppHandle) -> Result<(), String> {
         }
 
         // Skip scroll if not enabled (developer mode preview feature)
-        if action == "scroll" && !config.scroll_capture_enabled {
+        if (action == "scroll" || action == "toggle_scroll_listener")
+            && !config.scroll_capture_enabled
+        {
             continue;
         }
 
@@ -177,16 +195,16 @@ pub fn register_shortcuts_from_config(app: &AppHandle) -> Result<(), String> {
             match parse_shortcut(&shortcut_str) {
                 Ok(shortcut) => {
                     if let Err(e) = app.global_shortcut().register(shortcut) {
-                        eprintln!(
+                        log::error!(
                             "[shortcuts] Failed to register {} ({}): {}",
                             action, shortcut_str, e
                         );
                     } else {
-                        println!("[shortcuts] Registered {} -> {}", action, shortcut_str);
+                        log::info!("[shortcuts] Registered {} -> {}", action, shortcut_str);
                     }
                 }
                 Err(e) => {
-                    eprintln!("[shortcuts] Invalid shortcut for {}: {}", action, e);
+                    log::error!("[shortcuts] Invalid shortcut for {}: {}", action, e);
                 }
             }
         }
@@ -206,9 +224,12 @@ pub fn register_stop_shortcuts(app: &AppHandle) {
             let shortcut_str = cfg.to_shortcut_string();
             if let Ok(shortcut) = parse_shortcut(&shortcut_str) {
                 if let Err(e) = app.global_shortcut().register(shortcut) {
-                    eprintln!("[shortcuts] Failed to register stop shortcut ({}): {}", shortcut_str, e);
+                    log::error!(
+                        "[shortcuts] Failed to register stop shortcut ({}): {}",
+                        shortcut_str, e
+                    );
                 } else {
-                    println!("[shortcuts] Registered stop_recording -> {}", shortcut_str);
+                    log::info!("[shortcuts] Registered stop_recording -> {}", shortcut_str);
                 }
             }
         }
@@ -226,9 +247,12 @@ pub fn unregister_stop_shortcuts(app: &AppHandle) {
             let shortcut_str = cfg.to_shortcut_string();
             if let Ok(shortcut) = parse_shortcut(&shortcut_str) {
                 if let Err(e) = app.global_shortcut().unregister(shortcut) {
-                    eprintln!("[shortcuts] Failed to unregister stop shortcut ({}): {}", shortcut_str, e);
+                    log::error!(
+                        "[shortcuts] Failed to unregister stop shortcut ({}): {}",
+                        shortcut_str, e
+                    );
                 } else {
-                    println!("[shortcuts] Unregistered stop_recording -> {}", shortcut_str);
+                    log::info!("[shortcuts] Unregistered stop_recording -> {}", shortcut_str);
                 }
             }
         }
@@ -246,9 +270,12 @@ pub fn register_stop_scroll_shortcuts(app: &AppHandle) {
             let shortcut_str = cfg.to_shortcut_string();
             if let Ok(shortcut) = parse_shortcut(&shortcut_str) {
                 if let Err(e) = app.global_shortcut().register(shortcut) {
-                    eprintln!("[shortcuts] Failed to register stop_scroll shortcut ({}): {}", shortcut_str, e);
+                    log::error!(
+                        "[shortcuts] Failed to register stop_scroll shortcut ({}): {}",
+                        shortcut_str, e
+                    );
                 } else {
-                    println!("[shortcuts] Registered stop_scroll -> {}", shortcut_str);
+                    log::info!("[shortcuts] Registered stop_scroll -> {}", shortcut_str);
                 }
             }
         }
@@ -266,9 +293,12 @@ pub fn unregister_stop_scroll_shortcuts(app: &AppHandle) {
             let shortcut_str = cfg.to_shortcut_string();
             if let Ok(shortcut) = parse_shortcut(&shortcut_str) {
                 if let Err(e) = app.global_shortcut().unregister(shortcut) {
-                    eprintln!("[shortcuts] Failed to unregister stop_scroll shortcut ({}): {}", shortcut_str, e);
+                    log::error!(
+                        "[shortcuts] Failed to unregister stop_scroll shortcut ({}): {}",
+                        shortcut_str, e
+                    );
                 } else {
-                    println!("[shortcuts] Unregistered stop_scroll -> {}", shortcut_str);
+                    log::info!("[shortcuts] Unregistered stop_scroll -> {}", shortcut_str);
                 }
             }
         }
@@ -0,0 +1,117 @@
+//! Structural similarity (SSIM) between frames
+//!
+//! Compares local luminance, contrast and structure instead of raw pixel differences, so
+//! compression noise or subpixel jitter barely moves the score while real content changes
+//! do. Frames are downsampled first, but it still costs more than the sampled-row diff.
+
+use image::imageops::FilterType;
+use image::RgbaImage;
+
+/// Frames wider than this are shrunk (aspect kept) before comparing
+const SSIM_MAX_WIDTH: u32 = 256;
+/// Side of the square windows statistics are taken over; windows overlap by half
+const WINDOW: usize = 8;
+// Stabilizing constants for 8-bit luma, (K * 255)^2 with the paper's K1 = 0.01, K2 = 0.03
+const C1: f64 = 6.5025;
+const C2: f64 = 58.5225;
+
+/// Rec. 601 luma of `img`, downsampled to at most `SSIM_MAX_WIDTH` wide
+fn luma(img: &RgbaImage) -> (Vec<f64>, usize, usize) {
+    let (w, h) = img.dimensions();
+    let small;
+    let img = if w > SSIM_MAX_WIDTH {
+        let sh = ((h as u64 * SSIM_MAX_WIDTH as u64) / w as u64).max(1) as u32;
+        small = image::imageops::resize(img, SSIM_MAX_WIDTH, sh, FilterType::Triangle);
+        &small
+    } else {
+        img
+    };
+    let values = img
+        .pixels()
+        .map(|p| 0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64)
+        .collect();
+    (values, img.width() as usize, img.height() as usize)
+}
+
+/// Mean SSIM of `a` and `b`: 1.0 for identical frames, near 0 for unrelated ones.
+/// Frames of different sizes score 0.0.
+pub fn mean_ssim(a: &RgbaImage, b: &RgbaImage) -> f64 {
+    if a.dimensions() != b.dimensions() || a.width() == 0 || a.height() == 0 {
+        return 0.0;
+    }
+    let (la, w, h) = luma(a);
+    let (lb, _, _) = luma(b);
+    let (win_w, win_h) = (WINDOW.min(w), WINDOW.min(h));
+    let stride = (WINDOW / 2).max(1);
+
+    let mut total = 0.0;
+    let mut windows = 0u32;
+    for y0 in (0..=h - win_h).step_by(stride) {
+        for x0 in (0..=w - win_w).step_by(stride) {
+            let n = (win_w * win_h) as f64;
+            let (mut sum_a, mut sum_b) = (0.0, 0.0);
+            let (mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0);
+            for y in y0..y0 + win_h {
+                for x in x0..x0 + win_w {
+                    let (pa, pb) = (la[y * w + x], lb[y * w + x]);
+                    sum_a += pa;
+                    sum_b += pb;
+                    sum_aa += pa * pa;
+                    sum_bb += pb * pb;
+                    sum_ab += pa * pb;
+                }
+            }
+            let (mean_a, mean_b) = (sum_a / n, sum_b / n);
+            let var_a = sum_aa / n - mean_a * mean_a;
+            let var_b = sum_bb / n - mean_b * mean_b;
+            let covar = sum_ab / n - mean_a * mean_b;
+            total += ((2.0 * mean_a * mean_b + C1) * (2.0 * covar + C2))
+                / ((mean_a * mean_a + mean_b * mean_b + C1) * (var_a + var_b + C2));
+            windows += 1;
+        }
+    }
+    total / windows.max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    /// Horizontal stripes, `period` rows each, shifted down by `offset`
+    fn stripes(w: u32, h: u32, period: u32, offset: u32) -> RgbaImage {
+        RgbaImage::from_fn(w, h, |_, y| {
+            let v = if ((y + offset) / period) & 1 == 0 { 30 } else { 220 };
+            Rgba([v, v, v, 255])
+        })
+    }
+
+    #[test]
+    fn test_identical_frames_score_one() {
+        let a = stripes(64, 64, 5, 0);
+        assert!((mean_ssim(&a, &a) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_noise_scores_above_moved_content() {
+        let a = stripes(64, 64, 5, 0);
+        // Mild per-pixel noise, like recompression
+        let noisy = RgbaImage::from_fn(64, 64, |x, y| {
+            let mut p = *a.get_pixel(x, y);
+            let n = ((x * 7 + y * 13) % 7) as u8;
+            p[0] = p[0].saturating_add(n);
+            p[1] = p[1].saturating_add(n);
+            p[2] = p[2].saturating_add(n);
+            p
+        });
+        let moved = stripes(64, 64, 5, 3);
+        let noisy_score = mean_ssim(&a, &noisy);
+        assert!(noisy_score > 0.95, "noisy {}", noisy_score);
+        assert!(mean_ssim(&a, &moved) < 0.5);
+    }
+
+    #[test]
+    fn test_size_mismatch_scores_zero() {
+        assert_eq!(mean_ssim(&stripes(8, 8, 2, 0), &stripes(8, 9, 2, 0)), 0.0);
+    }
+}
@@ -1,26 +1,197 @@
-use crate::types::{CaptureMode, Region};
-use image::RgbaImage;
+use crate::config::RecordingFrameFormat;
+use crate::types::{CaptureMode, Region, ScrollAxis, ScrollChangeTolerance, ScrollMatchInset};
+use image::buffer::ConvertBuffer;
+use image::{RgbImage, RgbaImage};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Capture rate a recording starts at (the memory-cap policy may lower it mid-recording)
+pub const DEFAULT_RECORDING_FPS: u32 = 30;
+pub const DEFAULT_SCROLL_MAX_HEIGHT: u32 = 20000;
+pub const DEFAULT_SCROLL_SEAM_BLEND: u32 = 4;
+
+/// One buffered recording frame, stored as RGB when alpha isn't needed
+#[derive(Clone)]
+pub enum RecordedFrame {
+    Rgba(RgbaImage),
+    Rgb(RgbImage),
+}
+
+impl RecordedFrame {
+    pub fn new(img: RgbaImage, format: RecordingFrameFormat) -> Self {
+        match format {
+            RecordingFrameFormat::Rgba => Self::Rgba(img),
+            RecordingFrameFormat::Rgb => Self::Rgb(img.convert()),
+        }
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        match self {
+            Self::Rgba(img) => img.dimensions(),
+            Self::Rgb(img) => img.dimensions(),
+        }
+    }
+
+    /// Packed pixel data (RGB or RGBA, row-major)
+    pub fn as_raw(&self) -> &[u8] {
+        match self {
+            Self::Rgba(img) => img.as_raw(),
+            Self::Rgb(img) => img.as_raw(),
+        }
+    }
+
+    /// Bytes of pixel data held
+    pub fn byte_len(&self) -> u64 {
+        self.as_raw().len() as u64
+    }
+
+    /// The frame as RGBA for export (borrowed when already RGBA, alpha 255 otherwise)
+    pub fn to_rgba(&self) -> Cow<'_, RgbaImage> {
+        match self {
+            Self::Rgba(img) => Cow::Borrowed(img),
+            Self::Rgb(img) => Cow::Owned(img.convert()),
+        }
+    }
+}
 
 pub struct AppState {
     pub recording: bool,
     pub region: Option<Region>,
-    pub frames: Vec<RgbaImage>,
+    pub regions: Vec<Region>, // accumulated selections for batch capture
+    pub capture_padding: i32, // px added around `region` on each side when capturing (< 0 = inset)
+    pub region_history: Vec<(Option<u32>, Region)>, // (display id, region), newest first
+    pub frames: Vec<RecordedFrame>,
+    pub frames_bytes: u64, // approximate pixel bytes held by `frames`
+    pub frames_started_at: Option<Instant>, // when the current buffer started filling
     pub recording_fps: u32,
+    pub recording_max_duration_secs: Option<u32>, // auto-stop after this long (from first frame)
+    pub recording_track_window: bool, // region follows recording_window_id as it moves
+    pub recording_window_id: Option<u32>, // CGWindowID (macOS)
+    pub interval_capturing: bool, // timelapse capture into `frames`
     pub screen_x: i32,
     pub screen_y: i32,
     pub screen_scale: f32,
     pub pending_mode: Option<CaptureMode>,
+    pub last_pending_mode: Option<CaptureMode>, // mode of the last selector session (repeat_last_capture)
     pub screen_snapshot: Option<String>,       // base64 for frontend display
     pub cached_snapshot: Option<RgbaImage>,    // raw image for cropping (static mode)
+    pub display_snapshots: HashMap<u32, RgbaImage>, // frozen displays by index (use_cached_display)
+    pub display_snapshots_generation: u64, // bumped on clear; stale background caches are dropped
     pub magnifier_snapshot: Option<String>,    // base64 screenshot for magnifier
+    pub last_capture: Option<RgbaImage>,       // most recent saved still capture (for quick_save)
+    pub last_capture_mode: Option<CaptureMode>,
     pub shortcuts_paused_for_editing: bool,
     pub shortcuts_paused_for_tray_menu: bool,
     // Scroll capture state
     pub scroll_capturing: bool,
-    pub scroll_frames: Vec<RgbaImage>,
+    pub scroll_frames: Vec<RgbaImage>, // full frames, or only revealed strips (scroll_store_strips)
+    pub scroll_last_frame: Option<RgbaImage>, // latest full frame while strips are stored
+    pub scroll_store_strips: bool, // keep just the newly revealed part of each frame
     pub scroll_offsets: Vec<i32>, // cumulative scroll offset for each frame
     pub scroll_stitched: Option<RgbaImage>, // the stitched result
+    pub scroll_axis: ScrollAxis,
+    pub scroll_max_height: u32, // auto-stop once the stitched image grows past this
+    pub scroll_match_downscale: u32, // matching downscale factor, 0 = auto by frame width
+    pub scroll_change_tolerance: ScrollChangeTolerance,
+    pub scroll_match_inset: ScrollMatchInset,
+    pub scroll_seam_blend: u32, // rows cross-faded at each vertical stitch seam, 0 = pixel-exact
+    pub scroll_recent_deltas: Vec<u32>, // recent matched |delta|s, size the search range
+    pub scroll_identical_streak: u32, // consecutive unchanged frames, hints the end of the page
+    pub scroll_region: Option<Region>, // padded region, fixed when the scroll capture starts
+}
+
+impl AppState {
+    /// The selected region with `capture_padding` applied: what capture paths grab
+    pub fn effective_region(&self) -> Option<Region> {
+        self.region
+            .as_ref()
+            .map(|r| crate::capture::pad_region(r, self.capture_padding))
+    }
+
+    /// Record a matched scroll frame at `offset` after scrolling `delta` px. With
+    /// `scroll_store_strips` only the strip the scroll revealed is kept in `scroll_frames`
+    /// (the stitched image already holds the rest) and the full frame goes to
+    /// `scroll_last_frame`; otherwise the frame is moved into `scroll_frames` as is.
+    pub fn push_scroll_frame(&mut self, frame: RgbaImage, delta: i32, offset: i32) {
+        self.scroll_offsets.push(offset);
+        if !self.scroll_store_strips {
+            self.scroll_frames.push(frame);
+            self.scroll_last_frame = None;
+            return;
+        }
+        let stored = if delta != 0 {
+            revealed_strip(&frame, self.scroll_axis, delta)
+        } else {
+            frame.clone()
+        };
+        self.scroll_frames.push(stored);
+        self.scroll_last_frame = Some(frame);
+    }
+
+    /// Latest full scroll frame, the one the next capture is matched against
+    pub fn last_scroll_frame(&self) -> Option<&RgbaImage> {
+        self.scroll_last_frame.as_ref().or_else(|| self.scroll_frames.last())
+    }
+
+    /// Forget frozen displays; a cache_all_displays still running won't store its result
+    pub fn clear_display_snapshots(&mut self) {
+        self.display_snapshots.clear();
+        self.display_snapshots_generation += 1;
+    }
+
+    /// Drop everything a capture left behind (buffers, selection, snapshots, pending mode,
+    /// running flags) as on a fresh launch. Runtime tuning like padding, fps and the scroll
+    /// settings is kept.
+    pub fn reset_capture_data(&mut self) {
+        let defaults = AppState::default();
+        self.recording = false;
+        self.interval_capturing = false;
+        self.region = None;
+        self.regions.clear();
+        self.frames.clear();
+        self.frames_bytes = 0;
+        self.frames_started_at = None;
+        self.recording_window_id = None;
+        self.pending_mode = None;
+        self.last_pending_mode = None;
+        self.screen_snapshot = None;
+        self.cached_snapshot = None;
+        self.clear_display_snapshots();
+        self.magnifier_snapshot = None;
+        self.last_capture = None;
+        self.last_capture_mode = None;
+        self.screen_x = defaults.screen_x;
+        self.screen_y = defaults.screen_y;
+        self.screen_scale = defaults.screen_scale;
+        self.scroll_capturing = false;
+        self.scroll_frames.clear();
+        self.scroll_last_frame = None;
+        self.scroll_offsets.clear();
+        self.scroll_stitched = None;
+        self.scroll_recent_deltas.clear();
+        self.scroll_identical_streak = 0;
+        self.scroll_region = None;
+    }
+}
+
+/// The part of `frame` that a scroll of `delta` px brought into view: the trailing rows
+/// (columns) for a forward scroll, the leading ones for a backward scroll
+fn revealed_strip(frame: &RgbaImage, axis: ScrollAxis, delta: i32) -> RgbaImage {
+    let (w, h) = frame.dimensions();
+    let forward = delta > 0;
+    let (x, y, sw, sh) = match axis {
+        ScrollAxis::Vertical => {
+            let n = delta.unsigned_abs().min(h);
+            (0, if forward { h - n } else { 0 }, w, n)
+        }
+        ScrollAxis::Horizontal => {
+            let n = delta.unsigned_abs().min(w);
+            (if forward { w - n } else { 0 }, 0, n, h)
+        }
+    };
+    image::imageops::crop_imm(frame, x, y, sw, sh).to_image()
 }
 
 impl Default for AppState {
@@ -28,21 +199,46 @@ impl Default for AppState {
         Self {
             recording: false,
             region: None,
+            regions: Vec::new(),
+            capture_padding: 0,
+            region_history: Vec::new(),
             frames: Vec::new(),
-            recording_fps: 30,
+            frames_bytes: 0,
+            frames_started_at: None,
+            recording_fps: DEFAULT_RECORDING_FPS,
+            recording_max_duration_secs: None,
+            recording_track_window: false,
+            recording_window_id: None,
+            interval_capturing: false,
             screen_x: 0,
             screen_y: 0,
             screen_scale: 1.0,
             pending_mode: None,
+            last_pending_mode: None,
             screen_snapshot: None,
             cached_snapshot: None,
+            display_snapshots: HashMap::new(),
+            display_snapshots_generation: 0,
             magnifier_snapshot: None,
+            last_capture: None,
+            last_capture_mode: None,
             shortcuts_paused_for_editing: false,
             shortcuts_paused_for_tray_menu: false,
             scroll_capturing: false,
             scroll_frames: Vec::new(),
+            scroll_last_frame: None,
+            scroll_store_strips: false,
             scroll_offsets: Vec::new(),
             scroll_stitched: None,
+            scroll_axis: ScrollAxis::Vertical,
+            scroll_max_height: DEFAULT_SCROLL_MAX_HEIGHT,
+            scroll_match_downscale: 0,
+            scroll_change_tolerance: ScrollChangeTolerance::default(),
+            scroll_match_inset: ScrollMatchInset::default(),
+            scroll_seam_blend: DEFAULT_SCROLL_SEAM_BLEND,
+            scroll_recent_deltas: Vec::new(),
+            scroll_identical_streak: 0,
+            scroll_region: None,
         }
     }
 }
@@ -1,14 +1,23 @@
-use crate::capture::Screen;
+use crate::capture::{logical_to_physical, Screen};
 use crate::config;
 use tauri::image::Image as TauriImage;
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem};
 use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder};
 
+use crate::state::SharedState;
 use crate::types::Region;
 
-/// Build tray menu with current shortcuts from config
+/// Build tray menu with current shortcuts from config; while a recording runs the
+/// capture entries are disabled and "Stop Recording" leads the capture section
 pub fn build_tray_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error> {
     let cfg = config::load_config();
+    let recording = app
+        .try_state::<SharedState>()
+        .map(|state| {
+            let s = state.lock().unwrap();
+            s.recording || s.interval_capturing
+        })
+        .unwrap_or(false);
     // Get first enabled shortcut for each action (for tray menu display)
     let screenshot_shortcut = cfg
         .shortcuts
@@ -40,24 +49,36 @@ pub fn build_tray_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error
         .and_then(|v| v.first())
         .map(|s| s.to_shortcut_string())
         .unwrap_or_else(|| "Alt+O".to_string());
+    let stop_shortcut = cfg
+        .shortcuts
+        .get("stop_recording")
+        .and_then(|v| v.first())
+        .map(|s| s.to_shortcut_string());
 
     let menu_show = MenuItem::with_id(app, "show", "Show Lovshot", true, Some(show_main_shortcut.as_str()))?;
     let menu_devtools = MenuItem::with_id(app, "devtools", "Toggle DevTools", true, Some("Alt+Shift+I"))?;
     let menu_sep0 = PredefinedMenuItem::separator(app)?;
+    let menu_stop = MenuItem::with_id(
+        app,
+        "stop_recording",
+        "Stop Recording",
+        true,
+        stop_shortcut.as_deref(),
+    )?;
     let menu_screenshot = MenuItem::with_id(
         app,
         "screenshot",
         "Screenshot",
-        true,
+        !recording,
         Some(screenshot_shortcut.as_str()),
     )?;
     let menu_gif =
-        MenuItem::with_id(app, "gif", "Record GIF", true, Some(gif_shortcut.as_str()))?;
+        MenuItem::with_id(app, "gif", "Record GIF", !recording, Some(gif_shortcut.as_str()))?;
     let menu_scroll = MenuItem::with_id(
         app,
         "scroll",
         "Scroll Capture",
-        cfg.developer_mode && cfg.scroll_capture_enabled,
+        !recording && cfg.developer_mode && cfg.scroll_capture_enabled,
         Some(scroll_shortcut.as_str()),
     )?;
     let menu_video = MenuItem::with_id(
@@ -75,24 +96,25 @@ pub fn build_tray_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error
     let menu_sep3 = PredefinedMenuItem::separator(app)?;
     let menu_quit = MenuItem::with_id(app, "quit", "Quit Lovshot", true, None::<&str>)?;
 
-    Menu::with_items(
-        app,
-        &[
-            &menu_show,
-            &menu_devtools,
-            &menu_sep0,
-            &menu_screenshot,
-            &menu_gif,
-            &menu_scroll,
-            &menu_video,
-            &menu_sep1,
-            &menu_settings,
-            &menu_sep2,
-            &menu_about,
-            &menu_sep3,
-            &menu_quit,
-        ],
-    )
+    let mut items: Vec<&dyn IsMenuItem<tauri::Wry>> = vec![
+        &menu_show,
+        &menu_devtools,
+        &menu_sep0,
+        &menu_screenshot,
+        &menu_gif,
+        &menu_scroll,
+        &menu_video,
+        &menu_sep1,
+        &menu_settings,
+        &menu_sep2,
+        &menu_about,
+        &menu_sep3,
+        &menu_quit,
+    ];
+    if recording {
+        items.insert(3, &menu_stop);
+    }
+    Menu::with_items(app, &items)
 }
 
 /// Update tray menu with current config (call after shortcut changes)
@@ -118,8 +140,9 @@ pub fn load_tray_icon(is_recording: bool) -> Option<TauriImage<'static>> {
     Some(TauriImage::new_owned(rgba.into_raw(), width, height))
 }
 
-/// Update tray icon (recording state)
+/// Update tray icon and menu (recording state)
 pub fn update_tray_icon(app: &AppHandle, is_recording: bool) {
+    update_tray_menu(app);
     if let Some(icon) = load_tray_icon(is_recording) {
         if let Some(tray) = app.tray_by_id("main") {
             let _ = tray.set_icon(Some(icon));
@@ -133,6 +156,14 @@ pub fn update_tray_icon(app: &AppHandle, is_recording: bool) {
     }
 }
 
+/// Point at the finished recording from the tray when no window is shown
+pub fn show_recording_ready(app: &AppHandle, frame_count: usize) {
+    if let Some(tray) = app.tray_by_id("main") {
+        let tooltip = format!("Lovshot - Recording ready ({} frames), open from menu", frame_count);
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
 /// Create recording border overlay window
 pub fn create_recording_overlay(app: &AppHandle, region: &Region, static_mode: bool) {
     if app.get_webview_window("recording-overlay").is_some() {
@@ -170,13 +201,11 @@ pub fn create_recording_overlay(app: &AppHandle, region: &Region, static_mode: b
         .build();
 
     if let Ok(win) = win {
-        let physical_width = (width as f32 * scale) as u32;
-        let physical_height = (height as f32 * scale) as u32;
-        let physical_x = (screen_x as f32 * scale) as i32;
-        let physical_y = (screen_y as f32 * scale) as i32;
+        let display = Region { x: screen_x, y: screen_y, width, height };
+        let physical = logical_to_physical(&display, (0, 0), scale);
 
-        let _ = win.set_size(PhysicalSize::new(physical_width, physical_height));
-        let _ = win.set_position(PhysicalPosition::new(physical_x, physical_y));
+        let _ = win.set_size(PhysicalSize::new(physical.width, physical.height));
+        let _ = win.set_position(PhysicalPosition::new(physical.x, physical.y));
         let _ = win.set_ignore_cursor_events(true);
 
         #[cfg(target_os = "macos")]
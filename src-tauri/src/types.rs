@@ -19,6 +19,10 @@ pub struct SaveResult {
     pub success: bool,
     pub path: Option<String>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub frame_count: Option<usize>, // frames actually written (GIF export only)
+    #[serde(default)]
+    pub note: Option<String>, // format tradeoff for recording exports
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -33,6 +37,79 @@ pub struct ExportConfig {
     #[serde(default = "default_speed")]
     pub speed: f32, // playback speed (affects duration, not frame count)
     pub output_path: Option<String>, // custom output path from Finder dialog
+    #[serde(default)]
+    pub loop_count: Option<u16>, // total plays, overrides loop_mode (1 = once, 0 = infinite)
+    #[serde(default)]
+    pub dither: bool, // Floyd-Steinberg dithering against the quantized palette
+    #[serde(default)]
+    pub dedup_threshold: Option<f32>, // merge near-identical consecutive frames (avg RGB diff)
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub watermark: Option<ExportWatermark>, // falls back to the export_watermark setting
+}
+
+/// One of nine spots an overlay is pinned to
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    #[default]
+    BottomRight,
+}
+
+impl OverlayAnchor {
+    /// (column, row) in a 3x3 grid, 0 = left/top, 2 = right/bottom
+    pub fn grid(self) -> (u32, u32) {
+        match self {
+            OverlayAnchor::TopLeft => (0, 0),
+            OverlayAnchor::Top => (1, 0),
+            OverlayAnchor::TopRight => (2, 0),
+            OverlayAnchor::Left => (0, 1),
+            OverlayAnchor::Center => (1, 1),
+            OverlayAnchor::Right => (2, 1),
+            OverlayAnchor::BottomLeft => (0, 2),
+            OverlayAnchor::Bottom => (1, 2),
+            OverlayAnchor::BottomRight => (2, 2),
+        }
+    }
+}
+
+/// What an export watermark stamps
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatermarkContent {
+    Image { path: String }, // PNG/JPEG logo, drawn at its own size (shrunk to fit)
+    Text { text: String, font_size: f32, color: [u8; 4] },
+}
+
+/// Logo or text composited onto exported copies; cached captures stay unmarked
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportWatermark {
+    pub content: WatermarkContent,
+    #[serde(default)]
+    pub anchor: OverlayAnchor,
+    #[serde(default = "default_watermark_opacity")]
+    pub opacity: f32, // 0.0 - 1.0
+    #[serde(default = "default_watermark_margin")]
+    pub margin: u32, // px from the anchored edges
+    #[serde(default)]
+    pub all_frames: bool, // animations: stamp every frame, not just the first
+}
+
+fn default_watermark_opacity() -> f32 {
+    0.8
+}
+
+fn default_watermark_margin() -> u32 {
+    16
 }
 
 fn default_quality() -> u32 {
@@ -43,6 +120,13 @@ fn default_speed() -> f32 {
     1.0
 }
 
+/// Result of export_frame_sequence
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FrameSequenceExport {
+    pub count: usize,
+    pub directory: String,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct RecordingInfo {
     pub frame_count: usize,
@@ -53,6 +137,27 @@ pub struct RecordingInfo {
     pub has_frames: bool,
 }
 
+/// Frame buffer snapshot (get_recording_buffer_info)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecordingBufferInfo {
+    pub frame_count: usize,
+    pub width: u32, // per frame; every frame in a buffer shares the size
+    pub height: u32,
+    pub total_bytes: u64, // approximate pixel bytes held
+    pub max_bytes: u64,   // recording_max_memory_mb cap, 0 = unlimited
+    pub elapsed_ms: u64,  // capture time so far; the clip length once stopped
+    pub recording: bool,
+}
+
+/// Achieved recording rate, measured from capture timestamps (emitted ~1/s)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecordingStats {
+    pub fps: f32,        // frames actually captured per second over the last window
+    pub target_fps: u32, // nominal recording_fps
+    pub frame_count: usize,
+    pub elapsed_ms: u64, // since the first frame
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SizeEstimate {
     pub frame_count: usize,
@@ -77,6 +182,51 @@ pub enum GifLoopMode {
     PingPong,
 }
 
+/// Container for recording exports
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Gif,  // 256-color palette per frame, smallest files
+    Apng, // lossless true color + alpha, larger files
+}
+
+/// Codec for export_video's MP4 output
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoCodec {
+    #[default]
+    H264, // plays everywhere
+    H265, // ~half the size at the same quality, newer players only
+}
+
+/// Options for export_video (ffmpeg sidecar)
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct VideoExportOptions {
+    #[serde(default)]
+    pub start_frame: Option<usize>,
+    #[serde(default)]
+    pub end_frame: Option<usize>, // exclusive
+    #[serde(default)]
+    pub codec: VideoCodec,
+    #[serde(default)]
+    pub crf: Option<u32>, // 0-51, lower = better; codec default when None
+    #[serde(default)]
+    pub fps: Option<u32>, // output frame rate; the recording's rate when None
+    #[serde(default)]
+    pub output_scale: Option<f32>, // 0.1-1.0
+    #[serde(default)]
+    pub output_path: Option<String>,
+}
+
+/// Direction for convert_coords
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CoordConversion {
+    LogicalToPhysical, // global points -> display-relative physical px
+    PhysicalToLogical, // display-relative physical px -> global points
+}
+
 /// Capture mode: image (screenshot), gif, video, or scroll (scrolling screenshot)
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -89,12 +239,258 @@ pub enum CaptureMode {
     Scroll,
 }
 
+/// Direction scroll capture stitches along
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollAxis {
+    #[default]
+    Vertical,
+    Horizontal,
+}
+
+/// Which backend-held image an edit command operates on
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageSource {
+    #[default]
+    LastCapture,    // most recent saved still capture
+    ScrollStitched, // scroll capture result (in progress or stopped)
+    Snapshot,       // frozen full-screen snapshot (static mode)
+}
+
+/// Annotation drawn onto a capture; coordinates are image pixels, colors RGBA
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnnotationOp {
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color: [u8; 4],
+        #[serde(default = "default_stroke_width")]
+        stroke_width: f32,
+        #[serde(default)]
+        filled: bool,
+    },
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        color: [u8; 4],
+        #[serde(default = "default_stroke_width")]
+        stroke_width: f32,
+        #[serde(default)]
+        arrow: bool, // draw an arrow head at `to`
+    },
+    Path {
+        points: Vec<(f32, f32)>,
+        color: [u8; 4],
+        #[serde(default = "default_stroke_width")]
+        stroke_width: f32,
+    },
+    Text {
+        x: f32,
+        y: f32,
+        text: String,
+        font_size: f32,
+        color: [u8; 4],
+    },
+}
+
+fn default_stroke_width() -> f32 {
+    3.0
+}
+
+/// How redact_region obscures a sub-rectangle
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum RedactMode {
+    Blur { radius: f32 },
+    Pixelate { block_size: u32 },
+}
+
+/// Orientation fix applied by transform_capture (rotations are clockwise)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageTransform {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+/// One channel of a capture_histogram result
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChannelHistogram {
+    pub buckets: Vec<u64>, // 256 counts, indexed by channel value
+    pub mean: f64,
+    pub min: u8,
+    pub max: u8,
+}
+
+/// Per-channel histograms and stats of a capture or a sub-region of it
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CaptureHistogram {
+    pub width: u32, // size of the measured area (px)
+    pub height: u32,
+    pub red: ChannelHistogram,
+    pub green: ChannelHistogram,
+    pub blue: ChannelHistogram,
+    pub alpha: ChannelHistogram,
+}
+
+/// Ancillary metadata written into saved PNGs
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PngMetadata {
+    #[default]
+    None,      // no ancillary chunks at all
+    Timestamp, // tEXt "Creation Time" + "Software"
+}
+
+/// zlib effort for saved PNGs (default Best: smallest files)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PngCompression {
+    Fast,
+    #[default]
+    Best,
+}
+
+/// Per-row PNG filter; Adaptive picks the best one for each row
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PngFilter {
+    #[default]
+    Adaptive,
+    None,
+    Sub,
+    Up,
+    Avg,
+    Paeth,
+}
+
+/// Optional settings for save_screenshot; any field may be omitted
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScreenshotOptions {
+    pub scale: Option<f32>, // output scale, 0.1-1.0 (default 1.0)
+    pub use_cached: bool,   // crop the frozen snapshot instead of grabbing the live screen
+    pub caption_mode: bool, // open the caption window instead of the preview
+    pub png_metadata: PngMetadata,
+    pub png_compression: PngCompression,
+    pub png_filter: PngFilter,
+    pub grayscale: bool, // desaturate the saved/copied image (last_capture keeps color)
+    pub region: Option<Region>, // capture this rect instead of the selection (left untouched)
+}
+
+/// One side of merge_captures: a backend-held image or a (data URL) base64 image
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MergeInput {
+    Source { source: ImageSource },
+    Base64(String),
+}
+
+/// Result of diff_captures
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CaptureDiff {
+    pub image_base64: String, // PNG data URL: second image faded, changed pixels in magenta
+    pub width: u32,
+    pub height: u32,
+    pub changed_pixels: u64,
+    pub percent_changed: f32, // 0-100
+}
+
 /// Progress info for scroll capture preview
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ScrollCaptureProgress {
     pub frame_count: usize,
     pub total_height: u32,
+    pub total_width: u32, // grows instead of height for horizontal capture
     pub preview_base64: String,
+    #[serde(default)]
+    pub estimated_complete: bool, // recent scrolls left the content unchanged: likely at the end
+}
+
+/// Heuristic scroll capture progress (estimate_scroll_progress); the page's real length
+/// is unknown, so "complete" means several scroll attempts in a row changed nothing
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScrollProgressEstimate {
+    pub frame_count: usize,
+    pub total_height: u32,
+    pub total_width: u32,
+    pub identical_streak: u32, // consecutive captures with no content movement
+    pub estimated_complete: bool,
+}
+
+/// How two frames are compared to decide that content has not changed
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameCompare {
+    #[default]
+    Sampled, // average RGB diff over a few sampled rows (fast)
+    Ssim,    // structural similarity of downsampled frames; shrugs off compression noise
+}
+
+/// How the scroll listener decides that content has not moved between frames
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrollChangeTolerance {
+    pub threshold: f32,        // average per-pixel RGB diff below which frames count as identical
+    pub sample_rows: Vec<f32>, // rows to compare, as fractions of frame height (0.0-1.0)
+    pub sample_step: u32,      // compare every Nth pixel along each row
+    pub compare: FrameCompare, // Ssim ignores the three fields above and costs more CPU
+    pub ssim_threshold: f32,   // Ssim: mean SSIM at or above which frames count as identical
+}
+
+impl Default for ScrollChangeTolerance {
+    fn default() -> Self {
+        Self {
+            threshold: 5.0,
+            sample_rows: vec![0.25, 0.5, 0.75],
+            sample_step: 4,
+            compare: FrameCompare::Sampled,
+            ssim_threshold: 0.97,
+        }
+    }
+}
+
+/// Columns left out of scroll matching (logical px from each side), so a scrollbar that
+/// fades in and out doesn't break the match. Stitching still uses the full frame.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrollMatchInset {
+    pub left: u32,
+    pub right: u32,
+}
+
+impl Default for ScrollMatchInset {
+    fn default() -> Self {
+        Self { left: 0, right: 16 }
+    }
+}
+
+/// What finish_scroll_capture does when the image exceeds max_export_width/height
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OversizePolicy {
+    #[default]
+    Warn,  // write nothing and keep the capture, so the caller can ask the user
+    Split, // several files cut at frame seams along the scroll axis
+    Allow, // one file regardless of size
+}
+
+/// Result of finish_scroll_capture
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScrollExportResult {
+    pub paths: Vec<String>, // written files; empty when oversized under OversizePolicy::Warn
+    pub width: u32,
+    pub height: u32,
+    pub oversized: bool,
+    pub max_width: u32,
+    pub max_height: u32,
 }
 
 /// Crop edges for scroll capture (percentage from each edge, 0-100)
@@ -106,6 +502,68 @@ pub struct CropEdges {
     pub right: f32,
 }
 
+/// Zoomed-pixel data around the cursor for the selector loupe
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LoupeData {
+    pub image_base64: String, // PNG data URL, size x size physical pixels
+    pub size: u32,
+    pub center_color: String, // "#RRGGBB"
+    pub center_x: u32,        // physical pixel coords of the center pixel
+    pub center_y: u32,
+}
+
+/// One recognized line of text; box in image pixels (top-left origin)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OcrTextBlock {
+    pub text: String,
+    pub confidence: f32, // 0.0-1.0
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where a stored region sits relative to the current displays
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionVisibility {
+    Visible,   // entirely on the displays (possibly spanning several)
+    Clipped,   // partly off-screen
+    Offscreen, // no display overlaps it
+}
+
+/// Result of validate_region
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RegionValidation {
+    pub visibility: RegionVisibility,
+    pub suggested: Option<Region>, // clamped onto a display; None when already visible
+}
+
+/// Named selection computed from the display under the cursor (apply_region_preset)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RegionPreset {
+    Full,
+    LeftHalf,
+    RightHalf,
+    TopHalf,
+    BottomHalf,
+    TopLeftQuarter,
+    TopRightQuarter,
+    BottomLeftQuarter,
+    BottomRightQuarter,
+    Centered { width: u32, height: u32 }, // logical px, clamped to the display
+}
+
+/// One remembered selection for quick re-selection
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RegionHistoryItem {
+    pub index: usize, // pass to select_region_from_history
+    pub region: Region,
+    pub display_id: Option<u32>,
+    pub thumbnail: Option<String>, // PNG data URL cropped from the cached snapshot, if any
+}
+
 /// Extended window info including titlebar height
 #[derive(Clone, Serialize, Deserialize)]
 pub struct WindowInfo {
@@ -114,4 +572,6 @@ pub struct WindowInfo {
     pub width: u32,
     pub height: u32,
     pub titlebar_height: u32,
+    #[serde(default)]
+    pub window_id: Option<u32>, // CGWindowID (macOS)
 }
@@ -0,0 +1,157 @@
+//! Upload a capture to a user-configured HTTP endpoint and return its public URL
+//!
+//! Bytes are streamed from disk (or from an in-memory PNG, never base64) with
+//! `upload-progress` events so large scroll captures show progress.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::state::SharedState;
+use crate::types::ImageSource;
+
+#[derive(Clone, Serialize)]
+pub struct UploadProgress {
+    pub sent: u64,
+    pub total: u64,
+}
+
+/// Reader wrapper that reports progress roughly every percent
+struct ProgressReader<R> {
+    inner: R,
+    app: AppHandle,
+    sent: u64,
+    total: u64,
+    last_emitted: u64,
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sent += n as u64;
+        let step = (self.total / 100).max(64 * 1024);
+        if n == 0 || self.sent - self.last_emitted >= step {
+            self.last_emitted = self.sent;
+            let _ = self.app.emit(
+                "upload-progress",
+                UploadProgress {
+                    sent: self.sent,
+                    total: self.total,
+                },
+            );
+        }
+        Ok(n)
+    }
+}
+
+/// Pull the public URL out of the response: JSON `url`/`link`, Location, plain-text body,
+/// or (PUT with an empty body) the request URL minus its signing query
+fn extract_url(
+    response: ureq::Response,
+    request_url: &str,
+    is_put: bool,
+) -> Result<String, String> {
+    let location = response.header("location").map(str::to_string);
+    let body = response.into_string().map_err(|e| e.to_string())?;
+    let body = body.trim();
+
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(body) {
+        for key in ["url", "link", "location"] {
+            if let Some(url) = json.get(key).and_then(|v| v.as_str()) {
+                return Ok(url.to_string());
+            }
+        }
+    }
+    if let Some(url) = location {
+        return Ok(url);
+    }
+    if body.starts_with("http://") || body.starts_with("https://") {
+        return Ok(body.to_string());
+    }
+    if is_put {
+        return Ok(request_url.split('?').next().unwrap_or(request_url).to_string());
+    }
+    Err("Upload succeeded but the response contained no URL".to_string())
+}
+
+/// Upload a saved file (`path`) or a backend-held image (`source`) and return its URL
+#[tauri::command]
+pub async fn upload_capture(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    path: Option<String>,
+    source: Option<ImageSource>,
+) -> Result<String, String> {
+    let cfg = crate::config::load_config();
+    let endpoint = cfg.upload_endpoint.clone().ok_or("No upload endpoint configured")?;
+
+    // A saved file streams from disk; otherwise encode the in-memory image once
+    let (reader, total, filename): (Box<dyn Read + Send>, u64, String) = match path {
+        Some(p) => {
+            let path = PathBuf::from(&p);
+            let file = std::fs::File::open(&path).map_err(|e| format!("Open {}: {}", p, e))?;
+            let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+            let filename = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "capture.png".to_string());
+            (Box::new(file), total, filename)
+        }
+        None => {
+            let img = {
+                let mut s = state.lock().unwrap();
+                crate::annotate::source_image(&mut s, source.unwrap_or_default())
+                    .ok_or("No image to upload")?
+                    .clone()
+            };
+            let mut png_data = Vec::new();
+            img.write_to(&mut std::io::Cursor::new(&mut png_data), image::ImageFormat::Png)
+                .map_err(|e| e.to_string())?;
+            let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+            let filename = format!("lovshot_{}.png", timestamp);
+            let total = png_data.len() as u64;
+            (Box::new(std::io::Cursor::new(png_data)), total, filename)
+        }
+    };
+
+    let url = endpoint.replace("{filename}", &urlencoding::encode(&filename));
+    let is_put = cfg.upload_method.eq_ignore_ascii_case("put");
+    let content_type = if filename.to_lowercase().ends_with(".gif") {
+        "image/gif"
+    } else {
+        "image/png"
+    };
+    log::info!("[upload_capture] {} {} ({} bytes)", cfg.upload_method, url, total);
+
+    tokio::task::spawn_blocking(move || {
+        let mut request = (if is_put { ureq::put(&url) } else { ureq::post(&url) })
+            .set("Content-Type", content_type)
+            .set("X-Filename", &filename);
+        if total > 0 {
+            request = request.set("Content-Length", &total.to_string());
+        }
+        if let Some(token) = cfg.upload_token.as_deref() {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+
+        let body = ProgressReader {
+            inner: reader,
+            app,
+            sent: 0,
+            total,
+            last_emitted: 0,
+        };
+        match request.send(body) {
+            Ok(response) => extract_url(response, &url, is_put),
+            Err(ureq::Error::Status(code, response)) => {
+                let detail = response.into_string().unwrap_or_default();
+                Err(format!("Upload failed: HTTP {} {}", code, detail.trim()))
+            }
+            Err(e) => Err(format!("Upload failed: {}", e)),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
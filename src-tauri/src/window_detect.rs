@@ -17,6 +17,7 @@ pub struct WindowInfo {
     pub width: u32,
     pub height: u32,
     pub titlebar_height: u32,
+    pub window_id: u32, // CGWindowID, for isolated window capture
 }
 
 /// Get the window bounds under the cursor position
@@ -179,6 +180,47 @@ fn get_dock_region() -> Option<Region> {
     }
 }
 
+/// Usable part of `display` (logical, top-left origin): NSScreen's visibleFrame, which
+/// leaves out the menu bar and a visible Dock. None if no screen matches `display`.
+pub fn visible_frame(display: &Region) -> Option<Region> {
+    use core_graphics::geometry::CGRect;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let screens: *mut objc::runtime::Object = msg_send![class!(NSScreen), screens];
+        let count: usize = msg_send![screens, count];
+        if count == 0 {
+            return None;
+        }
+        // Cocoa's origin is the bottom-left of the primary screen
+        let primary: *mut objc::runtime::Object = msg_send![screens, objectAtIndex: 0usize];
+        let primary_frame: CGRect = msg_send![primary, frame];
+        let to_top_left = |r: CGRect| Region {
+            x: r.origin.x.round() as i32,
+            y: (primary_frame.size.height - (r.origin.y + r.size.height)).round() as i32,
+            width: r.size.width.round() as u32,
+            height: r.size.height.round() as u32,
+        };
+
+        let cx = display.x + display.width as i32 / 2;
+        let cy = display.y + display.height as i32 / 2;
+        for i in 0..count {
+            let screen: *mut objc::runtime::Object = msg_send![screens, objectAtIndex: i];
+            let frame: CGRect = msg_send![screen, frame];
+            let frame = to_top_left(frame);
+            let inside = cx >= frame.x
+                && cy >= frame.y
+                && cx < frame.x + frame.width as i32
+                && cy < frame.y + frame.height as i32;
+            if inside {
+                let visible: CGRect = msg_send![screen, visibleFrame];
+                return Some(to_top_left(visible));
+            }
+        }
+        None
+    }
+}
+
 unsafe fn get_number_from_dict(dict: CFDictionaryRef, key: &CFString) -> Option<f64> {
     let ptr =
         core_foundation::dictionary::CFDictionaryGetValue(dict, key.as_CFTypeRef() as *const _);
@@ -337,7 +379,7 @@ fn get_app_name_from_pid(pid: i32) -> Option<String> {
 /// Get titlebar height based on app name presets + AX fallback
 fn get_titlebar_height_for_window(pid: i32, win_bounds: (f64, f64, f64, f64)) -> u32 {
     let app_name = get_app_name_from_pid(pid);
-    println!("[titlebar] pid={}, app={:?}", pid, app_name);
+    log::info!("[titlebar] pid={}, app={:?}", pid, app_name);
 
     // Preset heights for known apps (titlebar + tabs/toolbar for browsers)
     if let Some(ref name) = app_name {
@@ -385,7 +427,7 @@ fn get_titlebar_height_for_window(pid: i32, win_bounds: (f64, f64, f64, f64)) ->
         {
             // Try AX detection for native apps
             if let Some(h) = try_ax_detection(pid, win_bounds) {
-                println!("[titlebar] AX detected height: {}", h);
+                log::info!("[titlebar] AX detected height: {}", h);
                 return h;
             }
             return 52; // Standard toolbar height
@@ -394,7 +436,7 @@ fn get_titlebar_height_for_window(pid: i32, win_bounds: (f64, f64, f64, f64)) ->
 
     // Try AX detection for unknown apps
     if let Some(h) = try_ax_detection(pid, win_bounds) {
-        println!("[titlebar] AX fallback height: {}", h);
+        log::info!("[titlebar] AX fallback height: {}", h);
         return h;
     }
 
@@ -714,12 +756,16 @@ pub fn get_window_info_at_position(x: f64, y: f64) -> Option<WindowInfo> {
                     28
                 };
 
+                let id_key = CFString::new("kCGWindowNumber");
+                let window_id = get_number_from_dict(dict_ref, &id_key).unwrap_or(0.0) as u32;
+
                 return Some(WindowInfo {
                     x: win_x as i32,
                     y: win_y as i32,
                     width: win_w as u32,
                     height: win_h as u32,
                     titlebar_height,
+                    window_id,
                 });
             }
         }
@@ -837,3 +883,216 @@ pub fn activate_window_at_position(x: f64, y: f64) -> bool {
         false
     }
 }
+
+/// Current bounds of the window with `window_id`, or None once it is closed or hidden
+pub fn window_bounds_by_id(window_id: u32) -> Option<Region> {
+    use core_graphics::display::kCGWindowListOptionIncludingWindow;
+
+    unsafe {
+        let window_list = CGWindowListCopyWindowInfo(kCGWindowListOptionIncludingWindow, window_id);
+        if window_list.is_null() {
+            return None;
+        }
+        let windows: core_foundation::array::CFArray<CFType> =
+            core_foundation::array::CFArray::wrap_under_get_rule(window_list as _);
+        let window = windows.get(0)?;
+        let dict_ref = window.as_CFTypeRef() as CFDictionaryRef;
+
+        // Closed windows can linger in the list briefly; minimized ones are off screen.
+        // The key is a CFBoolean and only present when true.
+        let onscreen_key = CFString::new("kCGWindowIsOnscreen");
+        let onscreen_ptr = core_foundation::dictionary::CFDictionaryGetValue(
+            dict_ref,
+            onscreen_key.as_CFTypeRef() as *const _,
+        );
+        if onscreen_ptr.is_null()
+            || !bool::from(core_foundation::boolean::CFBoolean::wrap_under_get_rule(
+                onscreen_ptr as _,
+            ))
+        {
+            return None;
+        }
+
+        let bounds_key = CFString::new("kCGWindowBounds");
+        let bounds_ptr = core_foundation::dictionary::CFDictionaryGetValue(
+            dict_ref,
+            bounds_key.as_CFTypeRef() as *const _,
+        );
+        if bounds_ptr.is_null() {
+            return None;
+        }
+        let bounds_dict = bounds_ptr as CFDictionaryRef;
+        let x = get_number_from_dict(bounds_dict, &CFString::new("X"))?;
+        let y = get_number_from_dict(bounds_dict, &CFString::new("Y"))?;
+        let w = get_number_from_dict(bounds_dict, &CFString::new("Width"))?;
+        let h = get_number_from_dict(bounds_dict, &CFString::new("Height"))?;
+        Some(Region {
+            x: x as i32,
+            y: y as i32,
+            width: w as u32,
+            height: h as u32,
+        })
+    }
+}
+
+/// Id of the frontmost normal (layer 0) window owned by another app; our own overlays
+/// are skipped so they never count as the focused window
+pub fn frontmost_window_id() -> Option<u32> {
+    let own_pid = std::process::id() as f64;
+    unsafe {
+        let window_list =
+            CGWindowListCopyWindowInfo(kCGWindowListOptionOnScreenOnly, kCGNullWindowID);
+        if window_list.is_null() {
+            return None;
+        }
+
+        let windows: core_foundation::array::CFArray<CFType> =
+            core_foundation::array::CFArray::wrap_under_get_rule(window_list as _);
+        let layer_key = CFString::new("kCGWindowLayer");
+        let pid_key = CFString::new("kCGWindowOwnerPID");
+        let number_key = CFString::new("kCGWindowNumber");
+
+        for i in 0..windows.len() {
+            let Some(window) = windows.get(i) else {
+                continue;
+            };
+            let dict_ref = window.as_CFTypeRef() as CFDictionaryRef;
+            if get_number_from_dict(dict_ref, &layer_key).unwrap_or(0.0) as i32 != 0 {
+                continue;
+            }
+            if get_number_from_dict(dict_ref, &pid_key) == Some(own_pid) {
+                continue;
+            }
+            if let Some(id) = get_number_from_dict(dict_ref, &number_key) {
+                return Some(id as u32);
+            }
+        }
+    }
+    None
+}
+
+/// IDs of all on-screen windows (any layer) not owned by this process, front to back
+pub fn foreign_window_ids() -> Vec<u32> {
+    let own_pid = std::process::id() as f64;
+    let mut ids = Vec::new();
+    unsafe {
+        let window_list =
+            CGWindowListCopyWindowInfo(kCGWindowListOptionOnScreenOnly, kCGNullWindowID);
+        if window_list.is_null() {
+            return ids;
+        }
+
+        let windows: core_foundation::array::CFArray<CFType> =
+            core_foundation::array::CFArray::wrap_under_get_rule(window_list as _);
+        let pid_key = CFString::new("kCGWindowOwnerPID");
+        let number_key = CFString::new("kCGWindowNumber");
+
+        for i in 0..windows.len() {
+            let Some(window) = windows.get(i) else {
+                continue;
+            };
+            let dict_ref = window.as_CFTypeRef() as CFDictionaryRef;
+            if get_number_from_dict(dict_ref, &pid_key) == Some(own_pid) {
+                continue;
+            }
+            if let Some(id) = get_number_from_dict(dict_ref, &number_key) {
+                ids.push(id as u32);
+            }
+        }
+    }
+    ids
+}
+
+/// Bounds of all on-screen normal (layer 0) windows, front to back
+pub fn list_window_bounds() -> Vec<Region> {
+    let mut bounds = Vec::new();
+    unsafe {
+        let window_list =
+            CGWindowListCopyWindowInfo(kCGWindowListOptionOnScreenOnly, kCGNullWindowID);
+        if window_list.is_null() {
+            return bounds;
+        }
+
+        let windows: core_foundation::array::CFArray<CFType> =
+            core_foundation::array::CFArray::wrap_under_get_rule(window_list as _);
+        let layer_key = CFString::new("kCGWindowLayer");
+        let bounds_key = CFString::new("kCGWindowBounds");
+        let x_key = CFString::new("X");
+        let y_key = CFString::new("Y");
+        let width_key = CFString::new("Width");
+        let height_key = CFString::new("Height");
+
+        for i in 0..windows.len() {
+            let Some(window) = windows.get(i) else {
+                continue;
+            };
+            let dict_ref = window.as_CFTypeRef() as CFDictionaryRef;
+            if get_number_from_dict(dict_ref, &layer_key).unwrap_or(0.0) as i32 != 0 {
+                continue;
+            }
+
+            let bounds_ptr = core_foundation::dictionary::CFDictionaryGetValue(
+                dict_ref,
+                bounds_key.as_CFTypeRef() as *const _,
+            );
+            if bounds_ptr.is_null() {
+                continue;
+            }
+            let bounds_dict = bounds_ptr as CFDictionaryRef;
+            let (Some(x), Some(y), Some(w), Some(h)) = (
+                get_number_from_dict(bounds_dict, &x_key),
+                get_number_from_dict(bounds_dict, &y_key),
+                get_number_from_dict(bounds_dict, &width_key),
+                get_number_from_dict(bounds_dict, &height_key),
+            ) else {
+                continue;
+            };
+            if w >= 1.0 && h >= 1.0 {
+                bounds.push(Region {
+                    x: x as i32,
+                    y: y as i32,
+                    width: w as u32,
+                    height: h as u32,
+                });
+            }
+        }
+    }
+    bounds
+}
+
+/// Nearest candidate to `value` within `threshold`, if any
+fn nearest_edge(value: i32, candidates: &[i32], threshold: i32) -> Option<i32> {
+    candidates
+        .iter()
+        .copied()
+        .filter(|c| (c - value).abs() <= threshold)
+        .min_by_key(|c| (c - value).abs())
+}
+
+/// Move each edge of `region` to the closest window edge within `threshold` (logical px).
+/// Edges with nothing nearby stay put; a snap that would collapse the region is dropped.
+pub fn snap_region_to_windows(region: &Region, threshold: u32) -> Region {
+    let windows = list_window_bounds();
+    let xs: Vec<i32> = windows.iter().flat_map(|w| [w.x, w.x + w.width as i32]).collect();
+    let ys: Vec<i32> = windows.iter().flat_map(|w| [w.y, w.y + w.height as i32]).collect();
+    let t = threshold as i32;
+
+    let left = nearest_edge(region.x, &xs, t).unwrap_or(region.x);
+    let top = nearest_edge(region.y, &ys, t).unwrap_or(region.y);
+    let right_orig = region.x + region.width as i32;
+    let bottom_orig = region.y + region.height as i32;
+    let right = nearest_edge(right_orig, &xs, t).unwrap_or(right_orig);
+    let bottom = nearest_edge(bottom_orig, &ys, t).unwrap_or(bottom_orig);
+
+    let (x, width) = if right > left {
+        (left, (right - left) as u32)
+    } else {
+        (region.x, region.width)
+    };
+    let (y, height) = if bottom > top {
+        (top, (bottom - top) as u32)
+    } else {
+        (region.y, region.height)
+    };
+    Region { x, y, width, height }
+}
@@ -1,11 +1,46 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
+use crate::types::ImageSource;
+
+/// Policy last applied by set_activation_policy (-1 = not set yet)
+static ACTIVATION_POLICY: AtomicI64 = AtomicI64::new(-1);
+
+/// Activation policy currently in effect (-1 before the first set_activation_policy)
+pub fn current_activation_policy() -> i64 {
+    ACTIVATION_POLICY.load(Ordering::SeqCst)
+}
+
+/// Switch to Accessory for GIF/Video capture, unless disabled via
+/// `accessory_during_capture` (dashboard stays visible then). Returns whether it hid.
+pub fn hide_main_for_capture(app: &AppHandle) -> bool {
+    if !crate::config::load_config().accessory_during_capture {
+        log::info!("[activation_policy] accessory_during_capture off, keeping dashboard");
+        return false;
+    }
+    hide_main_window(app);
+    true
+}
+
+/// Hide the dashboard and switch back to Accessory (menu bar only)
+pub fn hide_main_window(app: &AppHandle) {
+    if let Some(main_win) = app.get_webview_window("main") {
+        let _ = main_win.hide();
+    }
+    set_activation_policy(1);
+}
+
 /// Set macOS activation policy
 /// policy: 0 = Regular (normal app, shows in Dock when windows open)
 ///         1 = Accessory (menu bar app, no Dock icon)
+/// Redundant calls are skipped: re-applying the same policy makes the Dock icon flicker.
 #[cfg(target_os = "macos")]
 pub fn set_activation_policy(policy: i64) {
     use objc::{class, msg_send, sel, sel_impl};
+    if ACTIVATION_POLICY.swap(policy, Ordering::SeqCst) == policy {
+        return;
+    }
     unsafe {
         let ns_app: *mut objc::runtime::Object =
             msg_send![class!(NSApplication), sharedApplication];
@@ -50,7 +85,7 @@ fn set_dock_icon() {
 
                 if image != nil {
                     let _: () = msg_send![ns_app, setApplicationIconImage: image];
-                    println!("[dock] Set dock icon from bundle: {}", icon_path);
+                    log::info!("[dock] Set dock icon from bundle: {}", icon_path);
                     return;
                 }
             }
@@ -58,14 +93,17 @@ fn set_dock_icon() {
             // Try relative path (for dev mode)
             let cwd = std::env::current_dir().ok();
             if let Some(cwd) = cwd {
-                println!("[dock] cwd: {:?}", cwd);
+                log::info!("[dock] cwd: {:?}", cwd);
                 // Try both with and without src-tauri prefix
                 let paths_to_try = [
                     cwd.join("src-tauri").join(icon_path),
                     cwd.join(icon_path),
                 ];
                 for dev_path in &paths_to_try {
-                    println!("[dock] Trying dev path: {:?} exists={}", dev_path, dev_path.exists());
+                    log::info!(
+                        "[dock] Trying dev path: {:?} exists={}",
+                        dev_path, dev_path.exists()
+                    );
                     if dev_path.exists() {
                         if let Ok(bytes) = std::fs::read(dev_path) {
                             let data: id = NSData::dataWithBytes_length_(
@@ -76,7 +114,7 @@ fn set_dock_icon() {
                             let image: id = NSImage::initWithData_(NSImage::alloc(nil), data);
                             if image != nil {
                                 let _: () = msg_send![ns_app, setApplicationIconImage: image];
-                                println!("[dock] Set dock icon from dev path: {:?}", dev_path);
+                                log::info!("[dock] Set dock icon from dev path: {:?}", dev_path);
                                 return;
                             }
                         }
@@ -85,12 +123,14 @@ fn set_dock_icon() {
             }
         }
 
-        println!("[dock] Warning: Could not find icon file");
+        log::warn!("[dock] Could not find icon file");
     }
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn set_activation_policy(_policy: i64) {}
+pub fn set_activation_policy(policy: i64) {
+    ACTIVATION_POLICY.store(policy, Ordering::SeqCst);
+}
 
 /// Open the settings window
 pub fn open_settings_window(app: AppHandle) -> Result<(), String> {
@@ -200,17 +240,17 @@ pub fn open_permission_window(app: &AppHandle) -> Result<(), String> {
 
 /// Open the screenshot preview window (bottom-right corner, auto-close)
 pub fn open_preview_window(app: &AppHandle, image_path: &str) -> Result<(), String> {
-    println!("[preview] Opening preview window for: {}", image_path);
+    log::info!("[preview] Opening preview window for: {}", image_path);
 
     // Close existing preview window if any
     if let Some(win) = app.get_webview_window("preview") {
-        println!("[preview] Closing existing preview window");
+        log::info!("[preview] Closing existing preview window");
         let _ = win.destroy();
     }
 
     // Get screen size to position window in bottom-right
     let monitors = app.available_monitors().map_err(|e| {
-        println!("[preview] Failed to get monitors: {}", e);
+        log::warn!("[preview] Failed to get monitors: {}", e);
         e.to_string()
     })?;
     let primary = monitors.into_iter().next();
@@ -220,11 +260,11 @@ pub fn open_preview_window(app: &AppHandle, image_path: &str) -> Result<(), Stri
         // Convert physical pixels to logical pixels
         let logical_w = size.width as f64 / scale;
         let logical_h = size.height as f64 / scale;
-        println!("[preview] Screen: {}x{} physical, {}x{} logical (scale {})",
+        log::info!("[preview] Screen: {}x{} physical, {}x{} logical (scale {})",
             size.width, size.height, logical_w, logical_h, scale);
         (logical_w, logical_h)
     } else {
-        println!("[preview] No monitor found, using default 1920x1080");
+        log::warn!("[preview] No monitor found, using default 1920x1080");
         (1920.0, 1080.0)
     };
 
@@ -233,10 +273,10 @@ pub fn open_preview_window(app: &AppHandle, image_path: &str) -> Result<(), Stri
     let margin = 20.0;
     let x = screen_w - win_w - margin;
     let y = screen_h - win_h - margin - 50.0; // 50px for dock
-    println!("[preview] Window position: ({}, {})", x, y);
+    log::info!("[preview] Window position: ({}, {})", x, y);
 
     let url = format!("/preview.html?path={}", urlencoding::encode(image_path));
-    println!("[preview] URL: {}", url);
+    log::info!("[preview] URL: {}", url);
 
     let win = WebviewWindowBuilder::new(app, "preview", WebviewUrl::App(url.into()))
         .title("")
@@ -250,11 +290,11 @@ pub fn open_preview_window(app: &AppHandle, image_path: &str) -> Result<(), Stri
         .focused(false)
         .build()
         .map_err(|e| {
-            println!("[preview] Failed to create window: {}", e);
+            log::warn!("[preview] Failed to create window: {}", e);
             e.to_string()
         })?;
 
-    println!("[preview] Window created, showing...");
+    log::info!("[preview] Window created, showing...");
 
     // On macOS, we need to ensure the window is visible without activating the app
     #[cfg(target_os = "macos")]
@@ -275,7 +315,7 @@ pub fn open_preview_window(app: &AppHandle, image_path: &str) -> Result<(), Stri
     std::thread::spawn(move || {
         std::thread::sleep(std::time::Duration::from_secs(3));
         if let Some(win) = app_clone.get_webview_window("preview") {
-            println!("[preview] Auto-closing preview window");
+            log::info!("[preview] Auto-closing preview window");
             let _ = win.destroy();
         }
     });
@@ -283,6 +323,76 @@ pub fn open_preview_window(app: &AppHandle, image_path: &str) -> Result<(), Stri
     Ok(())
 }
 
+/// Open the borderless review window for a backend-held capture, sized to fit the image
+/// within the primary screen. The page loads pixels itself via `get_capture_rgba`.
+pub fn open_review_window(
+    app: &AppHandle,
+    source: ImageSource,
+    img_w: u32,
+    img_h: u32,
+) -> Result<(), String> {
+    if let Some(win) = app.get_webview_window("review") {
+        let _ = win.destroy();
+    }
+
+    let (screen_w, screen_h) = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|m| {
+            let size = m.size();
+            let scale = m.scale_factor();
+            (size.width as f64 / scale, size.height as f64 / scale)
+        })
+        .unwrap_or((1920.0, 1080.0));
+
+    // Fit the image into 60% of the screen, leaving room for the toolbar
+    let toolbar_h = 48.0;
+    let fit = (screen_w * 0.6 / img_w.max(1) as f64)
+        .min(screen_h * 0.6 / img_h.max(1) as f64)
+        .min(1.0);
+    let win_w = (img_w as f64 * fit).max(320.0);
+    let win_h = (img_h as f64 * fit).max(180.0) + toolbar_h;
+
+    let source = serde_json::to_value(source)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let url = format!("/review.html?source={}", source);
+    log::info!("[review] Opening {} ({}x{} window)", url, win_w, win_h);
+
+    let win = WebviewWindowBuilder::new(app, "review", WebviewUrl::App(url.into()))
+        .title("")
+        .inner_size(win_w, win_h)
+        .center()
+        .resizable(true)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .focused(true)
+        .build()
+        .map_err(|e| {
+            log::warn!("[review] Failed to create window: {}", e);
+            e.to_string()
+        })?;
+
+    #[cfg(target_os = "macos")]
+    {
+        use objc::{msg_send, sel, sel_impl};
+        unsafe {
+            let ns_win: *mut objc::runtime::Object = win.ns_window().unwrap() as *mut _;
+            let _: () = msg_send![ns_win, setLevel: 3_i64]; // NSFloatingWindowLevel
+            let _: () = msg_send![ns_win, orderFrontRegardless];
+        }
+    }
+
+    let _ = win.show();
+    let _ = win.set_focus();
+
+    Ok(())
+}
+
 /// Open the about window
 pub fn open_about_window(app: AppHandle) -> Result<(), String> {
     #[cfg(target_os = "macos")]
@@ -318,7 +428,7 @@ pub fn open_about_window(app: AppHandle) -> Result<(), String> {
 
 /// Open the zoom window for full-size image viewing
 pub fn open_zoom_window(app: &AppHandle, image_path: &str) -> Result<(), String> {
-    println!("[zoom] Opening zoom window for: {}", image_path);
+    log::info!("[zoom] Opening zoom window for: {}", image_path);
 
     #[cfg(target_os = "macos")]
     {
@@ -347,7 +457,7 @@ pub fn open_zoom_window(app: &AppHandle, image_path: &str) -> Result<(), String>
         .focused(true)
         .build()
         .map_err(|e| {
-            println!("[zoom] Failed to create window: {}", e);
+            log::warn!("[zoom] Failed to create window: {}", e);
             e.to_string()
         })?;
 
@@ -359,7 +469,7 @@ pub fn open_zoom_window(app: &AppHandle, image_path: &str) -> Result<(), String>
 
 /// Open the caption preview window (centered, with input for description)
 pub fn open_caption_window(app: &AppHandle, image_path: &str, description: Option<&str>) -> Result<(), String> {
-    println!("[caption] Opening caption window for: {}", image_path);
+    log::info!("[caption] Opening caption window for: {}", image_path);
 
     #[cfg(target_os = "macos")]
     {
@@ -389,7 +499,7 @@ pub fn open_caption_window(app: &AppHandle, image_path: &str, description: Optio
         ),
         _ => format!("/preview.html?path={}&mode=caption", urlencoding::encode(image_path)),
     };
-    println!("[caption] URL: {}", url);
+    log::info!("[caption] URL: {}", url);
 
     let win = WebviewWindowBuilder::new(app, &window_label, WebviewUrl::App(url.into()))
         .title("添加描述")
@@ -402,7 +512,7 @@ pub fn open_caption_window(app: &AppHandle, image_path: &str, description: Optio
         .focused(true)
         .build()
         .map_err(|e| {
-            println!("[caption] Failed to create window: {}", e);
+            log::warn!("[caption] Failed to create window: {}", e);
             e.to_string()
         })?;
 
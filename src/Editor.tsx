@@ -22,6 +22,7 @@ interface ExportConfig {
   quality: number;
   speed: number;
   output_path: string | null;
+  format: "gif" | "apng";
 }
 
 interface SizeEstimate {
@@ -36,6 +37,7 @@ interface SaveResult {
   success: boolean;
   path: string | null;
   error: string | null;
+  note?: string | null;
 }
 
 interface ExportProgress {
@@ -44,6 +46,11 @@ interface ExportProgress {
   stage: string;
 }
 
+type VideoCodec = "h264" | "h265";
+
+// ffmpeg's own CRF defaults; lower = better quality, larger file
+const DEFAULT_CRF: Record<VideoCodec, number> = { h264: 23, h265: 28 };
+
 interface ResolutionPreset {
   label: string;
   height: number;
@@ -61,12 +68,16 @@ export default function Editor() {
     quality: 80,
     speed: 1,
     output_path: null,
+    format: "gif",
   });
   const [sizeEstimate, setSizeEstimate] = useState<SizeEstimate | null>(null);
   const [filmstrip, setFilmstrip] = useState<string[]>([]);
   const [exporting, setExporting] = useState(false);
   const [exportProgress, setExportProgress] = useState<ExportProgress | null>(null);
   const [savedPath, setSavedPath] = useState("");
+  const [hasVideoEncoder, setHasVideoEncoder] = useState(false);
+  const [videoCodec, setVideoCodec] = useState<VideoCodec>("h264");
+  const [videoCrf, setVideoCrf] = useState(DEFAULT_CRF.h264);
 
   const filmstripRef = useRef<HTMLDivElement>(null);
   const [dragging, setDragging] = useState<"start" | "end" | null>(null);
@@ -120,6 +131,7 @@ export default function Editor() {
       try {
         const info = await invoke<RecordingInfo>("get_recording_info");
         setRecordingInfo(info);
+        const defaults = await invoke<{ gif: string }>("get_default_formats").catch(() => null);
         const initialConfig: ExportConfig = {
           start_frame: 0,
           end_frame: info.frame_count,
@@ -129,6 +141,7 @@ export default function Editor() {
           quality: 80,
           speed: 1,
           output_path: null,
+          format: defaults?.gif === "apng" ? "apng" : "gif",
         };
         setExportConfig(initialConfig);
         setPreviewFrame(0);
@@ -147,6 +160,10 @@ export default function Editor() {
     loadRecordingInfo();
   }, [updateSizeEstimate]);
 
+  useEffect(() => {
+    invoke<boolean>("has_video_encoder").then(setHasVideoEncoder).catch(console.error);
+  }, []);
+
   // Listen for export events
   useEffect(() => {
     const unlistenExport = listen<SaveResult>("export-complete", (event) => {
@@ -177,9 +194,11 @@ export default function Editor() {
 
   const handleExport = async () => {
     try {
+      const isApng = exportConfig.format === "apng";
+      const ext = isApng ? "png" : "gif";
       const path = await save({
-        defaultPath: `recording_${new Date().toISOString().replace(/[:.]/g, "").slice(0, 15)}.gif`,
-        filters: [{ name: "GIF", extensions: ["gif"] }],
+        defaultPath: `recording_${new Date().toISOString().replace(/[:.]/g, "").slice(0, 15)}.${ext}`,
+        filters: [{ name: isApng ? "Animated PNG" : "GIF", extensions: [ext] }],
       });
 
       if (!path) return;
@@ -192,6 +211,33 @@ export default function Editor() {
     }
   };
 
+  const handleExportVideo = async () => {
+    try {
+      const path = await save({
+        defaultPath: `recording_${new Date().toISOString().replace(/[:.]/g, "").slice(0, 15)}.mp4`,
+        filters: [{ name: "MP4 Video", extensions: ["mp4"] }],
+      });
+
+      if (!path) return;
+
+      setExporting(true);
+      await invoke("export_video", {
+        options: {
+          start_frame: exportConfig.start_frame,
+          end_frame: exportConfig.end_frame,
+          codec: videoCodec,
+          crf: videoCrf,
+          fps: exportConfig.target_fps,
+          output_scale: exportConfig.output_scale,
+          output_path: path,
+        },
+      });
+    } catch (e) {
+      console.error("导出失败:", e);
+      setExporting(false);
+    }
+  };
+
   useEffect(() => {
     if (previewFrame === null || !recordingInfo) {
       setPreviewImage(null);
@@ -398,6 +444,56 @@ export default function Editor() {
                 <option value="pingpong">↔ Ping-pong</option>
               </select>
             </div>
+
+            <div className="control-row">
+              <label>Format</label>
+              <select
+                value={exportConfig.format}
+                onChange={(e) =>
+                  setExportConfig((c) => ({ ...c, format: e.target.value as ExportConfig["format"] }))
+                }
+                title="APNG keeps full color but is much larger than GIF"
+              >
+                <option value="gif">GIF (256 colors)</option>
+                <option value="apng">APNG (lossless)</option>
+              </select>
+            </div>
+
+            {hasVideoEncoder && (
+              <>
+                <div className="control-row">
+                  <label>MP4 Codec</label>
+                  <select
+                    value={videoCodec}
+                    onChange={(e) => {
+                      const codec = e.target.value as VideoCodec;
+                      setVideoCodec(codec);
+                      setVideoCrf(DEFAULT_CRF[codec]);
+                    }}
+                    title="H.265 is about half the size but needs a newer player"
+                  >
+                    <option value="h264">H.264</option>
+                    <option value="h265">H.265 (HEVC)</option>
+                  </select>
+                </div>
+
+                <div className="control-row">
+                  <label>MP4 CRF</label>
+                  <div className="speed-slider">
+                    <input
+                      type="range"
+                      min="0"
+                      max="51"
+                      step="1"
+                      value={videoCrf}
+                      onChange={(e) => setVideoCrf(parseInt(e.target.value))}
+                      title="Lower = better quality, larger file"
+                    />
+                    <span className="speed-value">{videoCrf}</span>
+                  </div>
+                </div>
+              </>
+            )}
           </div>
 
           {sizeEstimate && (
@@ -433,9 +529,14 @@ export default function Editor() {
                   "Exporting..."
                 )
               ) : (
-                "Export GIF"
+                exportConfig.format === "apng" ? "Export APNG" : "Export GIF"
               )}
             </button>
+            {hasVideoEncoder && (
+              <button className="btn-open" onClick={handleExportVideo} disabled={exporting}>
+                MP4
+              </button>
+            )}
             {savedPath && (
               <button
                 className="btn-open"
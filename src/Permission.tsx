@@ -43,6 +43,11 @@ export default function Permission() {
     await invoke("quit_app");
   };
 
+  // macOS only applies a new grant after a restart
+  const handleRelaunch = async () => {
+    await invoke("relaunch_app");
+  };
+
   return (
     <div className="permission-container">
       <div className="permission-icon">
@@ -76,6 +81,9 @@ export default function Permission() {
         <button className="btn-primary" onClick={handleOpenSettings}>
           打开系统设置
         </button>
+        <button className="btn-secondary" onClick={handleRelaunch}>
+          重启应用
+        </button>
         <button className="btn-secondary" onClick={handleQuit}>
           退出应用
         </button>
@@ -65,6 +65,11 @@ export default function RecordingOverlay() {
       await getCurrentWindow().close();
     });
 
+    // Recording follows a tracked window: move the frame with it
+    const unlistenMoved = listen<OverlayRegion>("recording-region-moved", (event) => {
+      setRegion(event.payload);
+    });
+
     // Listen for scroll capture stop to close (for scroll capture)
     const unlistenScroll = listen("scroll-capture-stop", async () => {
       await getCurrentWindow().close();
@@ -88,6 +93,7 @@ export default function RecordingOverlay() {
     return () => {
       unlistenRecording.then((fn) => fn());
       unlistenScroll.then((fn) => fn());
+      unlistenMoved.then((fn) => fn());
       if (handleKeyDown) {
         document.removeEventListener("keydown", handleKeyDown);
       }
@@ -0,0 +1,209 @@
+import { useState, useEffect, useRef, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { save } from "@tauri-apps/plugin-dialog";
+import type Konva from "konva";
+import { AnnotationCanvas } from "./components/AnnotationCanvas";
+import { useAnnotationEditor } from "./hooks/useAnnotationEditor";
+import type { AnnotationTool } from "./types/annotation";
+
+interface Capture {
+  width: number;
+  height: number;
+  data: Uint8Array;
+}
+
+/** Where the capture is drawn on screen, with the image as a data URL, while annotating */
+interface AnnotateView {
+  imageUrl: string;
+  left: number;
+  top: number;
+  width: number;
+  height: number;
+}
+
+const ANNOTATE_TOOLS: { tool: AnnotationTool; label: string }[] = [
+  { tool: "rect", label: "Rect" },
+  { tool: "arrow", label: "Arrow" },
+  { tool: "text", label: "Text" },
+  { tool: "mosaic", label: "Mosaic" },
+];
+
+export default function Review() {
+  const params = new URLSearchParams(window.location.search);
+  const source = params.get("source") || "last_capture";
+  const canvasRef = useRef<HTMLCanvasElement>(null);
+  const [capture, setCapture] = useState<Capture | null>(null);
+  const [status, setStatus] = useState("");
+  const [annotating, setAnnotating] = useState<AnnotateView | null>(null);
+  const stageRef = useRef<Konva.Stage>(null);
+  const editor = useAnnotationEditor();
+
+  useEffect(() => {
+    invoke<ArrayBuffer>("get_capture_rgba", { source })
+      .then((buf) => {
+        const view = new DataView(buf);
+        const width = view.getUint32(0, true);
+        const height = view.getUint32(4, true);
+        setCapture({ width, height, data: new Uint8Array(buf, 8) });
+      })
+      .catch((e) => setStatus(String(e)));
+  }, [source]);
+
+  useEffect(() => {
+    const canvas = canvasRef.current;
+    if (!canvas || !capture) return;
+    canvas.width = capture.width;
+    canvas.height = capture.height;
+    const pixels = new Uint8ClampedArray(capture.data.buffer, capture.data.byteOffset, capture.data.length);
+    canvas.getContext("2d")?.putImageData(new ImageData(pixels, capture.width, capture.height), 0, 0);
+  }, [capture]);
+
+  // Destroy only this window; the main window is left as it was
+  const close = useCallback(() => {
+    getCurrentWindow().destroy();
+  }, []);
+
+  const exitAnnotate = useCallback(() => {
+    setAnnotating(null);
+    editor.reset();
+  }, [editor]);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key !== "Escape") return;
+      if (annotating) exitAnnotate();
+      else close();
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [close, annotating, exitAnnotate]);
+
+  const handleSave = async () => {
+    if (!capture) return;
+    try {
+      if (source === "last_capture") {
+        const path = await invoke<string>("quick_save", {});
+        setStatus(`Saved ${path.split("/").pop()}`);
+        return;
+      }
+      const path = await save({ filters: [{ name: "PNG", extensions: ["png"] }] });
+      if (!path) return;
+      await invoke("save_rgba_to_file", {
+        data: Array.from(capture.data),
+        width: capture.width,
+        height: capture.height,
+        path,
+      });
+      setStatus("Saved");
+    } catch (e) {
+      setStatus(String(e));
+    }
+  };
+
+  const handleCopy = async () => {
+    if (!capture) return;
+    try {
+      await invoke("copy_rgba_to_clipboard", {
+        data: Array.from(capture.data),
+        width: capture.width,
+        height: capture.height,
+      });
+      setStatus("Copied");
+    } catch (e) {
+      setStatus(String(e));
+    }
+  };
+
+  // Annotate in place with the selector's annotation canvas, laid over the drawn image
+  const handleAnnotate = () => {
+    const canvas = canvasRef.current;
+    if (!canvas || !capture) return;
+    const rect = canvas.getBoundingClientRect();
+    setAnnotating({
+      imageUrl: canvas.toDataURL("image/png"),
+      left: rect.left,
+      top: rect.top,
+      width: rect.width,
+      height: rect.height,
+    });
+    editor.setActiveTool("rect");
+  };
+
+  // Same save path as annotating in the selector, at the capture's full resolution
+  const handleAnnotateDone = async () => {
+    const stage = stageRef.current;
+    if (!stage || !capture || !annotating) return;
+    const transformer = stage.findOne("Transformer");
+    transformer?.hide();
+    const dataUrl = stage.toDataURL({ pixelRatio: capture.width / annotating.width });
+    transformer?.show();
+    try {
+      await invoke("save_annotated_screenshot", {
+        imageData: dataUrl.replace(/^data:image\/png;base64,/, ""),
+      });
+      close();
+    } catch (e) {
+      setStatus(String(e));
+    }
+  };
+
+  return (
+    <div className="review-container">
+      <div className="review-toolbar" data-tauri-drag-region>
+        <span className="review-status" data-tauri-drag-region>
+          {status || (capture ? `${capture.width}×${capture.height}` : "Loading…")}
+        </span>
+        {annotating ? (
+          <>
+            {ANNOTATE_TOOLS.map(({ tool, label }) => (
+              <button
+                key={tool}
+                className={editor.activeTool === tool ? "active" : ""}
+                onClick={() => editor.setActiveTool(tool)}
+              >
+                {label}
+              </button>
+            ))}
+            <button onClick={editor.undo} disabled={!editor.canUndo}>Undo</button>
+            <button onClick={handleAnnotateDone} disabled={editor.annotations.length === 0}>
+              Done
+            </button>
+            <button onClick={exitAnnotate} title="Cancel (Esc)">✕</button>
+          </>
+        ) : (
+          <>
+            <button onClick={handleSave} disabled={!capture}>Save</button>
+            <button onClick={handleCopy} disabled={!capture}>Copy</button>
+            <button onClick={handleAnnotate} disabled={!capture}>Annotate</button>
+            <button onClick={close} title="Close (Esc)">✕</button>
+          </>
+        )}
+      </div>
+      <div className="review-image">
+        <canvas ref={canvasRef} />
+      </div>
+      {annotating && (
+        <AnnotationCanvas
+          imageUrl={annotating.imageUrl}
+          width={annotating.width}
+          height={annotating.height}
+          left={annotating.left}
+          top={annotating.top}
+          annotations={editor.annotations}
+          selectedId={editor.selectedId}
+          activeTool={editor.activeTool}
+          activeColor={editor.activeColor}
+          activeStyles={editor.activeStyles}
+          strokeWidth={editor.strokeWidth}
+          fontSize={editor.fontSize}
+          onAddAnnotation={editor.addAnnotation}
+          onUpdateAnnotation={editor.updateAnnotation}
+          onDeleteAnnotation={editor.deleteAnnotation}
+          onSelectAnnotation={editor.setSelectedId}
+          stageRef={stageRef}
+        />
+      )}
+    </div>
+  );
+}
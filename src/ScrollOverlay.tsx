@@ -3,25 +3,79 @@ import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { listen } from "@tauri-apps/api/event";
 import { save } from "@tauri-apps/plugin-dialog";
+import { finishScrollCapture } from "./lib/scrollExport";
 
 import "./scroll-overlay.css";
 
 interface ScrollCaptureProgress {
   frame_count: number;
   total_height: number;
+  total_width: number;
   preview_base64: string;
+  estimated_complete: boolean;
 }
 
 export default function ScrollOverlay() {
   const [progress, setProgress] = useState<ScrollCaptureProgress | null>(null);
   const [isStopped, setIsStopped] = useState(false);
   const [pollingEnabled, setPollingEnabled] = useState(true);
+  const [isAutoScrolling, setIsAutoScrolling] = useState(false);
+  const [isListenerPaused, setIsListenerPaused] = useState(false);
+  const [isWindowChanged, setIsWindowChanged] = useState(false);
+  const [isLikelyComplete, setIsLikelyComplete] = useState(false);
   const isClosingRef = useRef(false); // Prevent double-close
 
   // Listen for instant initial preview data pushed from backend
   useEffect(() => {
     const unlisten = listen<ScrollCaptureProgress>("scroll-preview-update", (event) => {
       setProgress(event.payload);
+      setIsWindowChanged(false);
+      setIsLikelyComplete(event.payload.estimated_complete);
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, []);
+
+  // Several scrolls changed nothing: probably the end of the page
+  useEffect(() => {
+    const unlisten = listen("scroll-likely-complete", () => {
+      setIsLikelyComplete(true);
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, []);
+
+  // Scrolls are skipped while another window is in front of the capture target
+  useEffect(() => {
+    const unlisten = listen("scroll-window-changed", () => {
+      setIsWindowChanged(true);
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, []);
+
+  // Backend auto-stopped at scroll_max_height
+  useEffect(() => {
+    const unlisten = listen<ScrollCaptureProgress>("scroll-limit-reached", async (event) => {
+      setProgress(event.payload);
+      await invoke("stop_scroll_capture");
+      setIsStopped(true);
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, []);
+
+  // Auto-scroll reached the end of the content (macOS)
+  useEffect(() => {
+    const unlisten = listen<ScrollCaptureProgress | null>("auto-scroll-finished", async (event) => {
+      if (event.payload) setProgress(event.payload);
+      setIsAutoScrolling(false);
+      await invoke("stop_scroll_capture");
+      setIsStopped(true);
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, []);
+
+  // Listener paused/resumed via the toggle_scroll_listener shortcut
+  useEffect(() => {
+    const unlisten = listen<boolean>("scroll-listener-toggled", (event) => {
+      setIsListenerPaused(!event.payload);
     });
     return () => { unlisten.then(fn => fn()); };
   }, []);
@@ -129,6 +183,22 @@ export default function ScrollOverlay() {
     setIsStopped(true);
   };
 
+  const handleAutoScroll = async (e: React.MouseEvent) => {
+    e.preventDefault();
+    e.stopPropagation();
+    try {
+      if (isAutoScrolling) {
+        await invoke("stop_auto_scroll_capture");
+        setIsAutoScrolling(false);
+      } else {
+        await invoke("start_auto_scroll_capture", {});
+        setIsAutoScrolling(true);
+      }
+    } catch (e) {
+      console.error("[ScrollOverlay] auto-scroll error:", e);
+    }
+  };
+
   const handleFinish = async (e: React.MouseEvent) => {
     e.preventDefault();
     e.stopPropagation();
@@ -141,8 +211,9 @@ export default function ScrollOverlay() {
 
       if (!filePath) return;
 
+      // Stay visible until saved: an oversized capture asks how to split it
+      await finishScrollCapture(filePath);
       await getCurrentWindow().hide();
-      await invoke<string>("finish_scroll_capture", { path: filePath, crop: null });
       await getCurrentWindow().destroy();
     } catch (e) {
       console.error("[ScrollOverlay] handleFinish error:", e);
@@ -213,12 +284,20 @@ export default function ScrollOverlay() {
       {progress && (
         <div className="scroll-overlay-stats">
           {progress.frame_count} frames · {progress.total_height}px
+          {isListenerPaused && !isStopped && " · Paused"}
+          {isWindowChanged && !isStopped && " · Other window focused"}
+          {isLikelyComplete && !isStopped && " · End reached? Save to finish"}
         </div>
       )}
 
       <div className="scroll-overlay-actions">
         {!isStopped ? (
-          <button className="btn-stop" onPointerDown={handleStop as any}>Stop</button>
+          <>
+            <button className="btn-copy" onPointerDown={handleAutoScroll as any}>
+              {isAutoScrolling ? "Pause" : "Auto"}
+            </button>
+            <button className="btn-stop" onPointerDown={handleStop as any}>Stop</button>
+          </>
         ) : (
           <>
             <button className="btn-copy" onPointerDown={handleCopy as any}>Copy</button>
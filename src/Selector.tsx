@@ -7,6 +7,7 @@ import type Konva from "konva";
 import { AnnotationCanvas } from "./components/AnnotationCanvas";
 import { Magnifier } from "./components/Magnifier";
 import { useAnnotationEditor } from "./hooks/useAnnotationEditor";
+import { finishScrollCapture as saveScrollCapture } from "./lib/scrollExport";
 import type { AnnotationTool } from "./types/annotation";
 import { ANNOTATION_COLORS, STYLE_OPTIONS } from "./types/annotation";
 
@@ -26,6 +27,7 @@ interface WindowInfo {
   width: number;
   height: number;
   titlebar_height: number;
+  window_id?: number | null;
 }
 
 export default function Selector() {
@@ -42,6 +44,8 @@ export default function Selector() {
   const [currentTitlebarHeight, setCurrentTitlebarHeight] = useState(0);
   const [originalWindowInfo, setOriginalWindowInfo] = useState<WindowInfo | null>(null);
   const [scrollCaptureEnabled, setScrollCaptureEnabled] = useState(false);
+  // Regions queued with A for a batch capture (capture_all_regions)
+  const [batchRects, setBatchRects] = useState<SelectionRect[]>([]);
   const [screenSnapshot, setScreenSnapshot] = useState<string | null>(null);
   const [magnifierReady, setMagnifierReady] = useState(false);
   const [captionEnabled, setCaptionEnabled] = useState(() => {
@@ -154,7 +158,7 @@ export default function Selector() {
 
       // 保存并关闭
       setScrollCaptureActive(false);
-      await invoke("finish_scroll_capture", { path: filePath, crop: null });
+      await saveScrollCapture(filePath);
       await closeWindow();
     } catch (e) {
       console.error("[Selector] Failed to finish scroll capture:", e);
@@ -259,8 +263,48 @@ export default function Selector() {
     return () => document.removeEventListener("mousemove", handler);
   }, [isSelecting, showToolbar]);
 
+  // Queue the current selection and start drawing the next one
+  const addToBatch = useCallback(async () => {
+    if (!selectionRect || (mode !== "image" && mode !== "staticimage")) return;
+    const region = {
+      x: Math.round(selectionRect.x),
+      y: Math.round(selectionRect.y),
+      width: Math.round(selectionRect.w),
+      height: Math.round(selectionRect.h),
+    };
+    try {
+      // Drop whatever an earlier session left in the region list
+      if (batchRects.length === 0) await invoke("clear_regions");
+      await invoke("set_region", { region, append: true });
+    } catch (e) {
+      console.warn("[Selector] set_region rejected:", e);
+      return;
+    }
+    setBatchRects((prev) => [...prev, selectionRect]);
+    setSelectionRect(null);
+    setShowToolbar(false);
+    if (selectionRef.current) selectionRef.current.style.display = "none";
+  }, [selectionRect, mode, batchRects.length]);
+
+  // Save every queued region (plus the current selection) as numbered files
+  const captureBatch = useCallback(async () => {
+    if (selectionRect) await addToBatch();
+    const win = getCurrentWindow();
+    await win.hide();
+    try {
+      await invoke("capture_all_regions", { save: true });
+    } catch (e) {
+      console.error("[Selector] capture_all_regions failed:", e);
+    }
+    await win.close();
+  }, [selectionRect, addToBatch]);
+
   const doCapture = useCallback(async () => {
     console.log("[Selector] doCapture called, mode:", mode, "selectionRect:", selectionRect, "isEditing:", isEditing);
+    if (batchRects.length > 0 && !isEditing) {
+      await captureBatch();
+      return;
+    }
     if (!selectionRect) return;
 
     const region = {
@@ -270,7 +314,13 @@ export default function Selector() {
       height: Math.round(selectionRect.h),
     };
 
-    await invoke("set_region", { region });
+    try {
+      await invoke("set_region", { region });
+    } catch (e) {
+      // Below the configured minimum size: keep the selector open
+      console.warn("[Selector] set_region rejected:", e);
+      return;
+    }
 
     if (mode === "image" || mode === "staticimage") {
       const win = getCurrentWindow();
@@ -303,7 +353,9 @@ export default function Selector() {
       if (mode === "image") {
         await new Promise((r) => setTimeout(r, 50));
       }
-      await invoke("save_screenshot", { useCached: mode === "staticimage", captionMode: captionEnabled });
+      await invoke("save_screenshot", {
+        options: { useCached: mode === "staticimage", captionMode: captionEnabled },
+      });
       await win.close();
     } else if (mode === "gif") {
       await invoke("start_recording");
@@ -345,7 +397,7 @@ export default function Selector() {
         console.error("[Selector] Failed to start scroll capture:", e);
       }
     }
-  }, [selectionRect, mode, closeWindow, isEditing, editor.annotations.length, captionEnabled]);
+  }, [selectionRect, mode, closeWindow, isEditing, editor.annotations.length, captionEnabled, batchRects.length, captureBatch]);
 
   // Resize handle start
   const handleResizeStart = useCallback(
@@ -671,18 +723,12 @@ export default function Selector() {
 
   // Toggle between static and dynamic screenshot mode
   const toggleStaticMode = useCallback(async () => {
-    if (mode === "staticimage") {
-      // Static -> Dynamic: clear background and switch mode
-      await invoke("clear_screen_background");
-      setMode("image");
-    } else if (mode === "image") {
-      // Dynamic -> Static: set window background directly (GPU accelerated)
-      // This also updates cached_snapshot, so magnifier will use the frozen screen
-      const success = await invoke<boolean>("capture_screen_now");
-      if (success) {
-        setMode("staticimage");
-      }
-    }
+    if (mode !== "staticimage" && mode !== "image") return;
+    // Freezing sets the window background directly (GPU accelerated) and updates
+    // cached_snapshot, so the magnifier uses the frozen screen; other displays are frozen
+    // in the background for use_cached_display. The backend reports the resulting state.
+    const frozen = await invoke<boolean>("set_selector_frozen", { frozen: mode === "image" });
+    setMode(frozen ? "staticimage" : "image");
   }, [mode]);
 
   // Keyboard shortcuts
@@ -778,14 +824,16 @@ export default function Selector() {
         setExcludeTitlebar((prev) => !prev);
       } else if (e.key === "c" || e.key === "C") {
         setCaptionEnabled((prev) => !prev);
-      } else if (e.key === "Enter" && selectionRect) {
+      } else if ((e.key === "a" || e.key === "A") && selectionRect) {
+        await addToBatch();
+      } else if (e.key === "Enter" && (selectionRect || batchRects.length > 0)) {
         await doCapture();
       }
     };
 
     document.addEventListener("keydown", handleKeyDown);
     return () => document.removeEventListener("keydown", handleKeyDown);
-  }, [selectionRect, doCapture, closeWindow, scrollCaptureEnabled, mode, toggleStaticMode, isEditing, editor, exitEditMode, scrollCaptureActive, finishScrollCapture, cancelScrollCapture, showToolbar, mousePos]);
+  }, [selectionRect, doCapture, closeWindow, scrollCaptureEnabled, mode, toggleStaticMode, isEditing, editor, exitEditMode, scrollCaptureActive, finishScrollCapture, cancelScrollCapture, showToolbar, mousePos, addToBatch, batchRects.length]);
 
   const toolbarStyle: React.CSSProperties = (() => {
     if (!selectionRect) return {};
@@ -877,6 +925,15 @@ export default function Selector() {
           onColorChange={(color) => { currentColorRef.current = color; }}
         />
       )}
+      {batchRects.map((rect, i) => (
+        <div
+          key={i}
+          className="batch-region"
+          style={{ left: rect.x, top: rect.y, width: rect.w, height: rect.h }}
+        >
+          <span className="batch-region-index">{i + 1}</span>
+        </div>
+      ))}
       <div ref={selectionRef} className="selection" />
       <div ref={sizeRef} className="size-label" />
 
@@ -1176,6 +1233,18 @@ export default function Selector() {
           <div className="toolbar-section">
             <span className="toolbar-section-title">操作</span>
             <div className="toolbar-section-content">
+              {(mode === "image" || isStaticMode) && !isEditing && (
+                <button
+                  className="toolbar-btn has-tooltip"
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    addToBatch();
+                  }}
+                  data-tooltip={`加入批量 (A) - 已有 ${batchRects.length} 个，Enter 一起保存`}
+                >
+                  +
+                </button>
+              )}
               <button
                 className="toolbar-btn has-tooltip"
                 onClick={(e) => {
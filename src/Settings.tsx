@@ -17,8 +17,31 @@ interface AppConfig {
   scroll_capture_enabled: boolean;
   screenshot_preview_enabled: boolean;
   image_export_format: string;
+  restore_main_after_capture: boolean;
+  accessory_during_capture: boolean;
+  capture_sound_enabled: boolean;
+  capture_flash_enabled: boolean;
+  color_profile: "untagged" | "tag_srgb" | "convert_to_srgb";
+  auto_request_permission: boolean;
+  exclude_own_windows: boolean;
+  recording_frame_format: "rgba" | "rgb";
+  log_level: "off" | "error" | "warn" | "info" | "debug" | "trace";
+  log_to_file: boolean;
+  capture_content_area_only: boolean;
+  recording_shortcut_toggle: boolean;
 }
 
+type CaptureMode = "image" | "staticimage" | "gif" | "video" | "scroll";
+
+type ExactRegion = { x: number; y: number; width: number; height: number };
+
+const EXACT_REGION_FIELDS: [keyof ExactRegion, string][] = [
+  ["x", "X"],
+  ["y", "Y"],
+  ["width", "Width"],
+  ["height", "Height"],
+];
+
 type EditingState = {
   action: string;
   index: number; // -1 means adding new
@@ -32,7 +55,9 @@ const ACTION_LABELS: Record<string, string> = {
   video: "Record Video",
   scroll: "Scroll Capture",
   stop_scroll: "Stop Scroll (extra)",
+  toggle_scroll_listener: "Pause/Resume Scroll",
   show_main: "Open Main Panel",
+  repeat_capture: "Repeat Last Capture",
 };
 
 function formatShortcut(cfg: ShortcutConfig): string {
@@ -94,6 +119,9 @@ export default function Settings() {
   const [pendingShortcut, setPendingShortcut] = useState<{ modifiers: string[]; key: string } | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [debugInfo, setDebugInfo] = useState<string>("");
+  const [exactRegion, setExactRegion] = useState<ExactRegion>({ x: 0, y: 0, width: 800, height: 600 });
+  const [exactMode, setExactMode] = useState<CaptureMode>("image");
+  const [regionError, setRegionError] = useState<string | null>(null);
   const containerRef = useRef<HTMLDivElement>(null);
 
   // Load config on mount
@@ -245,6 +273,120 @@ export default function Settings() {
     }
   }, [config]);
 
+  const handleToggleRestoreMain = useCallback(async () => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_restore_main_after_capture", {
+        enabled: !config.restore_main_after_capture,
+      });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleToggleAccessoryDuringCapture = useCallback(async () => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_accessory_during_capture", {
+        enabled: !config.accessory_during_capture,
+      });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleCaptureFeedbackChange = useCallback(async (sound: boolean, flash: boolean) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_capture_feedback", { sound, flash });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleColorProfileChange = useCallback(async (profile: string) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_color_profile", { profile });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleAutoRequestPermissionChange = useCallback(async (enabled: boolean) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_auto_request_permission", { enabled });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleExcludeOwnWindowsChange = useCallback(async (enabled: boolean) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_exclude_own_windows", { enabled });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleRecordingFrameFormatChange = useCallback(async (format: string) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_recording_frame_format", { format });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleContentAreaOnlyChange = useCallback(async (enabled: boolean) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_capture_content_area_only", { enabled });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleRecordingShortcutToggleChange = useCallback(async (enabled: boolean) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_recording_shortcut_toggle", { enabled });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleLogLevelChange = useCallback(async (level: string) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_log_level", { level });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
+  const handleLogToFileChange = useCallback(async (enabled: boolean) => {
+    if (!config) return;
+    try {
+      const newConfig = await invoke<AppConfig>("set_log_to_file", { enabled });
+      setConfig(newConfig);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, [config]);
+
   const handleImageExportFormatChange = useCallback(async (format: string) => {
     if (!config) return;
     try {
@@ -255,6 +397,24 @@ export default function Settings() {
     }
   }, [config]);
 
+  const handleExactRegionField = useCallback((field: keyof ExactRegion, value: string) => {
+    setExactRegion((r) => ({ ...r, [field]: Math.round(Number(value) || 0) }));
+  }, []);
+
+  const handleCaptureExactRegion = useCallback(async () => {
+    setRegionError(null);
+    const win = getCurrentWindow();
+    // Keep this window out of the captured pixels
+    await win.hide();
+    try {
+      await invoke("set_region_exact", { ...exactRegion, mode: exactMode });
+    } catch (e) {
+      setRegionError(String(e));
+    } finally {
+      await win.show();
+    }
+  }, [exactRegion, exactMode]);
+
   const handleClose = useCallback(async () => {
     await getCurrentWindow().close();
   }, []);
@@ -263,7 +423,7 @@ export default function Settings() {
     return <div className="settings-container">Loading...</div>;
   }
 
-  const actions = ["screenshot_static", "screenshot", "gif", "stop_recording", "scroll", "stop_scroll", "video", "show_main"];
+  const actions = ["screenshot_static", "screenshot", "gif", "stop_recording", "scroll", "stop_scroll", "toggle_scroll_listener", "video", "repeat_capture", "show_main"];
 
   return (
     <div className="settings-container" ref={containerRef} tabIndex={-1}>
@@ -388,6 +548,143 @@ export default function Settings() {
                   <span className="switch-thumb" />
                 </button>
               </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Show Editor After Recording</span>
+                <button
+                  role="switch"
+                  aria-checked={config.restore_main_after_capture}
+                  className={`switch ${config.restore_main_after_capture ? "switch-on" : ""}`}
+                  onClick={handleToggleRestoreMain}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Hide Dashboard While Recording</span>
+                <button
+                  role="switch"
+                  aria-checked={config.accessory_during_capture}
+                  className={`switch ${config.accessory_during_capture ? "switch-on" : ""}`}
+                  onClick={handleToggleAccessoryDuringCapture}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Capture Sound</span>
+                <button
+                  role="switch"
+                  aria-checked={config.capture_sound_enabled}
+                  className={`switch ${config.capture_sound_enabled ? "switch-on" : ""}`}
+                  onClick={() => handleCaptureFeedbackChange(!config.capture_sound_enabled, config.capture_flash_enabled)}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Capture Flash</span>
+                <button
+                  role="switch"
+                  aria-checked={config.capture_flash_enabled}
+                  className={`switch ${config.capture_flash_enabled ? "switch-on" : ""}`}
+                  onClick={() => handleCaptureFeedbackChange(config.capture_sound_enabled, !config.capture_flash_enabled)}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Recording Frame Buffer</span>
+                <select
+                  className="setting-select"
+                  value={config.recording_frame_format}
+                  onChange={(e) => handleRecordingFrameFormatChange(e.target.value)}
+                >
+                  <option value="rgba">RGBA</option>
+                  <option value="rgb">RGB (less memory)</option>
+                </select>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Recording Shortcut Also Stops Recording</span>
+                <button
+                  role="switch"
+                  aria-checked={config.recording_shortcut_toggle}
+                  className={`switch ${config.recording_shortcut_toggle ? "switch-on" : ""}`}
+                  onClick={() => handleRecordingShortcutToggleChange(!config.recording_shortcut_toggle)}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Log Level</span>
+                <select
+                  className="setting-select"
+                  value={config.log_level}
+                  onChange={(e) => handleLogLevelChange(e.target.value)}
+                >
+                  <option value="off">Off</option>
+                  <option value="error">Error</option>
+                  <option value="warn">Warn</option>
+                  <option value="info">Info</option>
+                  <option value="debug">Debug</option>
+                  <option value="trace">Trace</option>
+                </select>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Write Log File</span>
+                <button
+                  role="switch"
+                  aria-checked={config.log_to_file}
+                  className={`switch ${config.log_to_file ? "switch-on" : ""}`}
+                  onClick={() => handleLogToFileChange(!config.log_to_file)}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Exclude Menu Bar and Dock from Full Screen</span>
+                <button
+                  role="switch"
+                  aria-checked={config.capture_content_area_only}
+                  className={`switch ${config.capture_content_area_only ? "switch-on" : ""}`}
+                  onClick={() => handleContentAreaOnlyChange(!config.capture_content_area_only)}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Hide Lovshot Windows in Static Captures</span>
+                <button
+                  role="switch"
+                  aria-checked={config.exclude_own_windows}
+                  className={`switch ${config.exclude_own_windows ? "switch-on" : ""}`}
+                  onClick={() => handleExcludeOwnWindowsChange(!config.exclude_own_windows)}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Prompt for Missing Permission</span>
+                <button
+                  role="switch"
+                  aria-checked={config.auto_request_permission}
+                  className={`switch ${config.auto_request_permission ? "switch-on" : ""}`}
+                  onClick={() => handleAutoRequestPermissionChange(!config.auto_request_permission)}
+                >
+                  <span className="switch-thumb" />
+                </button>
+              </div>
+              <div className="setting-row has-border">
+                <span className="setting-label">Screenshot Color Profile</span>
+                <select
+                  className="setting-select"
+                  value={config.color_profile}
+                  onChange={(e) => handleColorProfileChange(e.target.value)}
+                >
+                  <option value="untagged">Untagged</option>
+                  <option value="tag_srgb">Tag as sRGB</option>
+                  <option value="convert_to_srgb">Convert to sRGB</option>
+                </select>
+              </div>
               <div className="setting-row">
                 <span className="setting-label">Image Export Format</span>
                 <select
@@ -405,6 +702,46 @@ export default function Settings() {
           </AccordionContent>
         </AccordionItem>
 
+        <AccordionItem value="exact-region">
+          <AccordionTrigger>Capture Exact Region</AccordionTrigger>
+          <AccordionContent>
+            <div className="settings-card">
+              {EXACT_REGION_FIELDS.map(([field, label]) => (
+                <div className="setting-row has-border" key={field}>
+                  <span className="setting-label">{label}</span>
+                  <input
+                    type="number"
+                    className="setting-select"
+                    min={field === "width" || field === "height" ? 1 : undefined}
+                    value={exactRegion[field]}
+                    onChange={(e) => handleExactRegionField(field, e.target.value)}
+                  />
+                </div>
+              ))}
+              <div className="setting-row has-border">
+                <span className="setting-label">Mode</span>
+                <select
+                  className="setting-select"
+                  value={exactMode}
+                  onChange={(e) => setExactMode(e.target.value as CaptureMode)}
+                >
+                  <option value="image">Screenshot</option>
+                  <option value="gif">GIF</option>
+                  <option value="video">Video</option>
+                  <option value="scroll">Scroll Capture</option>
+                </select>
+              </div>
+              <div className="setting-row">
+                <span className="setting-label">Logical pixels, relative to the main display</span>
+                <button className="btn-small" onClick={handleCaptureExactRegion}>
+                  Capture
+                </button>
+              </div>
+              {regionError && <div className="error-message">{regionError}</div>}
+            </div>
+          </AccordionContent>
+        </AccordionItem>
+
         <AccordionItem value="advanced">
           <AccordionTrigger>Advanced</AccordionTrigger>
           <AccordionContent>
@@ -0,0 +1,37 @@
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
+
+interface ScrollExportResult {
+  paths: string[];
+  width: number;
+  height: number;
+  oversized: boolean;
+  max_width: number;
+  max_height: number;
+}
+
+/**
+ * Save the stitched scroll capture to `path`. If it is larger than the configured export
+ * limit, ask whether to split it into several files or keep a single (possibly
+ * unopenable) image. Returns the written paths.
+ */
+export async function finishScrollCapture(path: string): Promise<string[]> {
+  const result = await invoke<ScrollExportResult>("finish_scroll_capture", {
+    path,
+    crop: null,
+    oversize: "warn",
+  });
+  if (!result.oversized) return result.paths;
+
+  const split = await ask(
+    `The capture is ${result.width}×${result.height}px, larger than many viewers can open ` +
+      `(${result.max_width}×${result.max_height}px). Split it into several files?`,
+    { title: "Large Capture", kind: "warning", okLabel: "Split", cancelLabel: "Save as One File" },
+  );
+  const final = await invoke<ScrollExportResult>("finish_scroll_capture", {
+    path,
+    crop: null,
+    oversize: split ? "split" : "allow",
+  });
+  return final.paths;
+}
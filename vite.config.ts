@@ -24,6 +24,7 @@ export default defineConfig(async () => ({
         permission: resolve(__dirname, "permission.html"),
         preview: resolve(__dirname, "preview.html"),
         zoom: resolve(__dirname, "zoom.html"),
+        review: resolve(__dirname, "review.html"),
       },
     },
   },